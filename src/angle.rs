@@ -0,0 +1,238 @@
+use std::{
+    f32::consts::PI,
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+macro_rules! angle {
+    ($name:ident, $half_turn:expr, $suffix:expr) => {
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub f32);
+
+        impl $name {
+            pub const HALF_TURN: $name = $name($half_turn);
+            pub const FULL_TURN: $name = $name(2.0 * $half_turn);
+
+            pub fn zero() -> Self {
+                $name(0.0)
+            }
+
+            // Wraps into (-half turn, half turn].
+            pub fn wrap(&mut self) {
+                *self = self.get_wrap()
+            }
+
+            pub fn get_wrap(&self) -> Self {
+                let full = 2.0 * $half_turn;
+                let r = self.0 % full;
+                if r > $half_turn {
+                    $name(r - full)
+                } else if r <= -$half_turn {
+                    $name(r + full)
+                } else {
+                    $name(r)
+                }
+            }
+
+            // Wraps into [0, full turn).
+            pub fn norm(&mut self) {
+                *self = self.get_norm()
+            }
+
+            pub fn get_norm(&self) -> Self {
+                let full = 2.0 * $half_turn;
+                let r = self.0.rem_euclid(full);
+                $name(if r >= full { 0.0 } else { r })
+            }
+
+            // Signed angle of the shortest turn from self to rhs.
+            pub fn shortest_diff(&self, rhs: &$name) -> Self {
+                (*rhs - *self).get_wrap()
+            }
+
+            pub fn sin(&self) -> f32 {
+                Rad::from(*self).0.sin()
+            }
+
+            pub fn cos(&self) -> f32 {
+                Rad::from(*self).0.cos()
+            }
+
+            pub fn tan(&self) -> f32 {
+                Rad::from(*self).0.tan()
+            }
+
+            pub fn sin_cos(&self) -> (f32, f32) {
+                Rad::from(*self).0.sin_cos()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match f.precision() {
+                    Some(p) => write!(f, "{:.*}{}", p, self.0, $suffix),
+                    None => write!(f, "{}{}", self.0, $suffix),
+                }
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: $name) -> Self {
+                $name(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: $name) -> Self {
+                $name(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $name(-self.0)
+            }
+        }
+
+        impl Mul<f32> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f32) -> Self {
+                $name(self.0 * rhs)
+            }
+        }
+
+        impl Mul<$name> for f32 {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                $name(self * rhs.0)
+            }
+        }
+
+        impl MulAssign<f32> for $name {
+            fn mul_assign(&mut self, rhs: f32) {
+                self.0 *= rhs
+            }
+        }
+
+        impl Div<f32> for $name {
+            type Output = Self;
+            fn div(self, rhs: f32) -> Self {
+                $name(self.0 / rhs)
+            }
+        }
+
+        impl Div<$name> for $name {
+            type Output = f32;
+            fn div(self, rhs: $name) -> f32 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl DivAssign<f32> for $name {
+            fn div_assign(&mut self, rhs: f32) {
+                self.0 /= rhs
+            }
+        }
+    };
+}
+
+angle!(Rad, PI, " rad");
+angle!(Deg, 180.0, "°");
+
+impl From<Deg> for Rad {
+    fn from(d: Deg) -> Self {
+        Rad(d.0.to_radians())
+    }
+}
+
+impl From<Rad> for Deg {
+    fn from(r: Rad) -> Self {
+        Deg(r.0.to_degrees())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_keeps_the_upper_half_turn() {
+        assert_eq!(Deg(180.0).get_wrap(), Deg(180.0));
+        assert_eq!(Deg(-180.0).get_wrap(), Deg(180.0));
+        assert_eq!(Deg(540.0).get_wrap(), Deg(180.0));
+        assert_eq!(Deg(-540.0).get_wrap(), Deg(180.0));
+        assert_eq!(Rad(PI).get_wrap(), Rad(PI));
+        assert_eq!(Rad(-PI).get_wrap(), Rad(PI));
+        assert_eq!(Deg(-179.5).get_wrap(), Deg(-179.5));
+    }
+
+    #[test]
+    fn wrap_negative_and_whole_turns() {
+        assert_eq!(Deg(190.0).get_wrap(), Deg(-170.0));
+        assert_eq!(Deg(-190.0).get_wrap(), Deg(170.0));
+        assert_eq!(Deg(-725.0).get_wrap(), Deg(-5.0));
+        for turns in [-3.0, -1.0, 0.0, 1.0, 4.0] {
+            assert_eq!(Deg(360.0 * turns).get_wrap(), Deg(0.0));
+            assert_eq!(Deg(360.0 * turns + 30.0).get_wrap(), Deg(30.0));
+            assert!((Rad(2.0 * PI * turns + 1.0).get_wrap().0 - 1.0).abs() < 1e-5);
+        }
+        let mut d = Deg(270.0);
+        d.wrap();
+        assert_eq!(d, Deg(-90.0));
+    }
+
+    #[test]
+    fn norm_is_within_one_turn() {
+        assert_eq!(Deg(-90.0).get_norm(), Deg(270.0));
+        assert_eq!(Deg(360.0).get_norm(), Deg(0.0));
+        assert_eq!(Deg(-360.0).get_norm(), Deg(0.0));
+        assert_eq!(Deg(725.0).get_norm(), Deg(5.0));
+        assert_eq!(Deg(180.0).get_norm(), Deg(180.0));
+        // Rounds up to exactly one turn, which must come back as zero.
+        assert_eq!(Deg(-1e-6).get_norm(), Deg(0.0));
+        assert_eq!(Rad(-1e-8).get_norm(), Rad(0.0));
+        let mut r = Rad(-PI / 2.0);
+        r.norm();
+        assert!((r.0 - 1.5 * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn shortest_diff_crosses_the_seam() {
+        assert_eq!(Deg(170.0).shortest_diff(&Deg(-170.0)), Deg(20.0));
+        assert_eq!(Deg(-170.0).shortest_diff(&Deg(170.0)), Deg(-20.0));
+        assert_eq!(Deg(350.0).shortest_diff(&Deg(10.0)), Deg(20.0));
+        assert_eq!(Deg(10.0).shortest_diff(&Deg(350.0)), Deg(-20.0));
+        // Exactly opposite resolves to the positive half turn either way.
+        assert_eq!(Deg(0.0).shortest_diff(&Deg(180.0)), Deg(180.0));
+        assert_eq!(Deg(0.0).shortest_diff(&Deg(-180.0)), Deg(180.0));
+        assert!((Rad(3.0).shortest_diff(&Rad(-3.0)).0 - (2.0 * PI - 6.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn conversions_and_trig() {
+        assert_eq!(Rad::from(Deg(180.0)), Rad(PI));
+        assert_eq!(Deg::from(Rad::HALF_TURN), Deg::HALF_TURN);
+        assert_eq!(Deg::FULL_TURN, Deg(360.0));
+        let (s, c) = Deg(90.0).sin_cos();
+        assert!((s - 1.0).abs() < 1e-6 && c.abs() < 1e-6);
+        assert!((Deg(45.0).tan() - 1.0).abs() < 1e-6);
+        assert_eq!(Deg(90.0) / Deg(45.0), 2.0);
+        assert_eq!(format!("{:.1}", Deg(12.345)), "12.3°");
+        assert_eq!(Rad(0.5).to_string(), "0.5 rad");
+    }
+}
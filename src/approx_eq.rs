@@ -0,0 +1,297 @@
+use crate::{
+    mat3::Mat3,
+    mat4::Mat4,
+    quat::Quat,
+    util::{is_equal, is_equal_rel, is_equal_ulps, MATH_TOLERANCE},
+    vector2::Vector2,
+    vector3::Vector3,
+    vector3_wide::{Vector3x4, Vector3x8},
+    vector4::Vector4,
+};
+
+// == on every pmath type compares fields exactly. Comparisons with a
+// tolerance go through ApproxEq and the assert_approx_eq!, assert_relative_eq!
+// and assert_ulps_eq! macros. Vector3 and Vector4 used to compare within
+// MATH_TOLERANCE under ==; use approx_eq for that now.
+
+pub const DEFAULT_MAX_RELATIVE: f32 = f32::EPSILON;
+pub const DEFAULT_MAX_ULPS: u32 = 4;
+
+pub trait ApproxEq {
+    fn abs_eq(&self, rhs: &Self, epsilon: f32) -> bool;
+    fn rel_eq(&self, rhs: &Self, epsilon: f32, max_relative: f32) -> bool;
+    fn ulps_eq(&self, rhs: &Self, epsilon: f32, max_ulps: u32) -> bool;
+
+    fn approx_eq(&self, rhs: &Self) -> bool {
+        self.abs_eq(rhs, MATH_TOLERANCE)
+    }
+}
+
+// Equal values, including equal infinities, always compare equal. NaN never
+// does.
+impl ApproxEq for f32 {
+    fn abs_eq(&self, rhs: &Self, epsilon: f32) -> bool {
+        self == rhs || is_equal(*self, *rhs, epsilon)
+    }
+
+    fn rel_eq(&self, rhs: &Self, epsilon: f32, max_relative: f32) -> bool {
+        is_equal_rel(*self, *rhs, epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, rhs: &Self, epsilon: f32, max_ulps: u32) -> bool {
+        is_equal_ulps(*self, *rhs, epsilon, max_ulps)
+    }
+}
+
+// Component-wise comparison, with $components yielding an iterator of f32s.
+macro_rules! impl_approx_eq {
+    ($ty:ty, |$v:ident| $components:expr) => {
+        impl ApproxEq for $ty {
+            fn abs_eq(&self, rhs: &Self, epsilon: f32) -> bool {
+                fn components($v: &$ty) -> impl Iterator<Item = &f32> + '_ {
+                    $components
+                }
+                components(self).zip(components(rhs)).all(|(a, b)| a.abs_eq(b, epsilon))
+            }
+
+            fn rel_eq(&self, rhs: &Self, epsilon: f32, max_relative: f32) -> bool {
+                fn components($v: &$ty) -> impl Iterator<Item = &f32> + '_ {
+                    $components
+                }
+                components(self)
+                    .zip(components(rhs))
+                    .all(|(a, b)| a.rel_eq(b, epsilon, max_relative))
+            }
+
+            fn ulps_eq(&self, rhs: &Self, epsilon: f32, max_ulps: u32) -> bool {
+                fn components($v: &$ty) -> impl Iterator<Item = &f32> + '_ {
+                    $components
+                }
+                components(self)
+                    .zip(components(rhs))
+                    .all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+            }
+        }
+    };
+}
+
+impl_approx_eq!(Vector2, |v| v.iter());
+impl_approx_eq!(Vector3, |v| v.iter());
+impl_approx_eq!(Vector4, |v| v.iter());
+impl_approx_eq!(Mat3, |m| m.iter());
+impl_approx_eq!(Mat4, |m| m.iter());
+impl_approx_eq!(Vector3x4, |v| v.x.iter().chain(v.y.iter()).chain(v.z.iter()));
+impl_approx_eq!(Vector3x8, |v| v.x.iter().chain(v.y.iter()).chain(v.z.iter()));
+
+// q and -q describe the same rotation, so either sign of rhs is accepted.
+impl ApproxEq for Quat {
+    fn abs_eq(&self, rhs: &Self, epsilon: f32) -> bool {
+        let neg: [f32; 4] = (*rhs.as_array()).map(|v| -v);
+        self.as_array().abs_eq(rhs.as_array(), epsilon) || self.as_array().abs_eq(&neg, epsilon)
+    }
+
+    fn rel_eq(&self, rhs: &Self, epsilon: f32, max_relative: f32) -> bool {
+        let neg: [f32; 4] = (*rhs.as_array()).map(|v| -v);
+        self.as_array().rel_eq(rhs.as_array(), epsilon, max_relative)
+            || self.as_array().rel_eq(&neg, epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, rhs: &Self, epsilon: f32, max_ulps: u32) -> bool {
+        let neg: [f32; 4] = (*rhs.as_array()).map(|v| -v);
+        self.as_array().ulps_eq(rhs.as_array(), epsilon, max_ulps)
+            || self.as_array().ulps_eq(&neg, epsilon, max_ulps)
+    }
+}
+
+impl<T: ApproxEq, const N: usize> ApproxEq for [T; N] {
+    fn abs_eq(&self, rhs: &Self, epsilon: f32) -> bool {
+        self.iter().zip(rhs).all(|(a, b)| a.abs_eq(b, epsilon))
+    }
+
+    fn rel_eq(&self, rhs: &Self, epsilon: f32, max_relative: f32) -> bool {
+        self.iter().zip(rhs).all(|(a, b)| a.rel_eq(b, epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, rhs: &Self, epsilon: f32, max_ulps: u32) -> bool {
+        self.iter().zip(rhs).all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {
+        $crate::assert_approx_eq!($a, $b, $crate::util::MATH_TOLERANCE)
+    };
+    ($a:expr, $b:expr, $epsilon:expr) => {{
+        let (a, b) = (&$a, &$b);
+        if !$crate::approx_eq::ApproxEq::abs_eq(a, b, $epsilon) {
+            panic!(
+                "assertion `left ≈ right` failed (epsilon = {:?})\n  left: {:?}\n right: {:?}",
+                $epsilon, a, b
+            );
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! assert_approx_ne {
+    ($a:expr, $b:expr) => {
+        $crate::assert_approx_ne!($a, $b, $crate::util::MATH_TOLERANCE)
+    };
+    ($a:expr, $b:expr, $epsilon:expr) => {{
+        let (a, b) = (&$a, &$b);
+        if $crate::approx_eq::ApproxEq::abs_eq(a, b, $epsilon) {
+            panic!(
+                "assertion `left ≉ right` failed (epsilon = {:?})\n  left: {:?}\n right: {:?}",
+                $epsilon, a, b
+            );
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! assert_relative_eq {
+    ($a:expr, $b:expr) => {
+        $crate::assert_relative_eq!($a, $b, $crate::approx_eq::DEFAULT_MAX_RELATIVE)
+    };
+    ($a:expr, $b:expr, $max_relative:expr) => {{
+        let (a, b) = (&$a, &$b);
+        if !$crate::approx_eq::ApproxEq::rel_eq(a, b, f32::EPSILON, $max_relative) {
+            panic!(
+                "assertion `left ≈ right` failed (max_relative = {:?})\n  left: {:?}\n right: {:?}",
+                $max_relative, a, b
+            );
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! assert_ulps_eq {
+    ($a:expr, $b:expr) => {
+        $crate::assert_ulps_eq!($a, $b, $crate::approx_eq::DEFAULT_MAX_ULPS)
+    };
+    ($a:expr, $b:expr, $max_ulps:expr) => {{
+        let (a, b) = (&$a, &$b);
+        if !$crate::approx_eq::ApproxEq::ulps_eq(a, b, f32::EPSILON, $max_ulps) {
+            panic!(
+                "assertion `left ≈ right` failed (max_ulps = {:?})\n  left: {:?}\n right: {:?}",
+                $max_ulps, a, b
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_ULP_UP: f32 = 1.0 + f32::EPSILON;
+
+    #[test]
+    fn abs_edge_cases() {
+        assert!(1.0f32.abs_eq(&1.0009, MATH_TOLERANCE));
+        assert!(!1.0f32.abs_eq(&1.002, MATH_TOLERANCE));
+        assert!(0.0f32.abs_eq(&-0.0, 0.0));
+        assert!(f32::INFINITY.abs_eq(&f32::INFINITY, 0.0));
+        assert!(!f32::INFINITY.abs_eq(&f32::NEG_INFINITY, f32::MAX));
+        assert!(!f32::INFINITY.abs_eq(&f32::MAX, 1.0));
+        assert!(!f32::NAN.abs_eq(&f32::NAN, f32::INFINITY));
+        assert!(!1.0f32.abs_eq(&f32::NAN, f32::INFINITY));
+    }
+
+    #[test]
+    fn rel_edge_cases() {
+        assert!(1000.0f32.rel_eq(&1000.0001, 0.0, 1e-6));
+        assert!(!1000.0f32.rel_eq(&1000.1, 0.0, 1e-6));
+        // Near zero the relative bound shrinks to nothing, so epsilon takes over.
+        assert!(!1e-30f32.rel_eq(&-1e-30, 0.0, 0.5));
+        assert!(1e-30f32.rel_eq(&-1e-30, f32::EPSILON, 0.5));
+        assert!(0.0f32.rel_eq(&-0.0, 0.0, 0.0));
+        assert!(f32::NEG_INFINITY.rel_eq(&f32::NEG_INFINITY, 0.0, 0.0));
+        assert!(!f32::INFINITY.rel_eq(&f32::MAX, 0.0, 1.0));
+        assert!(!f32::NAN.rel_eq(&f32::NAN, f32::INFINITY, f32::INFINITY));
+    }
+
+    #[test]
+    fn ulps_edge_cases() {
+        assert!(1.0f32.ulps_eq(&ONE_ULP_UP, 0.0, 1));
+        assert!(!1.0f32.ulps_eq(&(1.0 + 5.0 * f32::EPSILON), 0.0, 4));
+        assert!(0.0f32.ulps_eq(&-0.0, 0.0, 0));
+        // The smallest subnormals are one step apart in value but on opposite
+        // sides of zero in bits, so only epsilon can join them.
+        let tiny = f32::from_bits(1);
+        assert!(!tiny.ulps_eq(&-tiny, 0.0, 4));
+        assert!(tiny.ulps_eq(&-tiny, f32::EPSILON, 4));
+        assert!(f32::INFINITY.ulps_eq(&f32::INFINITY, 0.0, 0));
+        assert!(!f32::NAN.ulps_eq(&f32::NAN, f32::INFINITY, u32::MAX));
+    }
+
+    #[test]
+    fn components_must_all_match() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert!(v.abs_eq(&Vector3::new(1.0005, 2.0, 2.9995), MATH_TOLERANCE));
+        assert!(!v.abs_eq(&Vector3::new(1.0, 2.0, f32::NAN), f32::INFINITY));
+        assert!(!v.ulps_eq(&Vector3::new(1.0, ONE_ULP_UP * 2.0, 3.5), 0.0, 4));
+        let w = Vector3x4::splat(&v);
+        let mut u = w;
+        u.z[3] = 3.1;
+        assert!(w.approx_eq(&w) && !w.approx_eq(&u));
+        assert!([v, v].abs_eq(&[v, Vector3::new(1.0, 2.0, 3.0004)], MATH_TOLERANCE));
+    }
+
+    #[test]
+    fn quat_accepts_either_sign() {
+        let q = Quat::from([0.1, -0.2, 0.3, 0.927]);
+        let neg = Quat::from(q.as_array().map(|v| -v));
+        assert_ne!(q, neg);
+        assert!(q.abs_eq(&neg, 0.0));
+        assert!(q.rel_eq(&neg, 0.0, 0.0));
+        assert!(q.ulps_eq(&neg, 0.0, 0));
+        // Mixed signs are a different rotation.
+        let mixed = Quat::from([-0.1, -0.2, 0.3, 0.927]);
+        assert!(!q.abs_eq(&mixed, 0.1));
+    }
+
+    #[test]
+    fn eq_is_exact() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_ne!(v, Vector3::new(1.0, 2.0, 3.0001));
+        assert_ne!(Vector4::new(1.0, 2.0, 3.0, 4.0), Vector4::new(1.0, 2.0, 3.0, 4.0001));
+        assert_eq!(Vector4::new(0.0, 0.0, 0.0, 0.0), Vector4::new(-0.0, 0.0, 0.0, 0.0));
+        crate::assert_approx_eq!(v, Vector3::new(1.0, 2.0, 3.0001));
+    }
+
+    #[test]
+    fn macros_pass() {
+        crate::assert_approx_eq!(Mat4::identity(), Mat4::identity());
+        crate::assert_approx_eq!(1.0f32, 1.5, 0.5);
+        crate::assert_approx_ne!(1.0f32, 1.1);
+        crate::assert_relative_eq!(1e6f32, 1e6 + 0.1, 1e-6);
+        crate::assert_ulps_eq!(1.0f32, ONE_ULP_UP);
+        crate::assert_ulps_eq!(Vector2::new(1.0, 0.0), Vector2::new(ONE_ULP_UP, -0.0), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "epsilon = 0.001")]
+    fn assert_approx_eq_panics() {
+        crate::assert_approx_eq!(Vector2::new(1.0, 2.0), Vector2::new(1.0, 2.01));
+    }
+
+    #[test]
+    #[should_panic(expected = "left ≉ right")]
+    fn assert_approx_ne_panics() {
+        crate::assert_approx_ne!(Vector2::new(1.0, 2.0), Vector2::new(1.0, 2.0001));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_relative")]
+    fn assert_relative_eq_panics() {
+        crate::assert_relative_eq!(1.0f32, 1.001, 1e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_ulps = 4")]
+    fn assert_ulps_eq_panics() {
+        crate::assert_ulps_eq!(f32::NAN, f32::NAN);
+    }
+}
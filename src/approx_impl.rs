@@ -0,0 +1,52 @@
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use crate::{
+    approx_eq::{ApproxEq, DEFAULT_MAX_RELATIVE, DEFAULT_MAX_ULPS},
+    mat3::Mat3,
+    mat4::Mat4,
+    quat::Quat,
+    vector2::Vector2,
+    vector3::Vector3,
+    vector3_wide::{Vector3x4, Vector3x8},
+    vector4::Vector4,
+};
+
+macro_rules! impl_approx {
+    ($($ty:ty),*) => {
+        $(
+            impl AbsDiffEq for $ty {
+                type Epsilon = f32;
+
+                fn default_epsilon() -> f32 {
+                    f32::EPSILON
+                }
+
+                fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+                    ApproxEq::abs_eq(self, other, epsilon)
+                }
+            }
+
+            impl RelativeEq for $ty {
+                fn default_max_relative() -> f32 {
+                    DEFAULT_MAX_RELATIVE
+                }
+
+                fn relative_eq(&self, other: &Self, epsilon: f32, max_relative: f32) -> bool {
+                    ApproxEq::rel_eq(self, other, epsilon, max_relative)
+                }
+            }
+
+            impl UlpsEq for $ty {
+                fn default_max_ulps() -> u32 {
+                    DEFAULT_MAX_ULPS
+                }
+
+                fn ulps_eq(&self, other: &Self, epsilon: f32, max_ulps: u32) -> bool {
+                    ApproxEq::ulps_eq(self, other, epsilon, max_ulps)
+                }
+            }
+        )*
+    };
+}
+
+impl_approx!(Vector2, Vector3, Vector4, Quat, Mat3, Mat4, Vector3x4, Vector3x8);
@@ -0,0 +1,102 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::vector3::Vector3;
+
+#[cfg(feature = "rayon")]
+const PAR_CHUNK: usize = 4096;
+
+// Runs kernel over matching src/dst blocks, split across the rayon pool
+// when the feature is enabled.
+pub(crate) fn map_slice<F>(src: &[Vector3], dst: &mut [Vector3], kernel: F)
+where
+    F: Fn(&[Vector3], &mut [Vector3]) + Send + Sync,
+{
+    assert_eq!(src.len(), dst.len(), "source and destination slices must be the same length");
+    #[cfg(feature = "rayon")]
+    src.par_chunks(PAR_CHUNK)
+        .zip(dst.par_chunks_mut(PAR_CHUNK))
+        .for_each(|(s, d)| kernel(s, d));
+    #[cfg(not(feature = "rayon"))]
+    kernel(src, dst);
+}
+
+pub(crate) fn map_slice_mut<F>(v: &mut [Vector3], kernel: F)
+where
+    F: Fn(&mut [Vector3]) + Send + Sync,
+{
+    #[cfg(feature = "rayon")]
+    v.par_chunks_mut(PAR_CHUNK).for_each(&kernel);
+    #[cfg(not(feature = "rayon"))]
+    kernel(v);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{angle::Deg, mat4::Mat4, quat::Quat, test_util::Rng, vector3::Vector3};
+
+    // Around the Vector3x8 lane count and across the rayon chunk size, with
+    // lengths that leave a partial last block.
+    const LENGTHS: [usize; 7] = [0, 1, 7, 8, 13, 4099, 3 * 4096 + 5];
+
+    fn components(v: &Vector3) -> [f32; 3] {
+        [v.x, v.y, v.z]
+    }
+
+    fn points(rng: &mut Rng, n: usize) -> Vec<Vector3> {
+        (0..n).map(|_| rng.vec3()).collect()
+    }
+
+    #[test]
+    fn rotate_slice_matches_lqcvq() {
+        let mut rng = Rng(28);
+        for n in LENGTHS {
+            let q = Quat::from_axis_angle(&rng.vec3(), Deg(rng.next() * 18.0));
+            let src = points(&mut rng, n);
+            let mut dst = vec![Vector3::zero(); n];
+            q.rotate_slice(&src, &mut dst);
+            for (s, d) in src.iter().zip(&dst) {
+                assert_eq!(components(d), components(&q.lqcvq(s)), "n = {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn normalize_slice_matches_norm() {
+        let mut rng = Rng(29);
+        for n in LENGTHS {
+            let src = points(&mut rng, n);
+            let mut v = src.clone();
+            Vector3::normalize_slice(&mut v);
+            for (s, d) in src.iter().zip(&v) {
+                assert_eq!(components(d), components(&s.get_norm()), "n = {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn transform_slices_match_per_element() {
+        let mut rng = Rng(30);
+        for n in LENGTHS {
+            let m = rng.mat4();
+            let src = points(&mut rng, n);
+            let mut dst = vec![Vector3::zero(); n];
+            m.transform_points(&src, &mut dst);
+            for (s, d) in src.iter().zip(&dst) {
+                assert_eq!(components(d), components(&m.transform_point(s)), "n = {n}");
+            }
+            m.transform_vectors(&src, &mut dst);
+            for (s, d) in src.iter().zip(&dst) {
+                assert_eq!(components(d), components(&m.transform_vector(s)), "n = {n}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn mismatched_lengths_panic() {
+        let src = vec![Vector3::zero(); 4];
+        let mut dst = vec![Vector3::zero(); 3];
+        Mat4::identity().transform_points(&src, &mut dst);
+    }
+}
@@ -0,0 +1,72 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    mat3::Mat3,
+    mat4::Mat4,
+    quat::Quat,
+    vector2::Vector2,
+    vector3::Vector3,
+    vector3_wide::{Vector3x4, Vector3x8},
+    vector4::Vector4,
+};
+
+// SAFETY: all of these are repr(C) structs made only of f32 fields, so any
+// bit pattern is valid and all-zero is a valid value. None has padding: the
+// scalar types assert their size next to their definitions, and the wide
+// types, whose sizes must also be multiples of their 16 and 32 byte
+// alignment, are checked below.
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(
+            unsafe impl Zeroable for $ty {}
+            unsafe impl Pod for $ty {}
+        )*
+    };
+}
+
+const _: () = assert!(std::mem::size_of::<Vector3x4>() == std::mem::size_of::<[f32; 12]>());
+const _: () = assert!(std::mem::size_of::<Vector3x8>() == std::mem::size_of::<[f32; 24]>());
+
+impl_pod!(Vector2, Vector3, Vector4, Quat, Mat3, Mat4, Vector3x4, Vector3x8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slices_round_trip_through_f32() {
+        let points = vec![Vector3::new(1.0, 2.0, 3.0), Vector3::new(-4.0, 5.5, 0.25)];
+        let floats: &[f32] = bytemuck::cast_slice(&points);
+        assert_eq!(floats, [1.0, 2.0, 3.0, -4.0, 5.5, 0.25]);
+        let back: &[Vector3] = bytemuck::cast_slice(floats);
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[1].as_array(), points[1].as_array());
+    }
+
+    #[test]
+    fn values_round_trip_through_arrays() {
+        let m = Mat4::from(std::array::from_fn::<f32, 16, _>(|i| i as f32));
+        let a: [f32; 16] = bytemuck::cast(m);
+        assert_eq!(&a, m.as_array());
+        assert_eq!(bytemuck::cast::<_, Mat4>(a), m);
+
+        let q = Quat::from([0.5, -0.5, 0.25, 1.0]);
+        assert_eq!(bytemuck::cast::<_, [f32; 4]>(q), [0.5, -0.5, 0.25, 1.0]);
+        let v = Vector2::new(3.0, -1.0);
+        assert_eq!(bytemuck::bytes_of(&v), [3.0f32.to_ne_bytes(), (-1.0f32).to_ne_bytes()].concat());
+
+        // Structure of arrays: every x lane, then every y, then every z.
+        let w: Vector3x4 = bytemuck::cast(std::array::from_fn::<f32, 12, _>(|i| i as f32));
+        assert_eq!((w.x, w.y, w.z), ([0.0, 1.0, 2.0, 3.0], [4.0, 5.0, 6.0, 7.0], [8.0, 9.0, 10.0, 11.0]));
+        let w8: Vector3x8 = bytemuck::cast(std::array::from_fn::<f32, 24, _>(|i| i as f32));
+        assert_eq!(w8.get_lane(7), Vector3::new(7.0, 15.0, 23.0));
+    }
+
+    #[test]
+    fn zeroed_is_zero() {
+        assert_eq!(Mat4::zeroed(), Mat4::zero());
+        assert_eq!(Mat3::zeroed(), Mat3::from([0.0; 9]));
+        assert_eq!(Vector4::zeroed().as_array(), &[0.0; 4]);
+        assert_eq!(Vector3x8::zeroed(), Vector3x8::zero());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{mat3::Mat3, mat4::Mat4, quat::Quat, vector2::Vector2, vector3::Vector3, vector4::Vector4};
+
+// cgmath matrices are column-major and multiply column vectors (M * v), so
+// each pmath row becomes a cgmath column.
+
+impl From<cgmath::Vector2<f32>> for Vector2 {
+    fn from(v: cgmath::Vector2<f32>) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector2> for cgmath::Vector2<f32> {
+    fn from(v: Vector2) -> Self {
+        cgmath::Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<cgmath::Vector3<f32>> for Vector3 {
+    fn from(v: cgmath::Vector3<f32>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for cgmath::Vector3<f32> {
+    fn from(v: Vector3) -> Self {
+        cgmath::Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<cgmath::Vector4<f32>> for Vector4 {
+    fn from(v: cgmath::Vector4<f32>) -> Self {
+        Vector4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Vector4> for cgmath::Vector4<f32> {
+    fn from(v: Vector4) -> Self {
+        cgmath::Vector4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<cgmath::Quaternion<f32>> for Quat {
+    fn from(q: cgmath::Quaternion<f32>) -> Self {
+        Quat { x: q.v.x, y: q.v.y, z: q.v.z, real: q.s }
+    }
+}
+
+impl From<Quat> for cgmath::Quaternion<f32> {
+    fn from(q: Quat) -> Self {
+        cgmath::Quaternion::new(q.real, q.x, q.y, q.z)
+    }
+}
+
+impl From<cgmath::Matrix3<f32>> for Mat3 {
+    fn from(m: cgmath::Matrix3<f32>) -> Self {
+        let cols: &[f32; 9] = m.as_ref();
+        let mut out = Mat3::zero();
+        *out.as_mut_array() = *cols;
+        out
+    }
+}
+
+impl From<Mat3> for cgmath::Matrix3<f32> {
+    fn from(m: Mat3) -> Self {
+        *<&cgmath::Matrix3<f32>>::from(m.as_array())
+    }
+}
+
+impl From<cgmath::Matrix4<f32>> for Mat4 {
+    fn from(m: cgmath::Matrix4<f32>) -> Self {
+        let cols: &[f32; 16] = m.as_ref();
+        let mut out = Mat4::zero();
+        *out.as_mut_array() = *cols;
+        out
+    }
+}
+
+impl From<Mat4> for cgmath::Matrix4<f32> {
+    fn from(m: Mat4) -> Self {
+        *<&cgmath::Matrix4<f32>>::from(m.as_array())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Deg, test_util::Rng};
+
+    // A general matrix, so a transposed conversion moves every off-diagonal term.
+    #[test]
+    fn matrices_transform_like_cgmath() {
+        let mut rng = Rng(36);
+        for _ in 0..20 {
+            let m = rng.mat4();
+            let v = rng.vec4();
+            let c = cgmath::Matrix4::from(m);
+            crate::assert_approx_eq!(v * m, Vector4::from(c * cgmath::Vector4::from(v)), 1e-3);
+            assert_eq!(Mat4::from(c), m);
+
+            let m3 = Mat3::from(std::array::from_fn::<f32, 9, _>(|_| rng.next()));
+            let p = rng.vec3();
+            let c3 = cgmath::Matrix3::from(m3);
+            crate::assert_approx_eq!(p * m3, Vector3::from(c3 * cgmath::Vector3::from(p)), 1e-3);
+            assert_eq!(Mat3::from(c3), m3);
+        }
+    }
+
+    #[test]
+    fn quats_rotate_like_cgmath() {
+        let mut rng = Rng(37);
+        for _ in 0..20 {
+            let q = Quat::from_axis_angle(&rng.vec3(), Deg(rng.next() * 18.0));
+            let p = rng.vec3();
+            let c = cgmath::Quaternion::from(q);
+            crate::assert_approx_eq!(p * q, Vector3::from(c * cgmath::Vector3::from(p)), 1e-3);
+            assert_eq!(Quat::from(c), q);
+        }
+    }
+}
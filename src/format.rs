@@ -0,0 +1,155 @@
+use std::{error::Error, fmt, num::ParseFloatError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidNumber(ParseFloatError),
+    WrongCount { expected: usize, found: usize },
+    UnbalancedBrackets,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidNumber(e) => write!(f, "invalid component: {}", e),
+            ParseError::WrongCount { expected, found } => {
+                write!(f, "expected {} components, found {}", expected, found)
+            }
+            ParseError::UnbalancedBrackets => write!(f, "unbalanced or nested brackets"),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidNumber(e) => Some(e),
+            ParseError::WrongCount { .. } | ParseError::UnbalancedBrackets => None,
+        }
+    }
+}
+
+impl From<ParseFloatError> for ParseError {
+    fn from(e: ParseFloatError) -> Self {
+        ParseError::InvalidNumber(e)
+    }
+}
+
+fn fmt_component(f: &fmt::Formatter<'_>, v: f32) -> String {
+    match f.precision() {
+        Some(p) => format!("{:.*}", p, v),
+        None => format!("{}", v),
+    }
+}
+
+// Writes "(a, b, c)" honoring the formatter's precision.
+pub(crate) fn fmt_components(f: &mut fmt::Formatter<'_>, values: &[f32]) -> fmt::Result {
+    let parts: Vec<String> = values.iter().map(|v| fmt_component(f, *v)).collect();
+    write!(f, "({})", parts.join(", "))
+}
+
+// Writes one "[a, b, c]" line per row with each column right-aligned.
+pub(crate) fn fmt_rows(f: &mut fmt::Formatter<'_>, values: &[f32], n: usize) -> fmt::Result {
+    let parts: Vec<String> = values.iter().map(|v| fmt_component(f, *v)).collect();
+    let widths: Vec<usize> = (0..n)
+        .map(|c| parts.iter().skip(c).step_by(n).map(|s| s.len()).max().unwrap_or(0))
+        .collect();
+    for (r, row) in parts.chunks(n).enumerate() {
+        if r > 0 {
+            writeln!(f)?;
+        }
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(s, w)| format!("{:>1$}", s, w))
+            .collect();
+        write!(f, "[{}]", cells.join(", "))?;
+    }
+    Ok(())
+}
+
+// Parses N numbers separated by commas or whitespace. Runs of numbers may be
+// wrapped in one level of () or [], so the output of both fmt_components and
+// fmt_rows reads back.
+pub(crate) fn parse_components<const N: usize>(s: &str) -> Result<[f32; N], ParseError> {
+    let mut open = None;
+    for c in s.chars() {
+        open = match (open, c) {
+            (None, '(' | '[') => Some(c),
+            (Some('('), ')') | (Some('['), ']') => None,
+            (_, '(' | '[' | ')' | ']') => return Err(ParseError::UnbalancedBrackets),
+            _ => open,
+        };
+    }
+    if open.is_some() {
+        return Err(ParseError::UnbalancedBrackets);
+    }
+    let values = s
+        .split(|c: char| c == ',' || c.is_whitespace() || "()[]".contains(c))
+        .filter(|t| !t.is_empty())
+        .map(|t| t.parse::<f32>())
+        .collect::<Result<Vec<f32>, _>>()?;
+    values.try_into().map_err(|v: Vec<f32>| ParseError::WrongCount {
+        expected: N,
+        found: v.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mat3::Mat3, mat4::Mat4, quat::Quat, vector2::Vector2, vector3::Vector3};
+
+    #[test]
+    fn display_round_trips() {
+        let v = Vector3::new(1.5, -0.25, 1e-7);
+        assert_eq!(v.to_string(), "(1.5, -0.25, 0.0000001)");
+        assert_eq!(v.to_string().parse::<Vector3>().unwrap().as_array(), v.as_array());
+
+        let q = Quat::from([0.1, -0.2, 0.3, 0.9]);
+        assert_eq!(q.to_string().parse::<Quat>(), Ok(q));
+
+        let m = Mat4::from(std::array::from_fn::<f32, 16, _>(|i| i as f32 * -1.25));
+        assert_eq!(m.to_string().parse::<Mat4>(), Ok(m));
+        let m3 = Mat3::from([1.0, 22.5, -3.0, 4.0, 5.0, 6.0, 7.0, 8.0, -900.0]);
+        assert_eq!(m3.to_string(), "[1, 22.5,   -3]\n[4,    5,    6]\n[7,    8, -900]");
+        assert_eq!(m3.to_string().parse::<Mat3>(), Ok(m3));
+    }
+
+    #[test]
+    fn precision_is_honored_and_reads_back() {
+        let v = Vector2::new(1.0 / 3.0, 2.0);
+        assert_eq!(format!("{v:.2}"), "(0.33, 2.00)");
+        assert_eq!(format!("{v:.2}").parse::<Vector2>(), Ok(Vector2::new(0.33, 2.0)));
+    }
+
+    #[test]
+    fn separators_and_brackets() {
+        assert_eq!(parse_components::<3>("1 2 3"), Ok([1.0, 2.0, 3.0]));
+        assert_eq!(parse_components::<3>(" [1,2]\t(3) "), Ok([1.0, 2.0, 3.0]));
+        assert_eq!(parse_components::<2>("(inf, -0)"), Ok([f32::INFINITY, -0.0]));
+        for s in ["((1, 2))", "[1, (2)]", "(1, 2", "1, 2)", "(1, 2]", ")1, 2("] {
+            assert_eq!(parse_components::<2>(s), Err(ParseError::UnbalancedBrackets), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn wrong_count() {
+        assert_eq!(parse_components::<3>(""), Err(ParseError::WrongCount { expected: 3, found: 0 }));
+        assert_eq!(parse_components::<3>("()"), Err(ParseError::WrongCount { expected: 3, found: 0 }));
+        assert_eq!(parse_components::<3>("(1, 2)"), Err(ParseError::WrongCount { expected: 3, found: 2 }));
+        assert_eq!(
+            "(1, 2, 3, 4)".parse::<Vector3>(),
+            Err(ParseError::WrongCount { expected: 3, found: 4 })
+        );
+    }
+
+    #[test]
+    fn non_numeric_tokens() {
+        for s in ["(1, x, 3)", "(1, 2, 3f)", "(1; 2; 3)", "(1, 2, --3)"] {
+            assert!(matches!(parse_components::<3>(s), Err(ParseError::InvalidNumber(_))), "{s:?}");
+        }
+        let err = parse_components::<3>("(1, x, 3)").unwrap_err();
+        assert!(err.to_string().starts_with("invalid component"));
+        assert!(err.source().is_some());
+    }
+}
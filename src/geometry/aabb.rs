@@ -0,0 +1,353 @@
+use crate::{mat4::Mat4, vector2::Vector2, vector3::Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb3 {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb2 {
+    pub min: Vector2,
+    pub max: Vector2,
+}
+
+impl Aabb3 {
+    pub fn new(min: Vector3, max: Vector3) -> Self {
+        Self { min, max }
+    }
+
+    // Inverted box that any union or expansion replaces.
+    pub fn empty() -> Self {
+        Self {
+            min: Vector3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+            max: Vector3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+        }
+    }
+
+    pub fn from_center_extents(center: &Vector3, extents: &Vector3) -> Self {
+        Self {
+            min: *center - *extents,
+            max: *center + *extents,
+        }
+    }
+
+    pub fn from_points(points: &[Vector3]) -> Self {
+        let mut out = Self::empty();
+        for p in points {
+            out.expand_to_point(p);
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    pub fn get_center(&self) -> Vector3 {
+        (self.min + self.max) * 0.5
+    }
+
+    // Half the size along each axis.
+    pub fn get_extents(&self) -> Vector3 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn get_size(&self) -> Vector3 {
+        self.max - self.min
+    }
+
+    pub fn get_surface_area(&self) -> f32 {
+        let s = self.get_size();
+        2.0 * (s.x * s.y + s.y * s.z + s.z * s.x)
+    }
+
+    pub fn get_volume(&self) -> f32 {
+        let s = self.get_size();
+        s.x * s.y * s.z
+    }
+
+    pub fn get_corners(&self) -> [Vector3; 8] {
+        std::array::from_fn(|i| {
+            Vector3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            )
+        })
+    }
+
+    pub fn union(&mut self, rhs: &Aabb3) {
+        *self = self.get_union(rhs)
+    }
+
+    pub fn get_union(&self, rhs: &Aabb3) -> Self {
+        Self {
+            min: self.min.min(&rhs.min),
+            max: self.max.max(&rhs.max),
+        }
+    }
+
+    pub fn get_intersection(&self, rhs: &Aabb3) -> Option<Self> {
+        let out = Self {
+            min: self.min.max(&rhs.min),
+            max: self.max.min(&rhs.max),
+        };
+        if out.is_empty() { None } else { Some(out) }
+    }
+
+    pub fn intersects(&self, rhs: &Aabb3) -> bool {
+        self.min.x <= rhs.max.x && self.max.x >= rhs.min.x &&
+        self.min.y <= rhs.max.y && self.max.y >= rhs.min.y &&
+        self.min.z <= rhs.max.z && self.max.z >= rhs.min.z
+    }
+
+    pub fn contains_point(&self, p: &Vector3) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x &&
+        p.y >= self.min.y && p.y <= self.max.y &&
+        p.z >= self.min.z && p.z <= self.max.z
+    }
+
+    pub fn contains_aabb(&self, rhs: &Aabb3) -> bool {
+        self.contains_point(&rhs.min) && self.contains_point(&rhs.max)
+    }
+
+    pub fn closest_point(&self, p: &Vector3) -> Vector3 {
+        p.max(&self.min).min(&self.max)
+    }
+
+    pub fn expand_to_point(&mut self, p: &Vector3) {
+        self.min = self.min.min(p);
+        self.max = self.max.max(p);
+    }
+
+    pub fn expand(&mut self, amount: f32) {
+        *self = self.get_expand(amount)
+    }
+
+    pub fn get_expand(&self, amount: f32) -> Self {
+        let v = Vector3::new(amount, amount, amount);
+        Self {
+            min: self.min - v,
+            max: self.max + v,
+        }
+    }
+
+    pub fn transform(&mut self, m: &Mat4) {
+        *self = self.get_transform(m)
+    }
+
+    // Arvo's method: the tight box around the transformed corners, built
+    // from the per-element min/max of the matrix terms. An empty box stays
+    // empty; its infinite bounds would otherwise turn into NaN.
+    pub fn get_transform(&self, m: &Mat4) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+        let t = Vector3::new(m.r3c0, m.r3c1, m.r3c2);
+        let mut min = t;
+        let mut max = t;
+        for i in 0..3 {
+            for j in 0..3 {
+                let a = m[(j, i)] * self.min[j];
+                let b = m[(j, i)] * self.max[j];
+                min[i] += a.min(b);
+                max[i] += a.max(b);
+            }
+        }
+        Self { min, max }
+    }
+}
+
+impl Aabb2 {
+    pub fn new(min: Vector2, max: Vector2) -> Self {
+        Self { min, max }
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            min: Vector2::new(f32::INFINITY, f32::INFINITY),
+            max: Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+        }
+    }
+
+    pub fn from_center_extents(center: &Vector2, extents: &Vector2) -> Self {
+        Self {
+            min: *center - *extents,
+            max: *center + *extents,
+        }
+    }
+
+    pub fn from_points(points: &[Vector2]) -> Self {
+        let mut out = Self::empty();
+        for p in points {
+            out.expand_to_point(p);
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+
+    pub fn get_center(&self) -> Vector2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn get_extents(&self) -> Vector2 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn get_size(&self) -> Vector2 {
+        self.max - self.min
+    }
+
+    pub fn get_perimeter(&self) -> f32 {
+        let s = self.get_size();
+        2.0 * (s.x + s.y)
+    }
+
+    pub fn get_area(&self) -> f32 {
+        let s = self.get_size();
+        s.x * s.y
+    }
+
+    pub fn union(&mut self, rhs: &Aabb2) {
+        *self = self.get_union(rhs)
+    }
+
+    pub fn get_union(&self, rhs: &Aabb2) -> Self {
+        Self {
+            min: self.min.min(&rhs.min),
+            max: self.max.max(&rhs.max),
+        }
+    }
+
+    pub fn get_intersection(&self, rhs: &Aabb2) -> Option<Self> {
+        let out = Self {
+            min: self.min.max(&rhs.min),
+            max: self.max.min(&rhs.max),
+        };
+        if out.is_empty() { None } else { Some(out) }
+    }
+
+    pub fn intersects(&self, rhs: &Aabb2) -> bool {
+        self.min.x <= rhs.max.x && self.max.x >= rhs.min.x &&
+        self.min.y <= rhs.max.y && self.max.y >= rhs.min.y
+    }
+
+    pub fn contains_point(&self, p: &Vector2) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x &&
+        p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    pub fn contains_aabb(&self, rhs: &Aabb2) -> bool {
+        self.contains_point(&rhs.min) && self.contains_point(&rhs.max)
+    }
+
+    pub fn closest_point(&self, p: &Vector2) -> Vector2 {
+        p.max(&self.min).min(&self.max)
+    }
+
+    pub fn expand_to_point(&mut self, p: &Vector2) {
+        self.min = self.min.min(p);
+        self.max = self.max.max(p);
+    }
+
+    pub fn expand(&mut self, amount: f32) {
+        *self = self.get_expand(amount)
+    }
+
+    pub fn get_expand(&self, amount: f32) -> Self {
+        let v = Vector2::new(amount, amount);
+        Self {
+            min: self.min - v,
+            max: self.max + v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Deg;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    #[test]
+    fn empty_is_replaced_by_union_and_points() {
+        let empty = Aabb3::empty();
+        assert!(empty.is_empty());
+        let b = Aabb3::new(v(0.0, 0.0, 0.0), v(1.0, 2.0, 3.0));
+        assert_eq!(empty.get_union(&b), b);
+        let from = Aabb3::from_points(&[v(1.0, -1.0, 0.0), v(-2.0, 3.0, 1.0), v(0.0, 0.0, -4.0)]);
+        assert_eq!(from, Aabb3::new(v(-2.0, -1.0, -4.0), v(1.0, 3.0, 1.0)));
+        assert!(Aabb3::from_points(&[]).is_empty());
+    }
+
+    #[test]
+    fn measures() {
+        let b = Aabb3::from_center_extents(&v(1.0, 1.0, 1.0), &v(1.0, 2.0, 3.0));
+        assert_eq!(b.get_size(), v(2.0, 4.0, 6.0));
+        assert_eq!(b.get_center(), v(1.0, 1.0, 1.0));
+        assert_eq!(b.get_surface_area(), 2.0 * (8.0 + 24.0 + 12.0));
+        assert_eq!(b.get_volume(), 48.0);
+        let corners = b.get_corners();
+        assert_eq!(corners[0], b.min);
+        assert_eq!(corners[7], b.max);
+        assert!(corners.iter().all(|c| b.contains_point(c)));
+    }
+
+    #[test]
+    fn overlap_and_containment() {
+        let a = Aabb3::new(v(0.0, 0.0, 0.0), v(2.0, 2.0, 2.0));
+        let b = Aabb3::new(v(1.0, 1.0, 1.0), v(3.0, 3.0, 3.0));
+        let c = Aabb3::new(v(2.5, 0.0, 0.0), v(3.0, 1.0, 1.0));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&c));
+        assert_eq!(a.get_intersection(&b), Some(Aabb3::new(v(1.0, 1.0, 1.0), v(2.0, 2.0, 2.0))));
+        assert_eq!(a.get_intersection(&c), None);
+        // Touching faces still count as overlapping.
+        assert!(a.intersects(&Aabb3::new(v(2.0, 0.0, 0.0), v(3.0, 1.0, 1.0))));
+        assert!(a.get_union(&b).contains_aabb(&a));
+        assert!(!a.contains_aabb(&b));
+        assert_eq!(a.closest_point(&v(-1.0, 1.0, 5.0)), v(0.0, 1.0, 2.0));
+        assert_eq!(a.closest_point(&v(1.0, 1.0, 1.0)), v(1.0, 1.0, 1.0));
+        assert_eq!(a.get_expand(1.0), Aabb3::new(v(-1.0, -1.0, -1.0), v(3.0, 3.0, 3.0)));
+    }
+
+    #[test]
+    fn transform_bounds_the_transformed_corners_tightly() {
+        let mut m = Mat4::from_axis_angle(&v(1.0, 2.0, 3.0), Deg(40.0));
+        m.r3c0 = 5.0;
+        m.r3c1 = -2.0;
+        m.r0c0 *= 2.0;
+        let b = Aabb3::new(v(-1.0, 0.0, 2.0), v(3.0, 1.0, 4.0));
+        let corners = b.get_corners().map(|c| m.transform_point(&c));
+        let (got, want) = (b.get_transform(&m), Aabb3::from_points(&corners));
+        crate::assert_approx_eq!(got.min, want.min);
+        crate::assert_approx_eq!(got.max, want.max);
+        assert_eq!(Aabb3::empty().get_transform(&m), Aabb3::empty());
+        let mut from = Aabb3::from_points(&[]);
+        from.transform(&m);
+        from.expand_to_point(&v(1.0, 2.0, 3.0));
+        assert_eq!(from, Aabb3::new(v(1.0, 2.0, 3.0), v(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn aabb2_measures_and_overlap() {
+        let a = Aabb2::new(Vector2::new(0.0, 0.0), Vector2::new(2.0, 3.0));
+        assert_eq!(a.get_area(), 6.0);
+        assert_eq!(a.get_perimeter(), 10.0);
+        assert_eq!(a.get_center(), Vector2::new(1.0, 1.5));
+        let b = Aabb2::from_points(&[Vector2::new(1.0, 1.0), Vector2::new(4.0, 2.0)]);
+        assert!(a.intersects(&b));
+        assert_eq!(a.get_intersection(&b), Some(Aabb2::new(Vector2::new(1.0, 1.0), Vector2::new(2.0, 2.0))));
+        assert!(a.get_union(&b).contains_aabb(&b));
+        assert_eq!(a.closest_point(&Vector2::new(5.0, -1.0)), Vector2::new(2.0, 0.0));
+        assert!(Aabb2::empty().is_empty());
+        assert!(!a.intersects(&Aabb2::new(Vector2::new(3.0, 0.0), Vector2::new(4.0, 1.0))));
+    }
+}
@@ -0,0 +1,568 @@
+use crate::{
+    geometry::{
+        aabb::Aabb3,
+        frustum::{Containment, Frustum, FRUSTUM_ALL_PLANES},
+        ray::{Ray, RayHit},
+        sphere::Sphere,
+    },
+    vector3::Vector3,
+};
+
+const BVH_BINS: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Leaf(usize),
+    Branch([usize; 2]),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Node {
+    aabb: Aabb3,
+    parent: Option<usize>,
+    kind: Kind,
+}
+
+// Items are the caller's primitives, known to the tree only by their boxes.
+// Each item gets a stable index: its position in the slice passed to build,
+// or the value returned by insert.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+    free_nodes: Vec<usize>,
+    leaves: Vec<Option<usize>>,
+    free_items: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Top-down build splitting on binned centroids by the surface area heuristic.
+    pub fn build(aabbs: &[Aabb3]) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(aabbs.len() * 2),
+            leaves: vec![None; aabbs.len()],
+            ..Self::default()
+        };
+        let mut items: Vec<usize> = (0..aabbs.len()).collect();
+        let centroids: Vec<Vector3> = aabbs.iter().map(|b| b.get_center()).collect();
+        if !items.is_empty() {
+            bvh.root = Some(bvh.build_range(&mut items, aabbs, &centroids, None));
+        }
+        bvh
+    }
+
+    fn build_range(&mut self, items: &mut [usize], aabbs: &[Aabb3], centroids: &[Vector3], parent: Option<usize>) -> usize {
+        if let [item] = *items {
+            let node = self.alloc(Node { aabb: aabbs[item], parent, kind: Kind::Leaf(item) });
+            self.leaves[item] = Some(node);
+            return node;
+        }
+
+        let mut bounds = Aabb3::empty();
+        let mut centroid_bounds = Aabb3::empty();
+        for &i in items.iter() {
+            bounds.union(&aabbs[i]);
+            centroid_bounds.expand_to_point(&centroids[i]);
+        }
+        let size = centroid_bounds.get_size();
+        let axis = if size.x >= size.y && size.x >= size.z { 0 } else if size.y >= size.z { 1 } else { 2 };
+
+        let mid = if size[axis] > 0.0 {
+            let min = centroid_bounds.min[axis];
+            let scale = BVH_BINS as f32 / size[axis];
+            let bin_of = |i: usize| (((centroids[i][axis] - min) * scale) as usize).min(BVH_BINS - 1);
+
+            let mut counts = [0usize; BVH_BINS];
+            let mut boxes = [Aabb3::empty(); BVH_BINS];
+            for &i in items.iter() {
+                let b = bin_of(i);
+                counts[b] += 1;
+                boxes[b].union(&aabbs[i]);
+            }
+
+            // Sweep from the right to get the cost of everything past each split.
+            let mut right_cost = [0.0; BVH_BINS];
+            let (mut acc, mut count) = (Aabb3::empty(), 0);
+            for b in (1..BVH_BINS).rev() {
+                acc.union(&boxes[b]);
+                count += counts[b];
+                right_cost[b] = if count > 0 { acc.get_surface_area() * count as f32 } else { 0.0 };
+            }
+            let (mut best, mut best_cost) = (1, f32::INFINITY);
+            let (mut acc, mut count) = (Aabb3::empty(), 0);
+            for b in 1..BVH_BINS {
+                acc.union(&boxes[b - 1]);
+                count += counts[b - 1];
+                if count == 0 || count == items.len() {
+                    continue;
+                }
+                let cost = acc.get_surface_area() * count as f32 + right_cost[b];
+                if cost < best_cost {
+                    best = b;
+                    best_cost = cost;
+                }
+            }
+            partition(items, |i| bin_of(i) < best)
+        } else {
+            // Coincident centroids give the bins nothing to work with.
+            items.len() / 2
+        };
+
+        let node = self.alloc(Node { aabb: bounds, parent, kind: Kind::Leaf(usize::MAX) });
+        let (left, right) = items.split_at_mut(mid);
+        let left = self.build_range(left, aabbs, centroids, Some(node));
+        let right = self.build_range(right, aabbs, centroids, Some(node));
+        self.nodes[node].kind = Kind::Branch([left, right]);
+        node
+    }
+
+    fn alloc(&mut self, node: Node) -> usize {
+        match self.free_nodes.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len() - self.free_items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn get_bounds(&self) -> Option<Aabb3> {
+        self.root.map(|r| self.nodes[r].aabb)
+    }
+
+    pub fn get_aabb(&self, item: usize) -> Option<Aabb3> {
+        let leaf = (*self.leaves.get(item)?)?;
+        Some(self.nodes[leaf].aabb)
+    }
+
+    // Places the new leaf next to the node whose box grows least to take it,
+    // descending greedily by the same surface area cost as the build.
+    pub fn insert(&mut self, aabb: Aabb3) -> usize {
+        let item = match self.free_items.pop() {
+            Some(i) => i,
+            None => {
+                self.leaves.push(None);
+                self.leaves.len() - 1
+            }
+        };
+        let leaf = self.alloc(Node { aabb, parent: None, kind: Kind::Leaf(item) });
+        self.leaves[item] = Some(leaf);
+
+        let Some(mut sibling) = self.root else {
+            self.root = Some(leaf);
+            return item;
+        };
+        while let Kind::Branch(children) = self.nodes[sibling].kind {
+            let area = self.nodes[sibling].aabb.get_surface_area();
+            let combined = self.nodes[sibling].aabb.get_union(&aabb).get_surface_area();
+            // Cost of pairing with this node, against the cheapest descent.
+            let here = 2.0 * combined;
+            let inherited = 2.0 * (combined - area);
+            let descend = children.map(|c| {
+                let child = &self.nodes[c].aabb;
+                let grown = child.get_union(&aabb).get_surface_area();
+                match self.nodes[c].kind {
+                    Kind::Leaf(_) => grown + inherited,
+                    Kind::Branch(_) => grown - child.get_surface_area() + inherited,
+                }
+            });
+            if here < descend[0] && here < descend[1] {
+                break;
+            }
+            sibling = children[if descend[0] <= descend[1] { 0 } else { 1 }];
+        }
+
+        let old_parent = self.nodes[sibling].parent;
+        let branch = self.alloc(Node {
+            aabb: self.nodes[sibling].aabb.get_union(&aabb),
+            parent: old_parent,
+            kind: Kind::Branch([sibling, leaf]),
+        });
+        self.nodes[sibling].parent = Some(branch);
+        self.nodes[leaf].parent = Some(branch);
+        match old_parent {
+            Some(p) => self.replace_child(p, sibling, branch),
+            None => self.root = Some(branch),
+        }
+        self.refit_ancestors(branch);
+        item
+    }
+
+    // Returns false if the item was not in the tree. Its index may be handed
+    // out again by a later insert.
+    pub fn remove(&mut self, item: usize) -> bool {
+        let Some(leaf) = self.leaves.get_mut(item).and_then(Option::take) else {
+            return false;
+        };
+        self.free_items.push(item);
+        self.free_nodes.push(leaf);
+
+        let Some(parent) = self.nodes[leaf].parent else {
+            self.root = None;
+            return true;
+        };
+        let Kind::Branch(children) = self.nodes[parent].kind else {
+            unreachable!()
+        };
+        let sibling = if children[0] == leaf { children[1] } else { children[0] };
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        self.free_nodes.push(parent);
+        match grandparent {
+            Some(g) => {
+                self.replace_child(g, parent, sibling);
+                self.refit_ancestors(g);
+            }
+            None => self.root = Some(sibling),
+        }
+        true
+    }
+
+    // Moves one item, refitting only the boxes on its path to the root.
+    pub fn update(&mut self, item: usize, aabb: Aabb3) {
+        if let Some(Some(leaf)) = self.leaves.get(item) {
+            self.nodes[*leaf].aabb = aabb;
+            if let Some(p) = self.nodes[*leaf].parent {
+                self.refit_ancestors(p);
+            }
+        }
+    }
+
+    // Updates every item's box from aabb_of and refits the whole tree in one
+    // pass, keeping its topology. Quality degrades as items move far from
+    // where they were built; rebuild when queries slow down.
+    pub fn refit<F>(&mut self, aabb_of: F)
+    where
+        F: Fn(usize) -> Aabb3,
+    {
+        let Some(root) = self.root else {
+            return;
+        };
+        // Parents are listed before children, so refit in reverse.
+        let mut order = vec![root];
+        let mut i = 0;
+        while i < order.len() {
+            if let Kind::Branch(children) = self.nodes[order[i]].kind {
+                order.extend(children);
+            }
+            i += 1;
+        }
+        for &n in order.iter().rev() {
+            self.nodes[n].aabb = match self.nodes[n].kind {
+                Kind::Leaf(item) => aabb_of(item),
+                Kind::Branch([l, r]) => self.nodes[l].aabb.get_union(&self.nodes[r].aabb),
+            };
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if let Kind::Branch(children) = &mut self.nodes[parent].kind {
+            for c in children.iter_mut().filter(|c| **c == old) {
+                *c = new;
+            }
+        }
+    }
+
+    fn refit_ancestors(&mut self, from: usize) {
+        let mut node = Some(from);
+        while let Some(n) = node {
+            if let Kind::Branch([l, r]) = self.nodes[n].kind {
+                self.nodes[n].aabb = self.nodes[l].aabb.get_union(&self.nodes[r].aabb);
+            }
+            node = self.nodes[n].parent;
+        }
+    }
+
+    // hit tests the ray against one item. Returns the item with the smallest
+    // t within max_t; nearer subtrees are searched first so farther ones can
+    // be skipped.
+    pub fn ray_cast<F>(&self, ray: &Ray, max_t: f32, mut hit: F) -> Option<(usize, RayHit)>
+    where
+        F: FnMut(usize, &Ray) -> Option<RayHit>,
+    {
+        let inv_dir = Vector3::new(1.0 / ray.dir.x, 1.0 / ray.dir.y, 1.0 / ray.dir.z);
+        let mut best: Option<(usize, RayHit)> = None;
+        let mut best_t = max_t;
+        let mut stack = Vec::new();
+        if let Some(root) = self.root {
+            if let Some(t) = slab_entry(&self.nodes[root].aabb, ray, &inv_dir, best_t) {
+                stack.push((root, t));
+            }
+        }
+        while let Some((n, entry)) = stack.pop() {
+            if entry > best_t {
+                continue;
+            }
+            match self.nodes[n].kind {
+                Kind::Leaf(item) => {
+                    if let Some(h) = hit(item, ray) {
+                        if h.t >= 0.0 && h.t <= best_t {
+                            best_t = h.t;
+                            best = Some((item, h));
+                        }
+                    }
+                }
+                Kind::Branch(children) => {
+                    let [a, b] = children.map(|c| slab_entry(&self.nodes[c].aabb, ray, &inv_dir, best_t).map(|t| (c, t)));
+                    let (near, far) = match (a, b) {
+                        (Some(a), Some(b)) if b.1 < a.1 => (Some(b), Some(a)),
+                        _ => (a, b),
+                    };
+                    stack.extend(far);
+                    stack.extend(near);
+                }
+            }
+        }
+        best
+    }
+
+    pub fn query_aabb(&self, aabb: &Aabb3) -> Vec<usize> {
+        self.query(|b| aabb.intersects(b))
+    }
+
+    pub fn query_sphere(&self, sphere: &Sphere) -> Vec<usize> {
+        self.query(|b| sphere.intersects_aabb(b))
+    }
+
+    // Items whose boxes are inside or straddle the frustum. Subtrees fully
+    // inside are collected without further tests, and planes a node is fully
+    // inside are skipped for its children.
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack: Vec<(usize, u8)> = self.root.map(|r| (r, FRUSTUM_ALL_PLANES)).into_iter().collect();
+        while let Some((n, mask)) = stack.pop() {
+            let (containment, mask) = frustum.test_aabb_masked(&self.nodes[n].aabb, mask);
+            match (containment, self.nodes[n].kind) {
+                (Containment::Outside, _) => {}
+                (_, Kind::Leaf(item)) => out.push(item),
+                (Containment::Inside, _) => self.collect(n, &mut out),
+                (_, Kind::Branch(children)) => stack.extend(children.map(|c| (c, mask))),
+            }
+        }
+        out
+    }
+
+    fn query<F>(&self, overlaps: F) -> Vec<usize>
+    where
+        F: Fn(&Aabb3) -> bool,
+    {
+        let mut out = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(n) = stack.pop() {
+            if !overlaps(&self.nodes[n].aabb) {
+                continue;
+            }
+            match self.nodes[n].kind {
+                Kind::Leaf(item) => out.push(item),
+                Kind::Branch(children) => stack.extend(children),
+            }
+        }
+        out
+    }
+
+    fn collect(&self, from: usize, out: &mut Vec<usize>) {
+        let mut stack = vec![from];
+        while let Some(n) = stack.pop() {
+            match self.nodes[n].kind {
+                Kind::Leaf(item) => out.push(item),
+                Kind::Branch(children) => stack.extend(children),
+            }
+        }
+    }
+}
+
+// Moves the items for which left holds to the front and returns how many there are.
+fn partition<F>(items: &mut [usize], left: F) -> usize
+where
+    F: Fn(usize) -> bool,
+{
+    let mut mid = 0;
+    for i in 0..items.len() {
+        if left(items[i]) {
+            items.swap(i, mid);
+            mid += 1;
+        }
+    }
+    mid
+}
+
+// Where the ray enters the box, 0 if it starts inside, or None if it misses
+// or only enters beyond max_t.
+fn slab_entry(aabb: &Aabb3, ray: &Ray, inv_dir: &Vector3, max_t: f32) -> Option<f32> {
+    let mut t_min = 0.0f32;
+    let mut t_max = max_t;
+    for axis in 0..3 {
+        let t1 = (aabb.min[axis] - ray.origin[axis]) * inv_dir[axis];
+        let t2 = (aabb.max[axis] - ray.origin[axis]) * inv_dir[axis];
+        // min and max discard the NaN from a zero direction starting on a face.
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+    }
+    (t_min <= t_max).then_some(t_min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::frustum::DepthRange, mat4::Mat4, test_util::Rng};
+
+    fn aabb(rng: &mut Rng) -> Aabb3 {
+        let c = rng.vec3();
+        let e = Vector3::new(rng.next().abs(), rng.next().abs(), rng.next().abs()) * 0.1;
+        Aabb3::from_center_extents(&c, &e)
+    }
+
+    // Every live node bounds its children and knows its parent, and each
+    // item's leaf holds the box the reference says it has.
+    fn check_tree(bvh: &Bvh, reference: &[Option<Aabb3>]) {
+        let mut seen = 0;
+        if let Some(root) = bvh.root {
+            assert_eq!(bvh.nodes[root].parent, None);
+            let mut stack = vec![root];
+            while let Some(n) = stack.pop() {
+                match bvh.nodes[n].kind {
+                    Kind::Leaf(item) => {
+                        assert_eq!(bvh.leaves[item], Some(n));
+                        assert_eq!(Some(bvh.nodes[n].aabb), reference[item]);
+                        seen += 1;
+                    }
+                    Kind::Branch(children) => {
+                        for c in children {
+                            assert_eq!(bvh.nodes[c].parent, Some(n));
+                            assert!(bvh.nodes[n].aabb.contains_aabb(&bvh.nodes[c].aabb));
+                            stack.push(c);
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(seen, reference.iter().flatten().count());
+        assert_eq!(bvh.len(), seen);
+    }
+
+    fn brute<F: Fn(&Aabb3) -> bool>(reference: &[Option<Aabb3>], keep: F) -> Vec<usize> {
+        (0..reference.len()).filter(|&i| reference[i].as_ref().is_some_and(&keep)).collect()
+    }
+
+    fn sorted(mut v: Vec<usize>) -> Vec<usize> {
+        v.sort_unstable();
+        v
+    }
+
+    fn check_queries(bvh: &Bvh, reference: &[Option<Aabb3>], rng: &mut Rng) {
+        let frustum = Frustum::from_matrix(
+            &Mat4::from([
+                0.25, 0.0, 0.0, 0.0,
+                0.0, 0.25, 0.0, 0.0,
+                0.0, 0.0, 0.2, 0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ]),
+            DepthRange::NegOneToOne,
+        );
+        assert_eq!(
+            sorted(bvh.query_frustum(&frustum)),
+            brute(reference, |b| frustum.test_aabb(b) != Containment::Outside)
+        );
+        for _ in 0..20 {
+            let query = Aabb3::from_center_extents(&rng.vec3(), &Vector3::new(2.0, 2.0, 2.0));
+            assert_eq!(sorted(bvh.query_aabb(&query)), brute(reference, |b| b.intersects(&query)));
+            let sphere = Sphere::new(rng.vec3(), 2.5);
+            assert_eq!(sorted(bvh.query_sphere(&sphere)), brute(reference, |b| sphere.intersects_aabb(b)));
+
+            let ray = Ray::new(rng.vec3(), rng.vec3());
+            let hit = |item: usize, ray: &Ray| ray.intersect_aabb(&reference[item].unwrap());
+            let expected = (0..reference.len())
+                .filter_map(|i| reference[i].and_then(|b| ray.intersect_aabb(&b)).filter(|h| h.t <= 5.0).map(|h| h.t))
+                .fold(None, |best: Option<f32>, t| Some(best.map_or(t, |b| b.min(t))));
+            assert_eq!(bvh.ray_cast(&ray, 5.0, hit).map(|(_, h)| h.t), expected);
+        }
+    }
+
+    #[test]
+    fn build_matches_brute_force() {
+        let mut rng = Rng(48);
+        for n in [0, 1, 2, 7, 300] {
+            let boxes: Vec<Aabb3> = (0..n).map(|_| aabb(&mut rng)).collect();
+            let reference: Vec<Option<Aabb3>> = boxes.iter().copied().map(Some).collect();
+            let bvh = Bvh::build(&boxes);
+            check_tree(&bvh, &reference);
+            check_queries(&bvh, &reference, &mut rng);
+            assert_eq!(bvh.is_empty(), n == 0);
+        }
+    }
+
+    #[test]
+    fn insert_remove_update_match_brute_force() {
+        let mut rng = Rng(49);
+        let mut bvh = Bvh::new();
+        let mut reference: Vec<Option<Aabb3>> = Vec::new();
+        for round in 0..6 {
+            for _ in 0..50 {
+                let b = aabb(&mut rng);
+                let item = bvh.insert(b);
+                if item == reference.len() {
+                    reference.push(Some(b));
+                } else {
+                    assert!(reference[item].is_none(), "live index {item} handed out again");
+                    reference[item] = Some(b);
+                }
+            }
+            for i in (round..reference.len()).step_by(3) {
+                assert_eq!(bvh.remove(i), reference[i].is_some());
+                reference[i] = None;
+            }
+            for i in (0..reference.len()).step_by(5) {
+                if reference[i].is_some() {
+                    let b = aabb(&mut rng);
+                    bvh.update(i, b);
+                    reference[i] = Some(b);
+                }
+            }
+            check_tree(&bvh, &reference);
+            check_queries(&bvh, &reference, &mut rng);
+        }
+        assert!(!bvh.remove(reference.len() + 10));
+    }
+
+    #[test]
+    fn refit_matches_brute_force() {
+        let mut rng = Rng(50);
+        let boxes: Vec<Aabb3> = (0..100).map(|_| aabb(&mut rng)).collect();
+        let mut bvh = Bvh::build(&boxes);
+        let offset = Vector3::new(3.0, -1.0, 0.5);
+        let moved: Vec<Aabb3> = boxes.iter().map(|b| Aabb3::new(b.min + offset, b.max + offset)).collect();
+        bvh.refit(|i| moved[i]);
+        let reference: Vec<Option<Aabb3>> = moved.iter().copied().map(Some).collect();
+        check_tree(&bvh, &reference);
+        check_queries(&bvh, &reference, &mut rng);
+    }
+
+    #[test]
+    fn removing_everything_empties_the_tree() {
+        let mut bvh = Bvh::new();
+        let a = bvh.insert(Aabb3::new(Vector3::zero(), Vector3::new(1.0, 1.0, 1.0)));
+        let b = bvh.insert(Aabb3::new(Vector3::new(2.0, 0.0, 0.0), Vector3::new(3.0, 1.0, 1.0)));
+        assert_eq!(bvh.get_bounds(), Some(Aabb3::new(Vector3::zero(), Vector3::new(3.0, 1.0, 1.0))));
+        assert!(bvh.remove(a));
+        assert!(!bvh.remove(a));
+        assert_eq!(bvh.get_aabb(a), None);
+        assert!(bvh.remove(b));
+        assert!(bvh.is_empty());
+        assert_eq!(bvh.get_bounds(), None);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{geometry::closest::closest_point_segment, vector3::Vector3};
+
+// Every point within radius of the segment a-b.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capsule {
+    pub a: Vector3,
+    pub b: Vector3,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(a: Vector3, b: Vector3, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+
+    pub fn contains_point(&self, p: &Vector3) -> bool {
+        (closest_point_segment(p, &self.a, &self.b).point - *p).get_mag_sqr() <= self.radius * self.radius
+    }
+}
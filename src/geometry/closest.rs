@@ -0,0 +1,462 @@
+use crate::{
+    geometry::{aabb::Aabb3, obb::Obb},
+    vector3::Vector3,
+};
+
+// Closest-point routines after Ericson, Real-Time Collision Detection, ch. 5.
+
+const CLOSEST_EPSILON: f32 = 1e-9;
+
+// point == a + (b - a) * t for the segment a-b, with t in [0, 1].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SegmentPoint {
+    pub point: Vector3,
+    pub t: f32,
+}
+
+// Barycentric weights (1 - u - v, u, v) of the triangle's (a, b, c).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrianglePoint {
+    pub point: Vector3,
+    pub u: f32,
+    pub v: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SegmentSegment {
+    pub a: SegmentPoint,
+    pub b: SegmentPoint,
+    pub dist_sqr: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SegmentTriangle {
+    pub segment: SegmentPoint,
+    pub triangle: TrianglePoint,
+    pub dist_sqr: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleTriangle {
+    pub a: TrianglePoint,
+    pub b: TrianglePoint,
+    pub dist_sqr: f32,
+}
+
+pub fn closest_point_segment(p: &Vector3, a: &Vector3, b: &Vector3) -> SegmentPoint {
+    let ab = *b - *a;
+    let denom = ab.get_mag_sqr();
+    let t = if denom <= CLOSEST_EPSILON {
+        0.0
+    } else {
+        ((*p - *a).dot(&ab) / denom).clamp(0.0, 1.0)
+    };
+    SegmentPoint { point: *a + ab * t, t }
+}
+
+pub fn dist_sqr_point_segment(p: &Vector3, a: &Vector3, b: &Vector3) -> f32 {
+    (*p - closest_point_segment(p, a, b).point).get_mag_sqr()
+}
+
+// Segments p1-q1 and p2-q2. Parallel segments return one of the closest pairs.
+pub fn closest_segment_segment(p1: &Vector3, q1: &Vector3, p2: &Vector3, q2: &Vector3) -> SegmentSegment {
+    let d1 = *q1 - *p1;
+    let d2 = *q2 - *p2;
+    let r = *p1 - *p2;
+    let a = d1.get_mag_sqr();
+    let e = d2.get_mag_sqr();
+    let f = d2.dot(&r);
+
+    let (s, t) = if a <= CLOSEST_EPSILON && e <= CLOSEST_EPSILON {
+        (0.0, 0.0)
+    } else if a <= CLOSEST_EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(&r);
+        if e <= CLOSEST_EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(&d2);
+            let denom = a * e - b * b;
+            let s = if denom > CLOSEST_EPSILON {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    let c1 = *p1 + d1 * s;
+    let c2 = *p2 + d2 * t;
+    SegmentSegment {
+        a: SegmentPoint { point: c1, t: s },
+        b: SegmentPoint { point: c2, t },
+        dist_sqr: (c1 - c2).get_mag_sqr(),
+    }
+}
+
+// Voronoi region walk over the triangle's vertices, edges and face.
+pub fn closest_point_triangle(p: &Vector3, a: &Vector3, b: &Vector3, c: &Vector3) -> TrianglePoint {
+    let at = |u: f32, v: f32| TrianglePoint {
+        point: *a + (*b - *a) * u + (*c - *a) * v,
+        u,
+        v,
+    };
+    let ab = *b - *a;
+    let ac = *c - *a;
+
+    let ap = *p - *a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return at(0.0, 0.0);
+    }
+
+    let bp = *p - *b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return at(1.0, 0.0);
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return at(d1 / (d1 - d3), 0.0);
+    }
+
+    let cp = *p - *c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return at(0.0, 1.0);
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return at(0.0, d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return at(1.0 - w, w);
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    at(vb * denom, vc * denom)
+}
+
+pub fn dist_sqr_point_triangle(p: &Vector3, a: &Vector3, b: &Vector3, c: &Vector3) -> f32 {
+    (*p - closest_point_triangle(p, a, b, c).point).get_mag_sqr()
+}
+
+pub fn closest_point_aabb(p: &Vector3, aabb: &Aabb3) -> Vector3 {
+    aabb.closest_point(p)
+}
+
+// Sums the per-axis excess without building the clamped point.
+pub fn dist_sqr_point_aabb(p: &Vector3, aabb: &Aabb3) -> f32 {
+    let mut out = 0.0;
+    for i in 0..3 {
+        if p[i] < aabb.min[i] {
+            out += (aabb.min[i] - p[i]) * (aabb.min[i] - p[i]);
+        } else if p[i] > aabb.max[i] {
+            out += (p[i] - aabb.max[i]) * (p[i] - aabb.max[i]);
+        }
+    }
+    out
+}
+
+pub fn closest_point_obb(p: &Vector3, obb: &Obb) -> Vector3 {
+    obb.closest_point(p)
+}
+
+pub fn dist_sqr_point_obb(p: &Vector3, obb: &Obb) -> f32 {
+    let local = obb.to_local(p);
+    let mut out = 0.0;
+    for i in 0..3 {
+        let excess = local[i].abs() - obb.half_extents[i];
+        if excess > 0.0 {
+            out += excess * excess;
+        }
+    }
+    out
+}
+
+// Segment p-q against triangle a, b, c. A crossing segment returns the
+// crossing point with a distance of zero.
+pub fn closest_segment_triangle(
+    p: &Vector3,
+    q: &Vector3,
+    a: &Vector3,
+    b: &Vector3,
+    c: &Vector3,
+) -> SegmentTriangle {
+    // The segment crosses the plane when its ends lie on opposite sides; the
+    // crossing point's barycentrics then decide whether it hits the triangle.
+    // Both tests are free of absolute tolerances, so tiny triangles work too.
+    let e1 = *b - *a;
+    let e2 = *c - *a;
+    let n = e1.cross(&e2);
+    let nn = n.dot(&n);
+    let dp = n.dot(&(*p - *a));
+    let dq = n.dot(&(*q - *a));
+    if nn > 0.0 && dp != dq && (dp <= 0.0 && dq >= 0.0 || dp >= 0.0 && dq <= 0.0) {
+        let t = dp / (dp - dq);
+        let point = *p + (*q - *p) * t;
+        let ap = point - *a;
+        let u = ap.cross(&e2).dot(&n) / nn;
+        let v = e1.cross(&ap).dot(&n) / nn;
+        if u >= 0.0 && v >= 0.0 && u + v <= 1.0 {
+            return SegmentTriangle {
+                segment: SegmentPoint { point, t },
+                triangle: TrianglePoint { point, u, v },
+                dist_sqr: 0.0,
+            };
+        }
+    }
+
+    // Otherwise the closest pair involves a segment endpoint or a triangle edge.
+    let mut best = SegmentTriangle {
+        segment: SegmentPoint { point: *p, t: 0.0 },
+        triangle: closest_point_triangle(p, a, b, c),
+        dist_sqr: f32::INFINITY,
+    };
+    best.dist_sqr = (best.triangle.point - *p).get_mag_sqr();
+
+    let end = closest_point_triangle(q, a, b, c);
+    let dist_sqr = (end.point - *q).get_mag_sqr();
+    if dist_sqr < best.dist_sqr {
+        best = SegmentTriangle {
+            segment: SegmentPoint { point: *q, t: 1.0 },
+            triangle: end,
+            dist_sqr,
+        };
+    }
+
+    for (i, (e0, e1)) in [(a, b), (b, c), (c, a)].into_iter().enumerate() {
+        let pair = closest_segment_segment(p, q, e0, e1);
+        if pair.dist_sqr < best.dist_sqr {
+            let (u, v) = edge_uv(i, pair.b.t);
+            best = SegmentTriangle {
+                segment: pair.a,
+                triangle: TrianglePoint { point: pair.b.point, u, v },
+                dist_sqr: pair.dist_sqr,
+            };
+        }
+    }
+    best
+}
+
+// The closest pair always involves an edge of one triangle, so six
+// edge-triangle queries cover every case, including intersection.
+pub fn closest_triangle_triangle(a: &[Vector3; 3], b: &[Vector3; 3]) -> TriangleTriangle {
+    let mut best = TriangleTriangle {
+        a: TrianglePoint { point: a[0], u: 0.0, v: 0.0 },
+        b: TrianglePoint { point: b[0], u: 0.0, v: 0.0 },
+        dist_sqr: f32::INFINITY,
+    };
+    for i in 0..3 {
+        let (e0, e1) = (&a[i], &a[(i + 1) % 3]);
+        let hit = closest_segment_triangle(e0, e1, &b[0], &b[1], &b[2]);
+        if hit.dist_sqr < best.dist_sqr {
+            let (u, v) = edge_uv(i, hit.segment.t);
+            best = TriangleTriangle {
+                a: TrianglePoint { point: hit.segment.point, u, v },
+                b: hit.triangle,
+                dist_sqr: hit.dist_sqr,
+            };
+        }
+
+        let (e0, e1) = (&b[i], &b[(i + 1) % 3]);
+        let hit = closest_segment_triangle(e0, e1, &a[0], &a[1], &a[2]);
+        if hit.dist_sqr < best.dist_sqr {
+            let (u, v) = edge_uv(i, hit.segment.t);
+            best = TriangleTriangle {
+                a: hit.triangle,
+                b: TrianglePoint { point: hit.segment.point, u, v },
+                dist_sqr: hit.dist_sqr,
+            };
+        }
+    }
+    best
+}
+
+// Parameter s along edge i (a-b, b-c, c-a) as the triangle's (u, v).
+fn edge_uv(i: usize, s: f32) -> (f32, f32) {
+    match i {
+        0 => (s, 0.0),
+        1 => (1.0 - s, s),
+        _ => (0.0, 1.0 - s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Deg, quat::Quat, test_util::Rng};
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    // Smallest squared distance from p to a dense sampling of the triangle.
+    fn sampled_point_triangle(p: &Vector3, t: &[Vector3; 3]) -> f32 {
+        const N: usize = 60;
+        let mut best = f32::INFINITY;
+        for i in 0..=N {
+            for j in 0..=N - i {
+                let (u, w) = (i as f32 / N as f32, j as f32 / N as f32);
+                let q = t[0] + (t[1] - t[0]) * u + (t[2] - t[0]) * w;
+                best = best.min((*p - q).get_mag_sqr());
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn point_segment() {
+        let (a, b) = (v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0));
+        assert_eq!(closest_point_segment(&v(1.0, 3.0, 0.0), &a, &b), SegmentPoint { point: v(1.0, 0.0, 0.0), t: 0.5 });
+        assert_eq!(closest_point_segment(&v(-1.0, 1.0, 0.0), &a, &b).t, 0.0);
+        assert_eq!(closest_point_segment(&v(5.0, 1.0, 0.0), &a, &b).t, 1.0);
+        assert_eq!(dist_sqr_point_segment(&v(5.0, 4.0, 0.0), &a, &b), 25.0);
+        assert_eq!(closest_point_segment(&v(5.0, 1.0, 0.0), &a, &a).point, a);
+    }
+
+    #[test]
+    fn segment_segment_analytic() {
+        // Skew lines crossing over each other one unit apart.
+        let pair = closest_segment_segment(&v(-1.0, 0.0, 0.0), &v(1.0, 0.0, 0.0), &v(0.0, -1.0, 1.0), &v(0.0, 1.0, 1.0));
+        assert_eq!(pair.a.point, v(0.0, 0.0, 0.0));
+        assert_eq!(pair.b.point, v(0.0, 0.0, 1.0));
+        assert_eq!((pair.a.t, pair.b.t, pair.dist_sqr), (0.5, 0.5, 1.0));
+        // Parallel and overlapping.
+        let pair = closest_segment_segment(&v(0.0, 0.0, 0.0), &v(2.0, 0.0, 0.0), &v(1.0, 1.0, 0.0), &v(3.0, 1.0, 0.0));
+        assert_eq!(pair.dist_sqr, 1.0);
+        // Both degenerate.
+        let pair = closest_segment_segment(&v(0.0, 0.0, 0.0), &v(0.0, 0.0, 0.0), &v(3.0, 4.0, 0.0), &v(3.0, 4.0, 0.0));
+        assert_eq!(pair.dist_sqr, 25.0);
+    }
+
+    #[test]
+    fn segment_segment_matches_sampling() {
+        let mut rng = Rng(7);
+        for _ in 0..200 {
+            let (p1, q1, p2, q2) = (rng.vec3(), rng.vec3(), rng.vec3(), rng.vec3());
+            let pair = closest_segment_segment(&p1, &q1, &p2, &q2);
+            crate::assert_approx_eq!(pair.a.point, p1 + (q1 - p1) * pair.a.t);
+            crate::assert_approx_eq!(pair.b.point, p2 + (q2 - p2) * pair.b.t);
+            let mut sampled = f32::INFINITY;
+            for i in 0..=100 {
+                let a = p1 + (q1 - p1) * (i as f32 / 100.0);
+                sampled = sampled.min(dist_sqr_point_segment(&a, &p2, &q2));
+            }
+            assert!(pair.dist_sqr <= sampled + 1e-3, "{} > {sampled}", pair.dist_sqr);
+            assert!(pair.dist_sqr >= sampled - 0.5, "{} << {sampled}", pair.dist_sqr);
+        }
+    }
+
+    #[test]
+    fn point_triangle_regions() {
+        let t = [v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0)];
+        let at = |p: Vector3| closest_point_triangle(&p, &t[0], &t[1], &t[2]);
+        assert_eq!(at(v(-1.0, -1.0, 1.0)), TrianglePoint { point: t[0], u: 0.0, v: 0.0 });
+        assert_eq!(at(v(3.0, -1.0, 0.0)), TrianglePoint { point: t[1], u: 1.0, v: 0.0 });
+        assert_eq!(at(v(-1.0, 3.0, 0.0)), TrianglePoint { point: t[2], u: 0.0, v: 1.0 });
+        assert_eq!(at(v(1.0, -1.0, 0.0)), TrianglePoint { point: v(1.0, 0.0, 0.0), u: 0.5, v: 0.0 });
+        assert_eq!(at(v(-1.0, 1.0, 0.0)), TrianglePoint { point: v(0.0, 1.0, 0.0), u: 0.0, v: 0.5 });
+        assert_eq!(at(v(2.0, 2.0, 0.0)), TrianglePoint { point: v(1.0, 1.0, 0.0), u: 0.5, v: 0.5 });
+        assert_eq!(at(v(0.5, 0.5, 3.0)), TrianglePoint { point: v(0.5, 0.5, 0.0), u: 0.25, v: 0.25 });
+    }
+
+    #[test]
+    fn point_triangle_matches_sampling() {
+        let mut rng = Rng(8);
+        for _ in 0..100 {
+            let t = [rng.vec3(), rng.vec3(), rng.vec3()];
+            let p = rng.vec3();
+            let hit = closest_point_triangle(&p, &t[0], &t[1], &t[2]);
+            assert!(hit.u >= 0.0 && hit.v >= 0.0 && hit.u + hit.v <= 1.0 + 1e-5);
+            let d = (hit.point - p).get_mag_sqr();
+            let sampled = sampled_point_triangle(&p, &t);
+            assert!(d <= sampled + 1e-3 && d >= sampled - 1.0, "{d} vs {sampled}");
+        }
+    }
+
+    #[test]
+    fn point_boxes() {
+        let aabb = Aabb3::new(v(0.0, 0.0, 0.0), v(1.0, 2.0, 3.0));
+        assert_eq!(dist_sqr_point_aabb(&v(-1.0, 3.0, 1.0), &aabb), 2.0);
+        assert_eq!(dist_sqr_point_aabb(&v(0.5, 0.5, 0.5), &aabb), 0.0);
+        assert_eq!(closest_point_aabb(&v(-1.0, 3.0, 1.0), &aabb), v(0.0, 2.0, 1.0));
+
+        let obb = Obb::new(v(1.0, 1.0, 0.0), v(1.0, 1.0, 1.0), Quat::from_axis_angle(&v(0.0, 0.0, 1.0), Deg(45.0)));
+        let p = v(4.0, 1.0, 0.0);
+        let d = (3.0 - 2f32.sqrt()).powi(2);
+        crate::assert_approx_eq!(dist_sqr_point_obb(&p, &obb), d);
+        crate::assert_approx_eq!((closest_point_obb(&p, &obb) - p).get_mag_sqr(), d);
+        assert_eq!(dist_sqr_point_obb(&obb.center, &obb), 0.0);
+    }
+
+    #[test]
+    fn segment_triangle() {
+        let t = [v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0)];
+        // Crossing.
+        let hit = closest_segment_triangle(&v(0.5, 0.5, -1.0), &v(0.5, 0.5, 1.0), &t[0], &t[1], &t[2]);
+        assert_eq!(hit.dist_sqr, 0.0);
+        assert_eq!(hit.segment.t, 0.5);
+        crate::assert_approx_eq!(hit.triangle.point, v(0.5, 0.5, 0.0));
+        // Above the face, closest at an endpoint.
+        let hit = closest_segment_triangle(&v(0.5, 0.5, 1.0), &v(0.5, 0.5, 3.0), &t[0], &t[1], &t[2]);
+        assert_eq!((hit.segment.t, hit.dist_sqr), (0.0, 1.0));
+        // Passing over the hypotenuse edge.
+        let hit = closest_segment_triangle(&v(2.0, 2.0, -1.0), &v(2.0, 2.0, 1.0), &t[0], &t[1], &t[2]);
+        crate::assert_approx_eq!(hit.dist_sqr, 2.0);
+        crate::assert_approx_eq!(hit.triangle.point, v(1.0, 1.0, 0.0));
+        assert_eq!((hit.triangle.u, hit.triangle.v), (0.5, 0.5));
+        // Ending on the plane still counts as crossing.
+        let hit = closest_segment_triangle(&v(0.5, 0.5, 1.0), &v(0.5, 0.5, 0.0), &t[0], &t[1], &t[2]);
+        assert_eq!((hit.segment.t, hit.dist_sqr), (1.0, 0.0));
+        // A tiny triangle needs no absolute tolerance.
+        let t = [v(0.0, 0.0, 0.0), v(0.01, 0.0, 0.0), v(0.0, 0.01, 0.0)];
+        let hit = closest_segment_triangle(&v(0.0025, 0.0025, 0.005), &v(0.0025, 0.0025, -0.005), &t[0], &t[1], &t[2]);
+        assert_eq!(hit.dist_sqr, 0.0);
+        assert_eq!(hit.segment.t, 0.5);
+        crate::assert_approx_eq!(hit.triangle.u, 0.25);
+        crate::assert_approx_eq!(hit.triangle.v, 0.25);
+    }
+
+    #[test]
+    fn triangle_triangle() {
+        let a = [v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0)];
+        let b = a.map(|p| p + v(0.0, 0.0, 3.0));
+        assert_eq!(closest_triangle_triangle(&a, &b).dist_sqr, 9.0);
+        // Piercing.
+        let b = [v(0.5, 0.5, -1.0), v(0.5, 0.5, 1.0), v(5.0, 5.0, 0.0)];
+        assert_eq!(closest_triangle_triangle(&a, &b).dist_sqr, 0.0);
+        let small = [a, b].map(|t| t.map(|p| p * 0.005));
+        assert_eq!(closest_triangle_triangle(&small[0], &small[1]).dist_sqr, 0.0);
+
+        let mut rng = Rng(9);
+        for _ in 0..30 {
+            let a = [rng.vec3(), rng.vec3(), rng.vec3()];
+            let b = [rng.vec3(), rng.vec3(), rng.vec3()].map(|p| p + v(15.0, 0.0, 0.0));
+            let hit = closest_triangle_triangle(&a, &b);
+            crate::assert_approx_eq!((hit.a.point - hit.b.point).get_mag_sqr(), hit.dist_sqr, 1e-2);
+            // Every vertex-to-triangle distance bounds the closest pair from above.
+            let bound = b.iter().map(|p| sampled_point_triangle(p, &a))
+                .chain(a.iter().map(|p| sampled_point_triangle(p, &b)))
+                .fold(f32::INFINITY, f32::min);
+            assert!(hit.dist_sqr <= bound + 1e-2);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{vector2::Vector2, vector3::Vector3};
+
+// A cell the walk passes through. t is where the ray enters it, in units of
+// the ray's direction, and normal is the face it entered through, facing back
+// towards the ray. The starting cell has t == 0 and a zero normal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridHit3 {
+    pub cell: [i32; 3],
+    pub t: f32,
+    pub normal: Vector3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridHit2 {
+    pub cell: [i32; 2],
+    pub t: f32,
+    pub normal: Vector2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Crossing<const N: usize> {
+    cell: [i32; N],
+    t: f32,
+    normal: [f32; N],
+}
+
+// Amanatides and Woo, "A Fast Voxel Traversal Algorithm for Ray Tracing".
+// Cell i along an axis covers [i * cell_size, (i + 1) * cell_size).
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Dda<const N: usize> {
+    cell: [i32; N],
+    step: [i32; N],
+    // t at which the ray crosses the next boundary on each axis.
+    t_max: [f32; N],
+    // t between consecutive boundaries on each axis.
+    t_delta: [f32; N],
+    t: f32,
+    axis: Option<usize>,
+    limit: f32,
+    done: bool,
+}
+
+impl<const N: usize> Dda<N> {
+    fn new(origin: [f32; N], dir: [f32; N], cell_size: f32, max_t: f32) -> Self {
+        let cell = origin.map(|o| (o / cell_size).floor() as i32);
+        let step = dir.map(|d| if d > 0.0 { 1 } else if d < 0.0 { -1 } else { 0 });
+        let t_max = std::array::from_fn(|a| match step[a] {
+            0 => f32::INFINITY,
+            s => ((cell[a] as f32 + s.max(0) as f32) * cell_size - origin[a]) / dir[a],
+        });
+        let t_delta = dir.map(|d| if d != 0.0 { cell_size / d.abs() } else { f32::INFINITY });
+        Self {
+            cell,
+            step,
+            t_max,
+            t_delta,
+            t: 0.0,
+            axis: None,
+            limit: max_t,
+            done: false,
+        }
+    }
+
+    // The current cell, then a step across whichever boundary comes first.
+    // Where the ray crosses several at once, the axes are taken in turn.
+    fn advance(&mut self) -> Option<Crossing<N>> {
+        if self.done {
+            return None;
+        }
+        let normal = std::array::from_fn(|a| if self.axis == Some(a) { -self.step[a] as f32 } else { 0.0 });
+        let out = Crossing { cell: self.cell, t: self.t, normal };
+        let axis = (1..N).fold(0, |best, a| if self.t_max[a] < self.t_max[best] { a } else { best });
+        // An infinite t_max means the ray never leaves the cell, even with no
+        // limit. Walking off the edge of the i32 cell range also ends the walk.
+        let next = self.cell[axis].checked_add(self.step[axis]);
+        match next {
+            Some(cell) if self.t_max[axis] <= self.limit && self.t_max[axis] != f32::INFINITY => {
+                self.cell[axis] = cell;
+                self.t = self.t_max[axis];
+                self.t_max[axis] += self.t_delta[axis];
+                self.axis = Some(axis);
+            }
+            _ => self.done = true,
+        }
+        Some(out)
+    }
+}
+
+// Every cell of a grid of cubes crossed by the ray origin + dir * t for
+// t in [0, max_t], in order. max_t may be infinite.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridWalk3 {
+    dda: Dda<3>,
+}
+
+impl GridWalk3 {
+    pub fn new(origin: &Vector3, dir: &Vector3, cell_size: f32, max_t: f32) -> Self {
+        Self {
+            dda: Dda::new([origin.x, origin.y, origin.z], [dir.x, dir.y, dir.z], cell_size, max_t),
+        }
+    }
+}
+
+impl Iterator for GridWalk3 {
+    type Item = GridHit3;
+
+    fn next(&mut self) -> Option<GridHit3> {
+        let c = self.dda.advance()?;
+        Some(GridHit3 { cell: c.cell, t: c.t, normal: Vector3::from(c.normal) })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridWalk2 {
+    dda: Dda<2>,
+}
+
+impl GridWalk2 {
+    pub fn new(origin: &Vector2, dir: &Vector2, cell_size: f32, max_t: f32) -> Self {
+        Self {
+            dda: Dda::new([origin.x, origin.y], [dir.x, dir.y], cell_size, max_t),
+        }
+    }
+}
+
+impl Iterator for GridWalk2 {
+    type Item = GridHit2;
+
+    fn next(&mut self) -> Option<GridHit2> {
+        let c = self.dda.advance()?;
+        Some(GridHit2 { cell: c.cell, t: c.t, normal: Vector2::from(c.normal) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    fn cells3(walk: GridWalk3) -> Vec<[i32; 3]> {
+        walk.map(|h| h.cell).collect()
+    }
+
+    #[test]
+    fn axis_aligned_walk() {
+        let hits: Vec<GridHit3> = GridWalk3::new(&v(0.5, 0.5, 0.5), &v(1.0, 0.0, 0.0), 1.0, 3.0).collect();
+        assert_eq!(hits.iter().map(|h| h.cell).collect::<Vec<_>>(), [[0, 0, 0], [1, 0, 0], [2, 0, 0], [3, 0, 0]]);
+        assert_eq!(hits.iter().map(|h| h.t).collect::<Vec<_>>(), [0.0, 0.5, 1.5, 2.5]);
+        assert_eq!(hits[0].normal, Vector3::zero());
+        assert!(hits[1..].iter().all(|h| h.normal == v(-1.0, 0.0, 0.0)));
+
+        // Negative direction, larger cells and a start in a negative cell.
+        let hits: Vec<GridHit3> = GridWalk3::new(&v(-1.0, 3.0, 0.0), &v(0.0, 0.0, -2.0), 2.0, 1.5).collect();
+        assert_eq!(hits.iter().map(|h| h.cell).collect::<Vec<_>>(), [[-1, 1, 0], [-1, 1, -1], [-1, 1, -2]]);
+        assert_eq!(hits.iter().map(|h| h.t).collect::<Vec<_>>(), [0.0, 0.0, 1.0]);
+        assert!(hits[1..].iter().all(|h| h.normal == v(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn diagonal_walk() {
+        let hits: Vec<GridHit3> = GridWalk3::new(&v(0.5, 0.25, 0.0), &v(1.0, 1.0, 0.0), 1.0, 1.6).collect();
+        assert_eq!(hits.iter().map(|h| h.cell).collect::<Vec<_>>(), [[0, 0, 0], [1, 0, 0], [1, 1, 0], [2, 1, 0]]);
+        assert_eq!(hits.iter().map(|h| h.t).collect::<Vec<_>>(), [0.0, 0.5, 0.75, 1.5]);
+        assert_eq!(
+            hits.iter().map(|h| h.normal).collect::<Vec<_>>(),
+            [Vector3::zero(), v(-1.0, 0.0, 0.0), v(0.0, -1.0, 0.0), v(-1.0, 0.0, 0.0)]
+        );
+
+        // Through a corner the axes are taken in turn, so each step is to a face neighbour.
+        let cells = cells3(GridWalk3::new(&v(0.5, 0.5, 0.5), &v(-1.0, -1.0, -1.0), 1.0, 1.0));
+        assert_eq!(cells, [[0, 0, 0], [-1, 0, 0], [-1, -1, 0], [-1, -1, -1]]);
+        for w in cells.windows(2) {
+            assert_eq!((0..3).map(|a| (w[0][a] - w[1][a]).abs()).sum::<i32>(), 1);
+        }
+    }
+
+    #[test]
+    fn every_step_lands_on_the_ray() {
+        let (origin, dir) = (v(0.3, -2.7, 5.1), v(0.4, 1.3, -0.9));
+        for h in GridWalk3::new(&origin, &dir, 0.5, 10.0) {
+            let p = origin + dir * h.t;
+            for a in 0..3 {
+                // The entry point sits on the cell, allowing for rounding at the boundary.
+                let c = [p.x, p.y, p.z][a] / 0.5;
+                assert!(c >= h.cell[a] as f32 - 1e-4 && c <= (h.cell[a] + 1) as f32 + 1e-4, "{h:?}");
+            }
+            assert!(h.t <= 10.0);
+        }
+    }
+
+    #[test]
+    fn zero_direction_yields_only_the_start_cell() {
+        assert_eq!(cells3(GridWalk3::new(&v(2.5, -0.5, 0.0), &Vector3::zero(), 1.0, f32::INFINITY)), [[2, -1, 0]]);
+        assert_eq!(cells3(GridWalk3::new(&v(2.5, -0.5, 0.0), &v(1.0, 0.0, 0.0), 1.0, 0.0)), [[2, -1, 0]]);
+    }
+
+    #[test]
+    fn infinite_walk_ends_at_the_edge_of_the_cell_range() {
+        let walk = GridWalk3::new(&v(2147483000.0, 0.5, 0.5), &v(1.0, 0.0, 0.0), 1.0, f32::INFINITY);
+        let cells: Vec<[i32; 3]> = walk.take(10_000).map(|h| h.cell).collect();
+        assert!(cells.len() < 10_000);
+        assert_eq!(cells.last(), Some(&[i32::MAX, 0, 0]));
+
+        let cells = cells3(GridWalk3::new(&v(0.5, -2147483648.0, 0.5), &v(0.0, -1.0, 0.0), 1.0, f32::INFINITY));
+        assert_eq!(cells, [[0, i32::MIN, 0]]);
+    }
+
+    #[test]
+    fn walk_2d() {
+        let hits: Vec<GridHit2> =
+            GridWalk2::new(&Vector2::new(0.5, 0.25), &Vector2::new(-1.0, 1.0), 1.0, 1.6).collect();
+        assert_eq!(hits.iter().map(|h| h.cell).collect::<Vec<_>>(), [[0, 0], [-1, 0], [-1, 1], [-2, 1]]);
+        assert_eq!(hits.iter().map(|h| h.t).collect::<Vec<_>>(), [0.0, 0.5, 0.75, 1.5]);
+        assert_eq!(
+            hits.iter().map(|h| h.normal).collect::<Vec<_>>(),
+            [Vector2::zero(), Vector2::new(1.0, 0.0), Vector2::new(0.0, -1.0), Vector2::new(1.0, 0.0)]
+        );
+        let walk = GridWalk2::new(&Vector2::new(0.5, -2147483000.0), &Vector2::new(0.0, -1.0), 1.0, f32::INFINITY);
+        assert_eq!(walk.take(10_000).last().map(|h| h.cell), Some([0, i32::MIN]));
+    }
+}
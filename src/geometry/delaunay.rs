@@ -0,0 +1,691 @@
+use std::collections::HashSet;
+
+use crate::{
+    geometry::{
+        aabb::Aabb2,
+        predicates::{incircle, orient2d},
+    },
+    vector2::Vector2,
+};
+
+// Stands in for the vertex at infinity. Ghost triangles join it to every hull
+// edge so points outside the hull need no special cases.
+const GHOST: usize = usize::MAX;
+
+// adjacent[i] is the triangle across the edge triangles[i] -> triangles[(i + 1) % 3],
+// or None on the convex hull. Triangles wind counter-clockwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delaunay {
+    pub points: Vec<Vector2>,
+    pub triangles: Vec<[usize; 3]>,
+    pub adjacent: Vec<[Option<usize>; 3]>,
+    // Constrained edges as (min, max) point indices.
+    pub constrained: HashSet<(usize, usize)>,
+}
+
+impl Delaunay {
+    // Bowyer–Watson insertion. Duplicate points are skipped and collinear
+    // input gives no triangles.
+    pub fn from_points(points: &[Vector2]) -> Self {
+        Self::from_points_constrained(points, &[])
+    }
+
+    // Each edge joins two point indices and is forced into the triangulation;
+    // the rest stays as Delaunay as the constraints allow. Points lying on an
+    // edge split it. An edge that would cross an earlier one is skipped, and
+    // an endpoint that duplicates another point is replaced by the copy kept.
+    pub fn from_points_constrained(points: &[Vector2], edges: &[(usize, usize)]) -> Self {
+        let mut builder = Builder {
+            points,
+            tris: Vec::new(),
+            vert_tri: vec![GHOST; points.len()],
+            alias: (0..points.len()).collect(),
+            last: 0,
+            constrained: HashSet::new(),
+        };
+        let order = spatial_order(points);
+        if let Some(seed) = builder.seed(&order) {
+            for &i in &order {
+                if !seed.contains(&i) {
+                    builder.insert(i);
+                }
+            }
+            for &(a, b) in edges {
+                builder.insert_constraint(builder.alias[a], builder.alias[b]);
+            }
+        }
+        builder.finish()
+    }
+
+    pub fn is_constrained(&self, a: usize, b: usize) -> bool {
+        self.constrained.contains(&(a.min(b), a.max(b)))
+    }
+
+    // One cell per input point, clipped to bounds and wound counter-clockwise.
+    // Cells are cut from bounds by the bisector with each triangulation
+    // neighbour, so with constraints present this is the dual of the
+    // constrained triangulation rather than a true Voronoi diagram.
+    pub fn voronoi(&self, bounds: &Aabb2) -> Vec<Vec<Vector2>> {
+        let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); self.points.len()];
+        for tri in &self.triangles {
+            for e in 0..3 {
+                let (a, b) = (tri[e], tri[(e + 1) % 3]);
+                if !neighbours[a].contains(&b) {
+                    neighbours[a].push(b);
+                    neighbours[b].push(a);
+                }
+            }
+        }
+        let rect = vec![
+            bounds.min,
+            Vector2::new(bounds.max.x, bounds.min.y),
+            bounds.max,
+            Vector2::new(bounds.min.x, bounds.max.y),
+        ];
+        let used: HashSet<usize> = self.triangles.iter().flatten().copied().collect();
+        (0..self.points.len())
+            .map(|i| {
+                if !used.contains(&i) {
+                    return Vec::new();
+                }
+                let site = self.points[i];
+                neighbours[i].iter().fold(rect.clone(), |cell, &n| {
+                    let other = self.points[n];
+                    clip_half_plane(&cell, &((site + other) * 0.5), &(other - site))
+                })
+            })
+            .collect()
+    }
+}
+
+// Keeps the part of a convex polygon with (p - origin) . normal <= 0.
+fn clip_half_plane(poly: &[Vector2], origin: &Vector2, normal: &Vector2) -> Vec<Vector2> {
+    let mut out = Vec::with_capacity(poly.len() + 1);
+    for (i, cur) in poly.iter().enumerate() {
+        let next = &poly[(i + 1) % poly.len()];
+        let d_cur = (*cur - *origin).dot(normal);
+        let d_next = (*next - *origin).dot(normal);
+        if d_cur <= 0.0 {
+            out.push(*cur);
+        }
+        // Points exactly on the line are kept as they are, not duplicated.
+        if (d_cur < 0.0 && d_next > 0.0) || (d_cur > 0.0 && d_next < 0.0) {
+            out.push(*cur + (*next - *cur) * (d_cur / (d_cur - d_next)));
+        }
+    }
+    out
+}
+
+// Rows of a coarse grid, alternating direction, so consecutive insertions
+// are close together and point location walks stay short.
+fn spatial_order(points: &[Vector2]) -> Vec<usize> {
+    let bounds = Aabb2::from_points(points);
+    let size = bounds.get_size();
+    let cells = ((points.len() as f32).sqrt() * 0.5).ceil().max(1.0);
+    let cell = |v: f32, min: f32, extent: f32| {
+        if extent > 0.0 {
+            (((v - min) / extent * cells) as usize).min(cells as usize - 1)
+        } else {
+            0
+        }
+    };
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by_key(|&i| {
+        let p = points[i];
+        let row = cell(p.y, bounds.min.y, size.y);
+        let col = cell(p.x, bounds.min.x, size.x);
+        (row, if row % 2 == 0 { col } else { usize::MAX - col })
+    });
+    order
+}
+
+// v[k] is opposite the edge v[k + 1] -> v[k + 2], across which lies n[k].
+#[derive(Clone, Copy, Debug)]
+struct Tri {
+    v: [usize; 3],
+    n: [usize; 3],
+    alive: bool,
+}
+
+// How the segment from i towards j leaves i.
+enum Walk {
+    // i-j is already an edge.
+    Edge,
+    // The segment runs along the edge from i to c, which lies on it.
+    Vertex(usize),
+    // The segment crosses these triangles up to end, which is j or the
+    // first vertex lying on the segment.
+    Crossing {
+        crossed: Vec<usize>,
+        left: Vec<usize>,
+        right: Vec<usize>,
+        end: usize,
+    },
+    // The segment crosses a constrained edge, or no way out of i was found.
+    Blocked,
+}
+
+struct Builder<'a> {
+    points: &'a [Vector2],
+    tris: Vec<Tri>,
+    vert_tri: Vec<usize>,
+    // The vertex each point was inserted as; differs for skipped duplicates.
+    alias: Vec<usize>,
+    last: usize,
+    constrained: HashSet<(usize, usize)>,
+}
+
+impl Builder<'_> {
+    // The first non-degenerate triangle, plus its three ghosts.
+    fn seed(&mut self, order: &[usize]) -> Option<[usize; 3]> {
+        let p = self.points;
+        let &a = order.first()?;
+        let &b = order.iter().find(|&&i| p[i].x != p[a].x || p[i].y != p[a].y)?;
+        let &c = order.iter().find(|&&i| orient2d(&p[a], &p[b], &p[i]) != 0.0)?;
+        let (b, c) = if orient2d(&p[a], &p[b], &p[c]) > 0.0 { (b, c) } else { (c, b) };
+
+        let faces = [[a, b, c], [b, a, GHOST], [c, b, GHOST], [a, c, GHOST]];
+        for v in faces {
+            self.tris.push(Tri { v, n: [GHOST; 3], alive: true });
+        }
+        for t in 0..4 {
+            for k in 0..3 {
+                let v = self.tris[t].v;
+                let (from, to) = (v[(k + 1) % 3], v[(k + 2) % 3]);
+                self.tris[t].n[k] = (0..4).find(|&o| self.edge_index(o, to, from).is_some()).unwrap();
+            }
+        }
+        for v in [a, b, c] {
+            self.vert_tri[v] = 0;
+        }
+        Some([a, b, c])
+    }
+
+    // The k for which tris[t] has the directed edge from -> to opposite v[k].
+    fn edge_index(&self, t: usize, from: usize, to: usize) -> Option<usize> {
+        let v = self.tris[t].v;
+        (0..3).find(|&k| v[(k + 1) % 3] == from && v[(k + 2) % 3] == to)
+    }
+
+    fn is_ghost(&self, t: usize) -> bool {
+        self.tris[t].v.contains(&GHOST)
+    }
+
+    // Whether p invalidates triangle t: inside its circumcircle, or for a
+    // ghost, beyond its hull edge or on the edge's interior.
+    fn in_conflict(&self, t: usize, p: &Vector2) -> bool {
+        let v = self.tris[t].v;
+        match v.iter().position(|&x| x == GHOST) {
+            Some(k) => {
+                let a = self.points[v[(k + 1) % 3]];
+                let b = self.points[v[(k + 2) % 3]];
+                let o = orient2d(&a, &b, p);
+                o > 0.0 || (o == 0.0 && (*p - a).dot(&(*p - b)) < 0.0)
+            }
+            None => {
+                let [a, b, c] = v.map(|i| self.points[i]);
+                incircle(&a, &b, &c, p) > 0.0
+            }
+        }
+    }
+
+    // Visibility walk from the last triangle touched. Err with the existing
+    // vertex when p duplicates one.
+    fn locate(&self, p: &Vector2) -> Result<usize, usize> {
+        let mut t = self.last;
+        if self.is_ghost(t) {
+            let k = self.tris[t].v.iter().position(|&x| x == GHOST).unwrap();
+            t = self.tris[t].n[k];
+        }
+        loop {
+            if self.is_ghost(t) {
+                return Ok(t);
+            }
+            let v = self.tris[t].v;
+            let step = (0..3).find(|&k| {
+                let a = self.points[v[(k + 1) % 3]];
+                let b = self.points[v[(k + 2) % 3]];
+                orient2d(&a, &b, p) < 0.0
+            });
+            match step {
+                Some(k) => t = self.tris[t].n[k],
+                None => {
+                    return match v.iter().find(|&&i| self.points[i].x == p.x && self.points[i].y == p.y) {
+                        Some(&i) => Err(i),
+                        None => Ok(t),
+                    };
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, pi: usize) {
+        let p = self.points[pi];
+        let start = match self.locate(&p) {
+            Ok(t) => t,
+            Err(existing) => {
+                self.alias[pi] = existing;
+                return;
+            }
+        };
+
+        // Flood the conflicting triangles; their outer edges bound the cavity.
+        let mut cavity = vec![start];
+        self.tris[start].alive = false;
+        let mut boundary = Vec::new();
+        let mut i = 0;
+        while i < cavity.len() {
+            let t = cavity[i];
+            let Tri { v, n, .. } = self.tris[t];
+            for k in 0..3 {
+                if !self.tris[n[k]].alive {
+                    continue;
+                }
+                if self.in_conflict(n[k], &p) {
+                    self.tris[n[k]].alive = false;
+                    cavity.push(n[k]);
+                } else {
+                    boundary.push((v[(k + 1) % 3], v[(k + 2) % 3], n[k]));
+                }
+            }
+            i += 1;
+        }
+
+        let new: Vec<usize> = boundary
+            .iter()
+            .map(|&(a, b, _)| self.alloc(&mut cavity, [a, b, pi]))
+            .collect();
+        for (slot, &(a, b, outside)) in boundary.iter().enumerate() {
+            let t = new[slot];
+            let k = self.edge_index(outside, b, a).unwrap();
+            self.tris[outside].n[k] = t;
+            let across_b = boundary.iter().position(|e| e.0 == b).unwrap();
+            let across_a = boundary.iter().position(|e| e.1 == a).unwrap();
+            self.tris[t].n = [new[across_b], new[across_a], outside];
+            self.touch(t);
+        }
+    }
+
+    // Reuses a freed slot when one is left.
+    fn alloc(&mut self, free: &mut Vec<usize>, v: [usize; 3]) -> usize {
+        let tri = Tri { v, n: [GHOST; 3], alive: true };
+        match free.pop() {
+            Some(t) => {
+                self.tris[t] = tri;
+                t
+            }
+            None => {
+                self.tris.push(tri);
+                self.tris.len() - 1
+            }
+        }
+    }
+
+    fn touch(&mut self, t: usize) {
+        for v in self.tris[t].v {
+            if v != GHOST {
+                self.vert_tri[v] = t;
+            }
+        }
+        if !self.is_ghost(t) {
+            self.last = t;
+        }
+    }
+
+    // Forces the segment i-j into the triangulation, one piece per vertex
+    // lying on it. Every piece is checked before any is inserted, so a
+    // segment crossing an earlier constraint leaves nothing behind.
+    fn insert_constraint(&mut self, i: usize, j: usize) {
+        if i == j || self.vert_tri[i] == GHOST || self.vert_tri[j] == GHOST {
+            return;
+        }
+        // Pieces after the first cross triangles the earlier ones leave alone,
+        // so they can all be checked against the current triangulation.
+        let mut from = i;
+        while from != j {
+            from = match self.walk(from, j) {
+                Walk::Edge => j,
+                Walk::Vertex(c) => c,
+                Walk::Crossing { end, .. } => end,
+                Walk::Blocked => return,
+            };
+        }
+        let mut from = i;
+        while from != j {
+            let to = match self.walk(from, j) {
+                Walk::Edge => j,
+                Walk::Vertex(c) => c,
+                Walk::Crossing { crossed, left, right, end } => {
+                    self.retriangulate(from, end, crossed, left, right);
+                    end
+                }
+                Walk::Blocked => unreachable!("checked above"),
+            };
+            self.constrained.insert((from.min(to), from.max(to)));
+            from = to;
+        }
+    }
+
+    fn is_constrained(&self, a: usize, b: usize) -> bool {
+        self.constrained.contains(&(a.min(b), a.max(b)))
+    }
+
+    // Follows the segment from i towards j without changing anything.
+    fn walk(&self, i: usize, j: usize) -> Walk {
+        let (pi, pj) = (self.points[i], self.points[j]);
+
+        // Find the triangle around i that the segment leaves through.
+        let mut t = self.vert_tri[i];
+        let mut first = None;
+        for _ in 0..self.tris.len() {
+            let v = self.tris[t].v;
+            let k = v.iter().position(|&x| x == i).unwrap();
+            let (a, b) = (v[(k + 1) % 3], v[(k + 2) % 3]);
+            if a == j || b == j {
+                return Walk::Edge;
+            }
+            if a != GHOST && b != GHOST {
+                for c in [a, b] {
+                    let pc = self.points[c];
+                    if orient2d(&pi, &pj, &pc) == 0.0 && (pc - pi).dot(&(pj - pi)) > 0.0 {
+                        return Walk::Vertex(c);
+                    }
+                }
+                if orient2d(&pi, &pj, &self.points[a]) < 0.0 && orient2d(&pi, &pj, &self.points[b]) > 0.0 {
+                    first = Some((t, a, b));
+                    break;
+                }
+            }
+            t = self.tris[t].n[(k + 1) % 3];
+        }
+        let Some((t, a, b)) = first else {
+            return Walk::Blocked;
+        };
+
+        // Walk across the triangles the segment passes through, splitting
+        // their vertices into the chains left and right of it.
+        let mut crossed = vec![t];
+        let mut left = vec![b];
+        let mut right = vec![a];
+        let (mut ea, mut eb) = (a, b);
+        let mut cur = t;
+        let end = loop {
+            if self.is_constrained(ea, eb) {
+                return Walk::Blocked;
+            }
+            let k = self.edge_index(cur, ea, eb).unwrap();
+            let next = self.tris[cur].n[k];
+            crossed.push(next);
+            let v = self.tris[next].v;
+            let c = *v.iter().find(|&&x| x != ea && x != eb).unwrap();
+            if c == j {
+                break j;
+            }
+            let o = orient2d(&pi, &pj, &self.points[c]);
+            if o == 0.0 {
+                // A vertex on the segment ends this piece of it.
+                break c;
+            }
+            if o > 0.0 {
+                left.push(c);
+                eb = c;
+            } else {
+                right.push(c);
+                ea = c;
+            }
+            cur = next;
+        };
+        Walk::Crossing { crossed, left, right, end }
+    }
+
+    // Removes the crossed triangles and refills the pseudo polygons on either
+    // side of i-end (Anglada, "An improved incremental algorithm for
+    // constructing restricted Delaunay triangulations").
+    fn retriangulate(&mut self, i: usize, end: usize, mut crossed: Vec<usize>, left: Vec<usize>, mut right: Vec<usize>) {
+        let mut outside = Vec::new();
+        for &t in &crossed {
+            self.tris[t].alive = false;
+        }
+        for &t in &crossed {
+            let Tri { v, n, .. } = self.tris[t];
+            for k in 0..3 {
+                if self.tris[n[k]].alive {
+                    outside.push(((v[(k + 1) % 3], v[(k + 2) % 3]), n[k]));
+                }
+            }
+        }
+
+        let mut faces = Vec::with_capacity(crossed.len());
+        self.fill_pseudo_polygon(i, end, &left, &mut faces);
+        right.reverse();
+        self.fill_pseudo_polygon(end, i, &right, &mut faces);
+
+        let new: Vec<usize> = faces.iter().map(|&v| self.alloc(&mut crossed, v)).collect();
+        for &t in &new {
+            let v = self.tris[t].v;
+            for k in 0..3 {
+                let (from, to) = (v[(k + 1) % 3], v[(k + 2) % 3]);
+                if let Some(&o) = new.iter().find(|&&o| self.edge_index(o, to, from).is_some()) {
+                    self.tris[t].n[k] = o;
+                } else if let Some(&(_, o)) = outside.iter().find(|(e, _)| *e == (from, to)) {
+                    self.tris[t].n[k] = o;
+                    let back = self.edge_index(o, to, from).unwrap();
+                    self.tris[o].n[back] = t;
+                }
+            }
+            self.touch(t);
+        }
+    }
+
+    // Triangulates the polygon a -> b -> chain back to a, where chain runs
+    // from a's side to b's side and lies left of a -> b.
+    fn fill_pseudo_polygon(&self, a: usize, b: usize, chain: &[usize], out: &mut Vec<[usize; 3]>) {
+        if chain.is_empty() {
+            return;
+        }
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let mut c = 0;
+        for k in 1..chain.len() {
+            if incircle(&pa, &pb, &self.points[chain[c]], &self.points[chain[k]]) > 0.0 {
+                c = k;
+            }
+        }
+        self.fill_pseudo_polygon(a, chain[c], &chain[..c], out);
+        self.fill_pseudo_polygon(chain[c], b, &chain[c + 1..], out);
+        out.push([a, b, chain[c]]);
+    }
+
+    fn finish(self) -> Delaunay {
+        let mut remap = vec![usize::MAX; self.tris.len()];
+        let mut triangles = Vec::new();
+        for (t, tri) in self.tris.iter().enumerate() {
+            if tri.alive && !tri.v.contains(&GHOST) {
+                remap[t] = triangles.len();
+                triangles.push(tri.v);
+            }
+        }
+        let adjacent = self
+            .tris
+            .iter()
+            .filter(|tri| tri.alive && !tri.v.contains(&GHOST))
+            .map(|tri| {
+                std::array::from_fn(|e| {
+                    let n = tri.n[(e + 2) % 3];
+                    if remap[n] == usize::MAX { None } else { Some(remap[n]) }
+                })
+            })
+            .collect();
+        Delaunay {
+            points: self.points.to_vec(),
+            triangles,
+            adjacent,
+            constrained: self.constrained,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::polygon::signed_area, test_util::Rng};
+
+    fn p(x: f32, y: f32) -> Vector2 {
+        Vector2::new(x, y)
+    }
+
+    fn points(rng: &mut Rng, n: usize) -> Vec<Vector2> {
+        (0..n).map(|_| p(rng.next(), rng.next())).collect()
+    }
+
+    fn has_edge(d: &Delaunay, a: usize, b: usize) -> bool {
+        d.triangles.iter().any(|t| (0..3).any(|e| (t[e], t[(e + 1) % 3]) == (a, b) || (t[e], t[(e + 1) % 3]) == (b, a)))
+    }
+
+    // Counter-clockwise triangles with symmetric adjacency, and None only
+    // across hull edges.
+    fn check_mesh(d: &Delaunay) {
+        for (t, tri) in d.triangles.iter().enumerate() {
+            let [a, b, c] = tri.map(|i| d.points[i]);
+            assert!(orient2d(&a, &b, &c) > 0.0, "triangle {t} is not counter-clockwise");
+            for e in 0..3 {
+                let (from, to) = (tri[e], tri[(e + 1) % 3]);
+                let twin = d.triangles.iter().position(|u| (0..3).any(|k| u[k] == to && u[(k + 1) % 3] == from));
+                assert_eq!(d.adjacent[t][e], twin, "triangle {t} edge {e}");
+                if let Some(u) = twin {
+                    let k = (0..3).find(|&k| d.triangles[u][k] == to).unwrap();
+                    assert_eq!(d.adjacent[u][k], Some(t));
+                }
+            }
+        }
+    }
+
+    fn check_empty_circumcircles(d: &Delaunay) {
+        for tri in &d.triangles {
+            let [a, b, c] = tri.map(|i| d.points[i]);
+            for (i, q) in d.points.iter().enumerate() {
+                if !tri.contains(&i) {
+                    assert!(incircle(&a, &b, &c, q) <= 0.0, "point {i} is inside the circumcircle of {tri:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_points_are_delaunay() {
+        let mut rng = Rng(47);
+        for n in [3, 10, 100, 400] {
+            let points = points(&mut rng, n);
+            let d = Delaunay::from_points(&points);
+            check_mesh(&d);
+            check_empty_circumcircles(&d);
+            // Euler for a triangulated point set: 2n - 2 - hull triangles.
+            let hull = d.adjacent.iter().flatten().filter(|a| a.is_none()).count();
+            assert_eq!(d.triangles.len(), 2 * n - 2 - hull);
+        }
+    }
+
+    #[test]
+    fn grid_with_duplicates_and_cocircular_points() {
+        let mut points: Vec<Vector2> = (0..36).map(|i| p((i % 6) as f32, (i / 6) as f32)).collect();
+        points.extend([p(2.0, 3.0), p(0.0, 0.0)]);
+        let d = Delaunay::from_points(&points);
+        check_mesh(&d);
+        check_empty_circumcircles(&d);
+        assert_eq!(d.triangles.len(), 50);
+        let area: f32 = d.triangles.iter().map(|t| signed_area(&t.map(|i| points[i]))).sum();
+        assert_eq!(area, 25.0);
+    }
+
+    #[test]
+    fn collinear_input_has_no_triangles() {
+        let d = Delaunay::from_points(&[p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0)]);
+        assert!(d.triangles.is_empty());
+        assert!(Delaunay::from_points(&[]).triangles.is_empty());
+    }
+
+    #[test]
+    fn constrained_edges_are_present() {
+        let mut rng = Rng(48);
+        let mut points = points(&mut rng, 200);
+        // A zigzag the unconstrained triangulation would not contain.
+        let first = points.len();
+        points.extend([p(-9.0, -1.0), p(-3.0, 1.0), p(3.0, -1.0), p(9.0, 1.0)]);
+        let edges = [(first, first + 1), (first + 1, first + 2), (first + 2, first + 3), (5, 77)];
+        let d = Delaunay::from_points_constrained(&points, &edges);
+        check_mesh(&d);
+        for &(a, b) in &d.constrained {
+            assert!(has_edge(&d, a, b), "constrained edge {a}-{b} is missing");
+        }
+        // Every piece lies on its requested edge, and together they join its ends.
+        for &(a, b) in &edges {
+            let (pa, pb) = (points[a], points[b]);
+            let pieces: Vec<_> = d
+                .constrained
+                .iter()
+                .filter(|&&(x, y)| orient2d(&pa, &pb, &points[x]) == 0.0 && orient2d(&pa, &pb, &points[y]) == 0.0)
+                .collect();
+            let length: f32 = pieces.iter().map(|&&(x, y)| (points[x] - points[y]).get_mag()).sum();
+            assert!((length - (pb - pa).get_mag()).abs() < 1e-3, "edge {a}-{b}");
+        }
+    }
+
+    #[test]
+    fn constraint_through_a_vertex_is_split() {
+        let points = [p(0.0, 0.0), p(1.0, 0.0), p(2.0, 0.0), p(1.0, 1.0), p(1.0, -1.0)];
+        let d = Delaunay::from_points_constrained(&points, &[(0, 2)]);
+        check_mesh(&d);
+        assert!(d.is_constrained(0, 1) && d.is_constrained(1, 2));
+        assert!(!d.is_constrained(0, 2));
+    }
+
+    #[test]
+    fn crossing_constraints_are_rejected() {
+        // The square's diagonals cross; the second is refused outright.
+        let points = [p(0.0, 0.0), p(1.0, 0.0), p(1.0, 1.0), p(0.0, 1.0), p(0.5, 0.1)];
+        let d = Delaunay::from_points_constrained(&points, &[(1, 3), (0, 2)]);
+        check_mesh(&d);
+        assert_eq!(d.constrained, HashSet::from([(1, 3)]));
+        assert!(has_edge(&d, 1, 3));
+        assert!(!has_edge(&d, 0, 2));
+
+        // A constraint with a valid first piece is still refused as a whole.
+        let points = [p(0.0, 0.0), p(1.0, 0.0), p(2.0, 0.0), p(3.0, 0.0), p(1.5, 1.0), p(1.5, -1.0), p(1.0, 1.0)];
+        let d = Delaunay::from_points_constrained(&points, &[(4, 5), (0, 3)]);
+        check_mesh(&d);
+        assert_eq!(d.constrained, HashSet::from([(4, 5)]));
+        assert!(has_edge(&d, 4, 5));
+    }
+
+    #[test]
+    fn duplicate_endpoints_use_the_kept_point() {
+        let points = [p(0.0, 0.0), p(2.0, 0.0), p(2.0, 2.0), p(0.0, 2.0), p(1.0, 1.5), p(2.0, 0.0)];
+        let d = Delaunay::from_points_constrained(&points, &[(5, 3)]);
+        check_mesh(&d);
+        let kept = if d.triangles.iter().flatten().any(|&i| i == 1) { 1 } else { 5 };
+        assert!(d.is_constrained(kept, 3));
+        assert!(has_edge(&d, kept, 3));
+    }
+
+    #[test]
+    fn voronoi_cells_tile_the_bounds() {
+        let mut rng = Rng(49);
+        let mut points = points(&mut rng, 60);
+        points.push(points[7]);
+        let d = Delaunay::from_points(&points);
+        let bounds = Aabb2::new(p(-12.0, -11.0), p(12.0, 11.0));
+        let cells = d.voronoi(&bounds);
+        assert_eq!(cells.len(), points.len());
+        assert!(cells.last().unwrap().is_empty());
+        let area: f32 = cells.iter().map(|c| signed_area(c)).sum();
+        assert!((area - bounds.get_area()).abs() < 1e-2, "{area}");
+        for (i, cell) in cells.iter().enumerate().filter(|(_, c)| !c.is_empty()) {
+            assert!(signed_area(cell) > 0.0);
+            // Every point of the cell is at least as close to its site as to any other.
+            let centroid = cell.iter().fold(p(0.0, 0.0), |a, v| a + *v) * (1.0 / cell.len() as f32);
+            let nearest = (0..points.len())
+                .min_by(|&a, &b| (points[a] - centroid).get_mag_sqr().total_cmp(&(points[b] - centroid).get_mag_sqr()))
+                .unwrap();
+            assert!((points[nearest] - centroid).get_mag_sqr() >= (points[i] - centroid).get_mag_sqr() - 1e-4);
+        }
+    }
+}
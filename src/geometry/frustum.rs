@@ -0,0 +1,248 @@
+use crate::{
+    geometry::{aabb::Aabb3, obb::Obb, plane::Plane, sphere::Sphere},
+    mat4::Mat4,
+    vector3::Vector3,
+    vector4::Vector4,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthRange {
+    // OpenGL style clip space, -w <= z <= w
+    NegOneToOne,
+    // Direct3D / Vulkan / Metal style clip space, 0 <= z <= w
+    ZeroToOne,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Containment {
+    Outside,
+    Intersect,
+    Inside,
+}
+
+pub const FRUSTUM_LEFT: usize = 0;
+pub const FRUSTUM_RIGHT: usize = 1;
+pub const FRUSTUM_BOTTOM: usize = 2;
+pub const FRUSTUM_TOP: usize = 3;
+pub const FRUSTUM_NEAR: usize = 4;
+pub const FRUSTUM_FAR: usize = 5;
+
+// Every plane bit set, for starting a hierarchical traversal.
+pub const FRUSTUM_ALL_PLANES: u8 = 0b11_1111;
+
+// Six normalized planes with normals pointing into the frustum.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+    pub corners: [Vector3; 8],
+}
+
+impl Frustum {
+    // Gribb–Hartmann plane extraction. pmath multiplies row vectors on the
+    // left, so clip = p * m and the planes come from the matrix columns.
+    pub fn from_matrix(m: &Mat4, depth: DepthRange) -> Self {
+        let c0 = m.get_col(0);
+        let c1 = m.get_col(1);
+        let c2 = m.get_col(2);
+        let c3 = m.get_col(3);
+        let near = match depth {
+            DepthRange::NegOneToOne => c3 + c2,
+            DepthRange::ZeroToOne => c2,
+        };
+        let planes = [c3 + c0, c3 - c0, c3 + c1, c3 - c1, near, c3 - c2]
+            .map(|p| Plane::new(Vector3::from_vec4(&p), p.w).get_norm());
+
+        let z_near = match depth {
+            DepthRange::NegOneToOne => -1.0,
+            DepthRange::ZeroToOne => 0.0,
+        };
+        let inv = m.get_inv();
+        let corners = std::array::from_fn(|i| {
+            let ndc = Vector4::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { z_near } else { 1.0 },
+                1.0,
+            );
+            let p = ndc * inv;
+            Vector3::from_vec4(&p) * (1.0 / p.w)
+        });
+        Self { planes, corners }
+    }
+
+    pub fn contains_point(&self, p: &Vector3) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(p) >= 0.0)
+    }
+
+    pub fn test_sphere(&self, sphere: &Sphere) -> Containment {
+        self.test_planes(FRUSTUM_ALL_PLANES, |plane| {
+            (plane.signed_distance(&sphere.center), sphere.radius)
+        })
+        .0
+    }
+
+    pub fn test_aabb(&self, aabb: &Aabb3) -> Containment {
+        self.test_aabb_masked(aabb, FRUSTUM_ALL_PLANES).0
+    }
+
+    pub fn test_obb(&self, obb: &Obb) -> Containment {
+        self.test_obb_masked(obb, FRUSTUM_ALL_PLANES).0
+    }
+
+    // Only planes whose bit is set in mask are tested. The returned mask has
+    // the bits cleared for planes the volume is fully inside, so children of
+    // this volume can skip them.
+    pub fn test_sphere_masked(&self, sphere: &Sphere, mask: u8) -> (Containment, u8) {
+        self.test_planes(mask, |plane| (plane.signed_distance(&sphere.center), sphere.radius))
+    }
+
+    pub fn test_aabb_masked(&self, aabb: &Aabb3, mask: u8) -> (Containment, u8) {
+        let center = aabb.get_center();
+        let extents = aabb.get_extents();
+        self.test_planes(mask, |plane| {
+            (plane.signed_distance(&center), plane.normal.abs().dot(&extents))
+        })
+    }
+
+    pub fn test_obb_masked(&self, obb: &Obb, mask: u8) -> (Containment, u8) {
+        let axes = obb.get_axes();
+        let h = obb.half_extents;
+        self.test_planes(mask, |plane| {
+            let radius = plane.normal.dot(&axes[0]).abs() * h.x
+                + plane.normal.dot(&axes[1]).abs() * h.y
+                + plane.normal.dot(&axes[2]).abs() * h.z;
+            (plane.signed_distance(&obb.center), radius)
+        })
+    }
+
+    // dist_radius gives the center's signed distance and the volume's
+    // projected radius along the plane normal.
+    fn test_planes<F>(&self, mask: u8, dist_radius: F) -> (Containment, u8)
+    where
+        F: Fn(&Plane) -> (f32, f32),
+    {
+        let mut out_mask = mask;
+        let mut result = Containment::Inside;
+        for (i, plane) in self.planes.iter().enumerate() {
+            let bit = 1 << i;
+            if mask & bit == 0 {
+                continue;
+            }
+            let (dist, radius) = dist_radius(plane);
+            if dist < -radius {
+                return (Containment::Outside, mask);
+            }
+            if dist < radius {
+                result = Containment::Intersect;
+            } else {
+                out_mask &= !bit;
+            }
+        }
+        (result, out_mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Deg, quat::Quat};
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    // Looks down +z with |x| <= z, |y| <= z and 1 <= z <= 10.
+    fn perspective() -> Frustum {
+        let k = 10.0 / 9.0;
+        let m = Mat4::from([
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, k, 1.0,
+            0.0, 0.0, -k, 0.0,
+        ]);
+        Frustum::from_matrix(&m, DepthRange::ZeroToOne)
+    }
+
+    // The box |x| <= 2, |y| <= 1, 1 <= z <= 5.
+    fn orthographic() -> Frustum {
+        let m = Mat4::from([
+            0.5, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 0.5, 0.0,
+            0.0, 0.0, -1.5, 1.0,
+        ]);
+        Frustum::from_matrix(&m, DepthRange::NegOneToOne)
+    }
+
+    #[test]
+    fn corners_follow_the_depth_range() {
+        let f = perspective();
+        crate::assert_approx_eq!(f.corners[0], v(-1.0, -1.0, 1.0));
+        crate::assert_approx_eq!(f.corners[3], v(1.0, 1.0, 1.0));
+        crate::assert_approx_eq!(f.corners[4], v(-10.0, -10.0, 10.0));
+        crate::assert_approx_eq!(f.corners[7], v(10.0, 10.0, 10.0));
+        let o = orthographic();
+        crate::assert_approx_eq!(o.corners[0], v(-2.0, -1.0, 1.0));
+        crate::assert_approx_eq!(o.corners[7], v(2.0, 1.0, 5.0));
+    }
+
+    #[test]
+    fn planes_face_inwards_and_pass_through_the_corners() {
+        for f in [perspective(), orthographic()] {
+            let center = f.corners.iter().fold(Vector3::zero(), |a, c| a + *c) * 0.125;
+            for plane in &f.planes {
+                crate::assert_approx_eq!(plane.normal.get_mag(), 1.0);
+                assert!(plane.signed_distance(&center) > 0.0);
+                assert!(f.corners.iter().all(|c| plane.signed_distance(c) > -1e-4));
+            }
+        }
+        let f = perspective();
+        crate::assert_approx_eq!(f.planes[FRUSTUM_NEAR].normal, v(0.0, 0.0, 1.0));
+        crate::assert_approx_eq!(f.planes[FRUSTUM_NEAR].d, -1.0);
+        crate::assert_approx_eq!(f.planes[FRUSTUM_FAR].normal, v(0.0, 0.0, -1.0));
+        crate::assert_approx_eq!(f.planes[FRUSTUM_LEFT].normal, v(1.0, 0.0, 1.0).get_norm());
+    }
+
+    #[test]
+    fn points() {
+        let f = perspective();
+        assert!(f.contains_point(&v(0.0, 0.0, 5.0)));
+        assert!(f.contains_point(&v(4.9, -4.9, 5.0)));
+        assert!(!f.contains_point(&v(5.1, 0.0, 5.0)));
+        assert!(!f.contains_point(&v(0.0, 0.0, 0.5)));
+        assert!(!f.contains_point(&v(0.0, 0.0, 10.5)));
+    }
+
+    #[test]
+    fn volumes() {
+        let f = orthographic();
+        assert_eq!(f.test_sphere(&Sphere::new(v(0.0, 0.0, 3.0), 0.5)), Containment::Inside);
+        assert_eq!(f.test_sphere(&Sphere::new(v(2.0, 0.0, 3.0), 0.5)), Containment::Intersect);
+        assert_eq!(f.test_sphere(&Sphere::new(v(3.0, 0.0, 3.0), 0.5)), Containment::Outside);
+
+        let inside = Aabb3::new(v(-1.0, -0.5, 2.0), v(1.0, 0.5, 4.0));
+        assert_eq!(f.test_aabb(&inside), Containment::Inside);
+        assert_eq!(f.test_aabb(&Aabb3::new(v(1.0, 0.0, 2.0), v(3.0, 0.5, 3.0))), Containment::Intersect);
+        assert_eq!(f.test_aabb(&Aabb3::new(v(-1.0, -1.0, 6.0), v(1.0, 1.0, 7.0))), Containment::Outside);
+
+        // Rotated 45 degrees about z the box reaches 0.3 * sqrt(2) along x.
+        let q = Quat::from_axis_angle(&v(0.0, 0.0, 1.0), Deg(45.0));
+        let obb = Obb::new(v(1.5, 0.0, 3.0), v(0.3, 0.3, 0.3), q);
+        assert_eq!(f.test_obb(&obb), Containment::Inside);
+        let obb = Obb::new(v(1.75, 0.0, 3.0), v(0.3, 0.3, 0.3), q);
+        assert_eq!(f.test_obb(&obb), Containment::Intersect);
+    }
+
+    #[test]
+    fn masks_drop_planes_the_volume_is_inside() {
+        let f = orthographic();
+        let (c, mask) = f.test_sphere_masked(&Sphere::new(v(1.9, 0.0, 3.0), 0.5), FRUSTUM_ALL_PLANES);
+        assert_eq!(c, Containment::Intersect);
+        assert_eq!(mask, 1 << FRUSTUM_RIGHT);
+        // With the right plane masked off the same sphere reads as inside.
+        let (c, _) = f.test_sphere_masked(&Sphere::new(v(1.9, 0.0, 3.0), 0.5), mask & !(1 << FRUSTUM_RIGHT));
+        assert_eq!(c, Containment::Inside);
+        let (c, mask) = f.test_aabb_masked(&Aabb3::new(v(-1.0, -0.5, 2.0), v(1.0, 0.5, 4.0)), FRUSTUM_ALL_PLANES);
+        assert_eq!((c, mask), (Containment::Inside, 0));
+    }
+}
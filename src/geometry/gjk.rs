@@ -0,0 +1,585 @@
+use crate::{
+    geometry::{
+        aabb::Aabb3,
+        capsule::Capsule,
+        closest::{closest_point_segment, closest_point_triangle},
+        obb::Obb,
+        sphere::Sphere,
+    },
+    quat::Quat,
+    vector3::Vector3,
+};
+
+const GJK_MAX_ITERATIONS: usize = 64;
+const GJK_EPSILON: f32 = 1e-10;
+const GJK_TOLERANCE: f32 = 1e-5;
+const GJK_MIN_WEIGHT: f32 = 1e-6;
+const GJK_FLATNESS: f32 = 1e-5;
+const EPA_MAX_ITERATIONS: usize = 128;
+const EPA_TOLERANCE: f32 = 1e-4;
+
+// The point of the shape furthest along dir. dir need not be unit length.
+pub trait SupportMap {
+    fn support(&self, dir: &Vector3) -> Vector3;
+}
+
+impl SupportMap for Vector3 {
+    fn support(&self, _dir: &Vector3) -> Vector3 {
+        *self
+    }
+}
+
+impl SupportMap for Sphere {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        let mag = dir.get_mag();
+        if mag <= GJK_EPSILON {
+            return self.center;
+        }
+        self.center + *dir * (self.radius / mag)
+    }
+}
+
+impl SupportMap for Aabb3 {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        Vector3::new(
+            if dir.x >= 0.0 { self.max.x } else { self.min.x },
+            if dir.y >= 0.0 { self.max.y } else { self.min.y },
+            if dir.z >= 0.0 { self.max.z } else { self.min.z },
+        )
+    }
+}
+
+impl SupportMap for Obb {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        let mut out = self.center;
+        for (i, axis) in self.get_axes().iter().enumerate() {
+            let h = self.half_extents[i];
+            out += *axis * if axis.dot(dir) >= 0.0 { h } else { -h };
+        }
+        out
+    }
+}
+
+impl SupportMap for Capsule {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        let end = if self.a.dot(dir) >= self.b.dot(dir) { self.a } else { self.b };
+        Sphere::new(end, self.radius).support(dir)
+    }
+}
+
+// The convex hull of a point cloud.
+impl SupportMap for [Vector3] {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        *self
+            .iter()
+            .max_by(|a, b| a.dot(dir).total_cmp(&b.dot(dir)))
+            .expect("support of an empty point set")
+    }
+}
+
+impl SupportMap for Vec<Vector3> {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        self.as_slice().support(dir)
+    }
+}
+
+// A shape defined in local space, rotated by orientation and then moved to position.
+#[derive(Clone, Copy, Debug)]
+pub struct Transformed<'a, S: SupportMap + ?Sized> {
+    pub shape: &'a S,
+    pub position: Vector3,
+    pub orientation: Quat,
+}
+
+impl<'a, S: SupportMap + ?Sized> Transformed<'a, S> {
+    pub fn new(shape: &'a S, position: Vector3, orientation: Quat) -> Self {
+        Self { shape, position, orientation }
+    }
+}
+
+impl<S: SupportMap + ?Sized> SupportMap for Transformed<'_, S> {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        let local_dir = self.orientation.get_conj().lqcvq(dir);
+        self.position + self.orientation.lqcvq(&self.shape.support(&local_dir))
+    }
+}
+
+// Closest points of two separated shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Separation {
+    pub point_a: Vector3,
+    pub point_b: Vector3,
+    pub distance: f32,
+}
+
+// normal points from a towards b; moving b by normal * depth separates the shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Penetration {
+    pub normal: Vector3,
+    pub depth: f32,
+    pub point_a: Vector3,
+    pub point_b: Vector3,
+}
+
+// A vertex of the Minkowski difference a - b, with the points it came from.
+#[derive(Clone, Copy, Debug)]
+struct SupportPoint {
+    w: Vector3,
+    a: Vector3,
+    b: Vector3,
+}
+
+fn support<A, B>(a: &A, b: &B, dir: &Vector3) -> SupportPoint
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let pa = a.support(dir);
+    let pb = b.support(&-*dir);
+    SupportPoint { w: pa - pb, a: pa, b: pb }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Simplex {
+    points: [SupportPoint; 4],
+    weights: [f32; 4],
+    len: usize,
+}
+
+impl Simplex {
+    fn new(p: SupportPoint) -> Self {
+        Self {
+            points: [p; 4],
+            weights: [1.0, 0.0, 0.0, 0.0],
+            len: 1,
+        }
+    }
+
+    fn push(&mut self, p: SupportPoint) {
+        self.points[self.len] = p;
+        self.len += 1;
+    }
+
+    // Exact, since Vector3's == allows for MATH_TOLERANCE and would stop GJK
+    // early on small shapes.
+    fn contains(&self, w: &Vector3) -> bool {
+        self.points[..self.len]
+            .iter()
+            .any(|p| p.w.x == w.x && p.w.y == w.y && p.w.z == w.z)
+    }
+
+    // Finds the point of the simplex closest to the origin and drops the
+    // vertices it does not depend on. None when a tetrahedron encloses the origin.
+    fn solve(&mut self) -> Option<Vector3> {
+        let origin = Vector3::zero();
+        let w: [Vector3; 4] = std::array::from_fn(|i| self.points[i].w);
+        match self.len {
+            1 => self.weights = [1.0, 0.0, 0.0, 0.0],
+            2 => {
+                let t = closest_point_segment(&origin, &w[0], &w[1]).t;
+                self.weights = [1.0 - t, t, 0.0, 0.0];
+            }
+            3 => {
+                let tp = closest_point_triangle(&origin, &w[0], &w[1], &w[2]);
+                self.weights = [1.0 - tp.u - tp.v, tp.u, tp.v, 0.0];
+            }
+            _ => {
+                // A nearly flat tetrahedron cannot reliably tell which side of a
+                // face the origin is on, so it is treated as enclosing nothing.
+                let scale = (1..4).map(|i| (w[i] - w[0]).get_mag()).fold(0.0, f32::max);
+                let volume = (w[1] - w[0]).dot(&(w[2] - w[0]).cross(&(w[3] - w[0])));
+                let flat = volume.abs() <= GJK_FLATNESS * scale * scale * scale;
+
+                // Faces the origin lies in front of, paired with the opposite vertex.
+                let faces = [([0, 1, 2], 3), ([0, 1, 3], 2), ([0, 2, 3], 1), ([1, 2, 3], 0)];
+                let mut best: Option<(f32, [usize; 3], [f32; 3])> = None;
+                for ([i, j, k], opp) in faces {
+                    let n = (w[j] - w[i]).cross(&(w[k] - w[i]));
+                    let side_origin = -n.dot(&w[i]);
+                    let side_opp = n.dot(&(w[opp] - w[i]));
+                    if !flat && side_origin * side_opp > 0.0 {
+                        continue;
+                    }
+                    let tp = closest_point_triangle(&origin, &w[i], &w[j], &w[k]);
+                    let dist_sqr = tp.point.get_mag_sqr();
+                    if best.is_none_or(|b| dist_sqr < b.0) {
+                        best = Some((dist_sqr, [i, j, k], [1.0 - tp.u - tp.v, tp.u, tp.v]));
+                    }
+                }
+                let (_, idx, face_weights) = best?;
+                self.weights = [0.0; 4];
+                for (i, weight) in idx.into_iter().zip(face_weights) {
+                    self.weights[i] = weight;
+                }
+            }
+        }
+
+        // Negligible weights would leave a degenerate simplex behind.
+        let mut len = 0;
+        let mut total = 0.0;
+        for i in 0..self.len {
+            if self.weights[i] > GJK_MIN_WEIGHT {
+                self.points[len] = self.points[i];
+                self.weights[len] = self.weights[i];
+                total += self.weights[i];
+                len += 1;
+            }
+        }
+        self.len = len;
+        for weight in &mut self.weights[..len] {
+            *weight /= total;
+        }
+        Some(self.combine(|p| p.w))
+    }
+
+    fn combine<F: Fn(&SupportPoint) -> Vector3>(&self, f: F) -> Vector3 {
+        let mut out = Vector3::zero();
+        for i in 0..self.len {
+            out += f(&self.points[i]) * self.weights[i];
+        }
+        out
+    }
+}
+
+enum GjkResult {
+    Separated(Simplex, Vector3),
+    Overlapping(Simplex),
+}
+
+fn gjk<A, B>(a: &A, b: &B) -> GjkResult
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let mut simplex = Simplex::new(support(a, b, &Vector3::new(1.0, 0.0, 0.0)));
+    let mut v = simplex.points[0].w;
+    for _ in 0..GJK_MAX_ITERATIONS {
+        let v_sqr = v.get_mag_sqr();
+        if v_sqr <= GJK_EPSILON {
+            return GjkResult::Overlapping(simplex);
+        }
+        let p = support(a, b, &-v);
+        if v_sqr - v.dot(&p.w) <= GJK_TOLERANCE * v_sqr || simplex.contains(&p.w) {
+            break;
+        }
+        let prev = simplex;
+        simplex.push(p);
+        match simplex.solve() {
+            None => return GjkResult::Overlapping(simplex),
+            Some(next) if next.get_mag_sqr() >= v_sqr => {
+                // No progress left to make at this precision.
+                simplex = prev;
+                break;
+            }
+            Some(next) => v = next,
+        }
+    }
+    GjkResult::Separated(simplex, v)
+}
+
+pub fn gjk_intersects<A, B>(a: &A, b: &B) -> bool
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    matches!(gjk(a, b), GjkResult::Overlapping(_))
+}
+
+// None when the shapes overlap.
+pub fn gjk_distance<A, B>(a: &A, b: &B) -> Option<Separation>
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    match gjk(a, b) {
+        GjkResult::Separated(simplex, v) => Some(Separation {
+            point_a: simplex.combine(|p| p.a),
+            point_b: simplex.combine(|p| p.b),
+            distance: v.get_mag(),
+        }),
+        GjkResult::Overlapping(_) => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct EpaFace {
+    idx: [usize; 3],
+    normal: Vector3,
+    dist: f32,
+}
+
+fn epa_face(verts: &[SupportPoint], i: usize, j: usize, k: usize) -> EpaFace {
+    let normal = (verts[j].w - verts[i].w).cross(&(verts[k].w - verts[i].w)).get_norm();
+    EpaFace {
+        idx: [i, j, k],
+        normal,
+        dist: normal.dot(&verts[i].w),
+    }
+}
+
+// Grows the simplex GJK stopped with into a tetrahedron around the origin.
+// When the Minkowski difference is too flat for that, returns a normal of
+// the point, segment or triangle it collapses to instead.
+fn blow_up<A, B>(a: &A, b: &B, mut simplex: Simplex) -> Result<[SupportPoint; 4], Vector3>
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let axes = [
+        Vector3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+        Vector3::new(0.0, 0.0, 1.0),
+    ];
+    if simplex.len == 1 {
+        let w0 = simplex.points[0].w;
+        let p = axes
+            .iter()
+            .flat_map(|axis| [*axis, -*axis])
+            .map(|dir| support(a, b, &dir))
+            .find(|p| (p.w - w0).get_mag_sqr() > GJK_EPSILON)
+            .ok_or(axes[0])?;
+        simplex.push(p);
+    }
+    if simplex.len == 2 {
+        let d = simplex.points[1].w - simplex.points[0].w;
+        let axis = axes
+            .iter()
+            .min_by(|x, y| x.dot(&d).abs().total_cmp(&y.dot(&d).abs()))
+            .unwrap();
+        let perp = d.cross(axis);
+        let perp2 = d.cross(&perp);
+        let p = [perp, perp2, -perp, -perp2]
+            .iter()
+            .map(|dir| support(a, b, dir))
+            .find(|p| (p.w - simplex.points[0].w).cross(&d).get_mag_sqr() > GJK_EPSILON)
+            .ok_or(perp.get_norm())?;
+        simplex.push(p);
+    }
+    if simplex.len == 3 {
+        let w = [simplex.points[0].w, simplex.points[1].w, simplex.points[2].w];
+        let n = (w[1] - w[0]).cross(&(w[2] - w[0]));
+        let p = [n, -n]
+            .iter()
+            .map(|dir| support(a, b, dir))
+            .find(|p| n.dot(&(p.w - w[0])).abs() > GJK_EPSILON)
+            .ok_or(n.get_norm())?;
+        simplex.push(p);
+    }
+    Ok(simplex.points)
+}
+
+// Expanding polytope algorithm. None when the shapes do not overlap. Shapes
+// whose Minkowski difference is flat, such as coplanar polygons, only touch
+// and give a depth of zero.
+pub fn epa_penetration<A, B>(a: &A, b: &B) -> Option<Penetration>
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let GjkResult::Overlapping(simplex) = gjk(a, b) else {
+        return None;
+    };
+    let tetra = match blow_up(a, b, simplex) {
+        Ok(tetra) => tetra,
+        Err(normal) => {
+            return Some(Penetration {
+                normal,
+                depth: 0.0,
+                point_a: simplex.combine(|p| p.a),
+                point_b: simplex.combine(|p| p.b),
+            })
+        }
+    };
+
+    let mut verts = tetra.to_vec();
+    let centroid = (tetra[0].w + tetra[1].w + tetra[2].w + tetra[3].w) * 0.25;
+    let mut faces: Vec<EpaFace> = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+        .into_iter()
+        .map(|[i, j, k]| {
+            let face = epa_face(&verts, i, j, k);
+            if face.normal.dot(&(verts[i].w - centroid)) < 0.0 {
+                epa_face(&verts, i, k, j)
+            } else {
+                face
+            }
+        })
+        .collect();
+
+    let mut closest = faces[0];
+    for _ in 0..EPA_MAX_ITERATIONS {
+        closest = *faces
+            .iter()
+            .min_by(|x, y| x.dist.total_cmp(&y.dist))
+            .unwrap();
+        let p = support(a, b, &closest.normal);
+        if p.w.dot(&closest.normal) - closest.dist <= EPA_TOLERANCE * (1.0 + closest.dist) {
+            break;
+        }
+
+        // Remove every face p can see, then stitch the horizon to p.
+        let mut horizon: Vec<(usize, usize)> = Vec::new();
+        faces.retain(|f| {
+            if f.normal.dot(&(p.w - verts[f.idx[0]].w)) <= 0.0 {
+                return true;
+            }
+            for e in 0..3 {
+                let edge = (f.idx[e], f.idx[(e + 1) % 3]);
+                if let Some(shared) = horizon.iter().position(|h| *h == (edge.1, edge.0)) {
+                    horizon.swap_remove(shared);
+                } else {
+                    horizon.push(edge);
+                }
+            }
+            false
+        });
+        let new = verts.len();
+        verts.push(p);
+        faces.extend(horizon.iter().map(|&(i, j)| epa_face(&verts, i, j, new)));
+        if faces.is_empty() {
+            break;
+        }
+    }
+
+    let [i, j, k] = closest.idx;
+    let tp = closest_point_triangle(&(closest.normal * closest.dist), &verts[i].w, &verts[j].w, &verts[k].w);
+    let weights = [1.0 - tp.u - tp.v, tp.u, tp.v];
+    let point_a = verts[i].a * weights[0] + verts[j].a * weights[1] + verts[k].a * weights[2];
+    let point_b = verts[i].b * weights[0] + verts[j].b * weights[1] + verts[k].b * weights[2];
+    Some(Penetration {
+        normal: closest.normal,
+        depth: closest.dist,
+        point_a,
+        point_b,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Deg, test_util::Rng};
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    fn obb(rng: &mut Rng) -> Obb {
+        let half = v(rng.next().abs() * 0.3 + 0.1, rng.next().abs() * 0.3 + 0.1, rng.next().abs() * 0.3 + 0.1);
+        let q = Quat::from_axis_angle(&rng.vec3(), Deg(rng.next() * 18.0));
+        Obb::new(rng.vec3() * 0.4, half, q)
+    }
+
+    #[test]
+    fn sphere_distance() {
+        let a = Sphere::new(v(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(v(3.0, 4.0, 0.0), 2.0);
+        let sep = gjk_distance(&a, &b).unwrap();
+        assert!((sep.distance - 2.0).abs() < 1e-3, "{}", sep.distance);
+        crate::assert_approx_eq!(sep.point_a, v(0.6, 0.8, 0.0), 1e-2);
+        crate::assert_approx_eq!(sep.point_b, v(1.8, 2.4, 0.0), 1e-2);
+        assert!(!gjk_intersects(&a, &b));
+    }
+
+    #[test]
+    fn box_distance() {
+        let a = Aabb3::new(v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0));
+        let b = Aabb3::new(v(3.0, 0.5, 0.5), v(4.0, 2.0, 2.0));
+        let sep = gjk_distance(&a, &b).unwrap();
+        assert!((sep.distance - 2.0).abs() < 1e-5);
+        assert_eq!(sep.point_b.x - sep.point_a.x, 2.0);
+        // A point inside the box overlaps it.
+        assert!(gjk_intersects(&a, &v(0.5, 0.5, 0.5)));
+        assert!(gjk_distance(&a, &v(0.5, 0.5, 0.5)).is_none());
+    }
+
+    #[test]
+    fn tiny_shapes_still_converge() {
+        // Both shapes are far smaller than MATH_TOLERANCE.
+        let a = Sphere::new(v(0.0, 0.0, 0.0), 1e-4);
+        let b = Sphere::new(v(3e-4, 4e-4, 0.0), 1e-4);
+        let sep = gjk_distance(&a, &b).unwrap();
+        assert!((sep.distance - 3e-4).abs() < 1e-5, "{}", sep.distance);
+    }
+
+    #[test]
+    fn epa_sphere_depth_is_analytic() {
+        let a = Sphere::new(v(0.0, 0.0, 0.0), 1.0);
+        for (center, radius) in [(v(1.5, 0.0, 0.0), 1.0), (v(0.0, -1.0, 1.0), 0.8), (v(0.3, 0.4, 0.0), 2.0)] {
+            let b = Sphere::new(center, radius);
+            let pen = epa_penetration(&a, &b).unwrap();
+            let expected = 1.0 + radius - center.get_mag();
+            assert!((pen.depth - expected).abs() < 1e-2, "{} vs {expected}", pen.depth);
+            crate::assert_approx_eq!(pen.normal, center.get_norm(), 2e-2);
+        }
+    }
+
+    #[test]
+    fn epa_box_depth_is_analytic() {
+        let a = Aabb3::new(v(-1.0, -1.0, -1.0), v(1.0, 1.0, 1.0));
+        let b = Aabb3::new(v(0.5, -0.7, -0.2), v(2.5, 1.3, 1.8));
+        let pen = epa_penetration(&a, &b).unwrap();
+        assert!((pen.depth - 0.5).abs() < 1e-4, "{}", pen.depth);
+        crate::assert_approx_eq!(pen.normal, v(1.0, 0.0, 0.0));
+
+        let moved = Aabb3::new(b.min + pen.normal * (pen.depth + 1e-3), b.max + pen.normal * (pen.depth + 1e-3));
+        assert!(!gjk_intersects(&a, &moved));
+
+        // The same overlap with both boxes rotated together.
+        let q = Quat::from_axis_angle(&v(1.0, 1.0, 0.0), Deg(30.0));
+        let ta = Transformed::new(&a, Vector3::zero(), q);
+        let tb = Transformed::new(&b, Vector3::zero(), q);
+        let pen = epa_penetration(&ta, &tb).unwrap();
+        assert!((pen.depth - 0.5).abs() < 1e-3, "{}", pen.depth);
+        crate::assert_approx_eq!(pen.normal, q.lqcvq(&v(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn epa_touching_and_flat_shapes_have_zero_depth() {
+        let p = v(1.0, 2.0, 3.0);
+        assert_eq!(epa_penetration(&p, &p).unwrap().depth, 0.0);
+
+        let tri = vec![v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0)];
+        let shifted: Vec<Vector3> = tri.iter().map(|p| *p + v(0.5, 0.5, 0.0)).collect();
+        let pen = epa_penetration(&tri, &shifted).unwrap();
+        assert_eq!(pen.depth, 0.0);
+        crate::assert_approx_eq!(pen.normal.abs(), v(0.0, 0.0, 1.0));
+        crate::assert_approx_eq!(pen.point_a, pen.point_b);
+
+        let a = Aabb3::new(v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0));
+        let b = Aabb3::new(v(1.0, 0.0, 0.0), v(2.0, 1.0, 1.0));
+        assert!(epa_penetration(&a, &b).unwrap().depth.abs() < 1e-4);
+        assert!(epa_penetration(&a, &Aabb3::new(v(1.1, 0.0, 0.0), v(2.0, 1.0, 1.0))).is_none());
+    }
+
+    #[test]
+    fn gjk_agrees_with_separating_axes() {
+        let mut rng = Rng(44);
+        let mut overlapping = 0;
+        for _ in 0..500 {
+            let (a, b) = (obb(&mut rng), obb(&mut rng));
+            // Skip near-contact pairs where either test may go either way.
+            let margin = match (gjk_distance(&a, &b), epa_penetration(&a, &b)) {
+                (Some(sep), _) => sep.distance,
+                (None, Some(pen)) => pen.depth,
+                (None, None) => unreachable!(),
+            };
+            if margin < 1e-3 {
+                continue;
+            }
+            let sat = a.intersects_obb(&b);
+            assert_eq!(gjk_intersects(&a, &b), sat, "{a:?} {b:?}");
+            overlapping += sat as usize;
+        }
+        assert!(overlapping > 50);
+    }
+
+    #[test]
+    fn capsule_and_hull_supports() {
+        let capsule = Capsule::new(v(0.0, 0.0, 0.0), v(0.0, 4.0, 0.0), 1.0);
+        assert_eq!(capsule.support(&v(0.0, 1.0, 0.0)), v(0.0, 5.0, 0.0));
+        assert_eq!(capsule.support(&v(1.0, -1.0, 0.0)), v(0.5f32.sqrt(), -(0.5f32.sqrt()), 0.0));
+        let sep = gjk_distance(&capsule, &Sphere::new(v(3.0, 2.0, 0.0), 1.0)).unwrap();
+        assert!((sep.distance - 1.0).abs() < 1e-3);
+        let hull = vec![v(0.0, 0.0, 0.0), v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0), v(0.0, 0.0, 1.0)];
+        assert_eq!(hull.support(&v(1.0, 0.1, 0.1)), v(1.0, 0.0, 0.0));
+    }
+}
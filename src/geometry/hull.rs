@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+
+use crate::{geometry::gjk::SupportMap, vector2::Vector2, vector3::Vector3};
+
+// adjacent[i] is the face across the edge indices[i] -> indices[(i + 1) % 3].
+// Indices wind counter-clockwise when seen from outside.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HullFace {
+    pub indices: [usize; 3],
+    pub normal: Vector3,
+    pub adjacent: [usize; 3],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexHull {
+    pub vertices: Vec<Vector3>,
+    pub faces: Vec<HullFace>,
+}
+
+// Planes are kept in f64: sliver triangles are common on dense, rounded input
+// and their f32 normals are too noisy to classify nearby points.
+struct BuildFace {
+    indices: [usize; 3],
+    normal: [f64; 3],
+    offset: f64,
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl BuildFace {
+    fn new(points: &[Vector3], indices: [usize; 3]) -> Self {
+        let [a, b, c] = indices.map(|i| points[i]);
+        let normal = normal_f64(&a, &b, &c);
+        Self {
+            indices,
+            normal,
+            offset: dot_f64(&normal, &a),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    fn distance(&self, p: &Vector3) -> f64 {
+        dot_f64(&self.normal, p) - self.offset
+    }
+}
+
+fn normal_f64(a: &Vector3, b: &Vector3, c: &Vector3) -> [f64; 3] {
+    // Differences of f32 values are exact in f64.
+    let [a, b, c] = [a, b, c].map(|v| [v.x as f64, v.y as f64, v.z as f64]);
+    let e1 = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let e2 = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let n = [
+        e1[1] * e2[2] - e1[2] * e2[1],
+        e1[2] * e2[0] - e1[0] * e2[2],
+        e1[0] * e2[1] - e1[1] * e2[0],
+    ];
+    let inv_mag = 1.0 / (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    n.map(|v| v * inv_mag)
+}
+
+fn dot_f64(n: &[f64; 3], p: &Vector3) -> f64 {
+    n[0] * p.x as f64 + n[1] * p.y as f64 + n[2] * p.z as f64
+}
+
+impl ConvexHull {
+    // Quickhull. Points within a scale-relative tolerance of the hull are
+    // treated as on it, which absorbs duplicate and coplanar input. Flat input
+    // gives a two-sided hull; None when the points are all collinear.
+    pub fn from_points(points: &[Vector3]) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+        let mut max_abs = Vector3::zero();
+        for p in points {
+            max_abs = max_abs.max(&p.abs());
+        }
+        let eps = 3.0 * f32::EPSILON * (max_abs.x + max_abs.y + max_abs.z);
+
+        // Widest pair of axis extremes, then the points furthest from their
+        // line and from the plane through all three.
+        let mut extremes = [0usize; 6];
+        for (i, p) in points.iter().enumerate() {
+            for axis in 0..3 {
+                if p[axis] < points[extremes[axis * 2]][axis] {
+                    extremes[axis * 2] = i;
+                }
+                if p[axis] > points[extremes[axis * 2 + 1]][axis] {
+                    extremes[axis * 2 + 1] = i;
+                }
+            }
+        }
+        let (i0, i1) = (0..3)
+            .map(|axis| (extremes[axis * 2], extremes[axis * 2 + 1]))
+            .max_by(|x, y| {
+                let dx = (points[x.1] - points[x.0]).get_mag_sqr();
+                let dy = (points[y.1] - points[y.0]).get_mag_sqr();
+                dx.total_cmp(&dy)
+            })
+            .unwrap();
+        let dir = (points[i1] - points[i0]).get_norm();
+        let line_dist = |p: &Vector3| (*p - points[i0]).cross(&dir).get_mag();
+        let i2 = furthest(points, line_dist);
+        if line_dist(&points[i2]) <= eps {
+            return None;
+        }
+        let normal = (points[i1] - points[i0]).cross(&(points[i2] - points[i0])).get_norm();
+        let plane_dist = |p: &Vector3| normal.dot(&(*p - points[i0])).abs();
+        let i3 = furthest(points, plane_dist);
+        if plane_dist(&points[i3]) <= eps {
+            return Self::from_planar(points, &normal, &dir);
+        }
+
+        let mut faces: Vec<BuildFace> = Vec::new();
+        let centroid = (points[i0] + points[i1] + points[i2] + points[i3]) * 0.25;
+        for [a, b, c] in [[i0, i1, i2], [i0, i1, i3], [i0, i2, i3], [i1, i2, i3]] {
+            let face = BuildFace::new(points, [a, b, c]);
+            faces.push(if face.distance(&centroid) > 0.0 {
+                BuildFace::new(points, [a, c, b])
+            } else {
+                face
+            });
+        }
+        // Directed edge -> owning face. The face across edge (a, b) owns (b, a).
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for (f, face) in faces.iter().enumerate() {
+            for e in 0..3 {
+                edges.insert((face.indices[e], face.indices[(e + 1) % 3]), f);
+            }
+        }
+        let unassigned: Vec<usize> = (0..points.len()).collect();
+        assign_outside(points, &mut faces, &[0, 1, 2, 3], unassigned, eps);
+
+        while let Some(start) = faces.iter().position(|f| f.alive && !f.outside.is_empty()) {
+            let eye = *faces[start]
+                .outside
+                .iter()
+                .max_by(|a, b| {
+                    let da = faces[start].distance(&points[**a]);
+                    let db = faces[start].distance(&points[**b]);
+                    da.total_cmp(&db)
+                })
+                .unwrap();
+
+            // Flood the faces the eye can see; the boundary is the horizon.
+            let mut visible = vec![start];
+            let mut horizon = Vec::new();
+            faces[start].alive = false;
+            let mut i = 0;
+            while i < visible.len() {
+                let f = visible[i];
+                for e in 0..3 {
+                    let edge = (faces[f].indices[e], faces[f].indices[(e + 1) % 3]);
+                    let n = edges[&(edge.1, edge.0)];
+                    if !faces[n].alive {
+                        continue;
+                    }
+                    // A strict test: accepting faces the eye is only just
+                    // behind would leave a slight concavity for later points.
+                    if faces[n].distance(&points[eye]) > 0.0 {
+                        faces[n].alive = false;
+                        visible.push(n);
+                    } else {
+                        horizon.push(edge);
+                    }
+                }
+                i += 1;
+            }
+
+            let mut orphans = Vec::new();
+            for &f in &visible {
+                for e in 0..3 {
+                    edges.remove(&(faces[f].indices[e], faces[f].indices[(e + 1) % 3]));
+                }
+                orphans.append(&mut faces[f].outside);
+            }
+            orphans.retain(|p| *p != eye);
+
+            let mut new_faces = Vec::with_capacity(horizon.len());
+            for (a, b) in horizon {
+                let f = faces.len();
+                let face = BuildFace::new(points, [a, b, eye]);
+                for e in 0..3 {
+                    edges.insert((face.indices[e], face.indices[(e + 1) % 3]), f);
+                }
+                faces.push(face);
+                new_faces.push(f);
+            }
+            assign_outside(points, &mut faces, &new_faces, orphans, eps);
+        }
+
+        let faces: Vec<[usize; 3]> = faces.iter().filter(|f| f.alive).map(|f| f.indices).collect();
+        Some(Self::from_triangles(points, &faces))
+    }
+
+    // Both sides of the 2D hull in the plane, fan triangulated. u lies in the
+    // plane, so the ring winds counter-clockwise about normal.
+    fn from_planar(points: &[Vector3], normal: &Vector3, u: &Vector3) -> Option<Self> {
+        let v = normal.cross(u);
+        let flat: Vec<Vector2> = points.iter().map(|p| Vector2::new(p.dot(u), p.dot(&v))).collect();
+        let ring = hull_indices_2d(&flat);
+        if ring.len() < 3 {
+            return None;
+        }
+        // The back is fanned from the next vertex so no directed edge repeats.
+        let back: Vec<usize> = ring[1..].iter().chain(&ring[..1]).copied().collect();
+        let front_count = ring.len() - 2;
+        let mut faces = Vec::with_capacity(2 * front_count);
+        for i in 1..ring.len() - 1 {
+            faces.push([ring[0], ring[i], ring[i + 1]]);
+        }
+        for i in 1..back.len() - 1 {
+            faces.push([back[0], back[i + 1], back[i]]);
+        }
+        let mut out = Self::from_triangles(points, &faces);
+        // Thin fan triangles give noisy normals; the plane's is exact.
+        for (i, face) in out.faces.iter_mut().enumerate() {
+            face.normal = if i < front_count { *normal } else { -*normal };
+        }
+        Some(out)
+    }
+
+    // Compacts the referenced points and links faces across shared edges.
+    fn from_triangles(points: &[Vector3], faces: &[[usize; 3]]) -> Self {
+        let mut remap = vec![usize::MAX; points.len()];
+        let mut vertices = Vec::new();
+        let faces: Vec<[usize; 3]> = faces
+            .iter()
+            .map(|tri| {
+                tri.map(|i| {
+                    if remap[i] == usize::MAX {
+                        remap[i] = vertices.len();
+                        vertices.push(points[i]);
+                    }
+                    remap[i]
+                })
+            })
+            .collect();
+
+        let mut edges = HashMap::with_capacity(faces.len() * 3);
+        for (f, tri) in faces.iter().enumerate() {
+            for e in 0..3 {
+                edges.insert((tri[e], tri[(e + 1) % 3]), f);
+            }
+        }
+        let faces = faces
+            .iter()
+            .map(|tri| {
+                let [a, b, c] = tri.map(|i| vertices[i]);
+                let n = normal_f64(&a, &b, &c);
+                HullFace {
+                    indices: *tri,
+                    normal: Vector3::new(n[0] as f32, n[1] as f32, n[2] as f32),
+                    adjacent: std::array::from_fn(|e| edges[&(tri[(e + 1) % 3], tri[e])]),
+                }
+            })
+            .collect();
+        Self { vertices, faces }
+    }
+
+    pub fn contains_point(&self, p: &Vector3) -> bool {
+        self.faces
+            .iter()
+            .all(|f| f.normal.dot(&(*p - self.vertices[f.indices[0]])) <= 0.0)
+    }
+}
+
+impl SupportMap for ConvexHull {
+    fn support(&self, dir: &Vector3) -> Vector3 {
+        self.vertices.support(dir)
+    }
+}
+
+fn furthest<F: Fn(&Vector3) -> f32>(points: &[Vector3], dist: F) -> usize {
+    (0..points.len())
+        .max_by(|a, b| dist(&points[*a]).total_cmp(&dist(&points[*b])))
+        .unwrap()
+}
+
+// Gives each point to the first candidate face it lies in front of; points
+// behind all of them are inside the hull and dropped.
+fn assign_outside(points: &[Vector3], faces: &mut [BuildFace], candidates: &[usize], unassigned: Vec<usize>, eps: f32) {
+    for p in unassigned {
+        if let Some(&f) = candidates.iter().find(|f| faces[**f].distance(&points[p]) > eps as f64) {
+            faces[f].outside.push(p);
+        }
+    }
+}
+
+// Andrew's monotone chain. Counter-clockwise, without collinear or duplicate points.
+pub fn convex_hull_2d(points: &[Vector2]) -> Vec<Vector2> {
+    hull_indices_2d(points).into_iter().map(|i| points[i]).collect()
+}
+
+fn hull_indices_2d(points: &[Vector2]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|a, b| {
+        points[*a]
+            .x
+            .total_cmp(&points[*b].x)
+            .then(points[*a].y.total_cmp(&points[*b].y))
+    });
+    order.dedup_by(|a, b| points[*a].x == points[*b].x && points[*a].y == points[*b].y);
+    if order.len() < 3 {
+        return order;
+    }
+
+    let turns_left = |hull: &[usize], p: usize| {
+        let [o, a] = [hull[hull.len() - 2], hull[hull.len() - 1]];
+        (points[a] - points[o]).cross(&(points[p] - points[o])) > 0.0
+    };
+    let mut hull: Vec<usize> = Vec::with_capacity(order.len() + 1);
+    for &p in &order {
+        while hull.len() >= 2 && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    let lower = hull.len() + 1;
+    for &p in order.iter().rev().skip(1) {
+        while hull.len() >= lower && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    // Every input point behind every face, a closed two-manifold with
+    // symmetric adjacency, and Euler's V - E + F = 2.
+    fn check_hull(hull: &ConvexHull, points: &[Vector3]) {
+        let scale = points.iter().fold(0.0f32, |m, p| m.max(p.abs().x).max(p.abs().y).max(p.abs().z));
+        let tolerance = 1e-4 * scale.max(1.0);
+        for (fi, f) in hull.faces.iter().enumerate() {
+            let [a, b, c] = f.indices.map(|i| hull.vertices[i]);
+            crate::assert_approx_eq!(f.normal, (b - a).cross(&(c - a)).get_norm(), 1e-3);
+            for p in points {
+                let d = f.normal.dot(&(*p - a));
+                assert!(d <= tolerance, "point {p:?} is {d} in front of face {fi}");
+            }
+            for e in 0..3 {
+                let g = &hull.faces[f.adjacent[e]];
+                let (i, j) = (f.indices[e], f.indices[(e + 1) % 3]);
+                let back = (0..3).find(|&k| g.indices[k] == j && g.indices[(k + 1) % 3] == i);
+                assert_eq!(back.map(|k| g.adjacent[k]), Some(fi), "face {fi} edge {e}");
+            }
+        }
+        for p in &hull.vertices {
+            assert!(points.contains(p));
+        }
+        let (v, f) = (hull.vertices.len() as i64, hull.faces.len() as i64);
+        assert_eq!(f * 3 % 2, 0);
+        assert_eq!(v - f * 3 / 2 + f, 2);
+    }
+
+    #[test]
+    fn cube_with_interior_points() {
+        let mut points: Vec<Vector3> = (0..8)
+            .map(|i| v((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32))
+            .collect();
+        points.extend([v(0.5, 0.5, 0.5), v(0.2, 0.7, 0.3), v(0.5, 0.5, 0.0), v(1.0, 0.5, 0.5)]);
+        // Duplicates are absorbed too.
+        points.push(points[3]);
+        let hull = ConvexHull::from_points(&points).unwrap();
+        check_hull(&hull, &points);
+        assert_eq!(hull.vertices.len(), 8);
+        assert_eq!(hull.faces.len(), 12);
+        assert!(hull.contains_point(&v(0.5, 0.5, 0.5)));
+        assert!(!hull.contains_point(&v(1.1, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn random_clouds() {
+        let mut rng = Rng(45);
+        for n in [4, 10, 50, 300] {
+            let points: Vec<Vector3> = (0..n).map(|_| rng.vec3()).collect();
+            check_hull(&ConvexHull::from_points(&points).unwrap(), &points);
+        }
+    }
+
+    #[test]
+    fn dense_sphere_surface() {
+        let mut rng = Rng(46);
+        let points: Vec<Vector3> = (0..1000).map(|_| rng.vec3().get_norm() * 5.0).collect();
+        let hull = ConvexHull::from_points(&points).unwrap();
+        check_hull(&hull, &points);
+        assert!(hull.vertices.len() > 900);
+    }
+
+    #[test]
+    fn flat_and_collinear_input() {
+        let square = [v(0.0, 0.0, 1.0), v(1.0, 0.0, 1.0), v(1.0, 1.0, 1.0), v(0.0, 1.0, 1.0), v(0.5, 0.5, 1.0)];
+        let hull = ConvexHull::from_points(&square).unwrap();
+        check_hull(&hull, &square);
+        assert_eq!(hull.vertices.len(), 4);
+        // Two-sided: two triangles facing each way.
+        assert_eq!(hull.faces.len(), 4);
+        assert_eq!(hull.faces.iter().filter(|f| f.normal.z > 0.0).count(), 2);
+
+        let line = [v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0), v(2.0, 2.0, 2.0)];
+        assert!(ConvexHull::from_points(&line).is_none());
+        assert!(ConvexHull::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn hull_2d() {
+        let points = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 2.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 2.0),
+            Vector2::new(2.0, 2.0),
+        ];
+        let hull = convex_hull_2d(&points);
+        assert_eq!(hull, vec![Vector2::new(0.0, 0.0), Vector2::new(2.0, 0.0), Vector2::new(2.0, 2.0), Vector2::new(0.0, 2.0)]);
+
+        let mut rng = Rng(47);
+        let points: Vec<Vector2> = (0..200).map(|_| Vector2::new(rng.next(), rng.next())).collect();
+        let hull = convex_hull_2d(&points);
+        for i in 0..hull.len() {
+            let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+            assert!(points.iter().all(|p| (b - a).cross(&(*p - a)) >= 0.0));
+        }
+        assert_eq!(convex_hull_2d(&points[..2]).len(), 2);
+    }
+}
@@ -0,0 +1,208 @@
+use std::ops::Index;
+
+use crate::{vector2::Vector2, vector3::Vector3};
+
+pub trait KdPoint: Copy + Index<usize, Output = f32> {
+    const DIM: usize;
+    fn dist_sqr(&self, rhs: &Self) -> f32;
+}
+
+impl KdPoint for Vector2 {
+    const DIM: usize = 2;
+    fn dist_sqr(&self, rhs: &Self) -> f32 {
+        (*self - *rhs).get_mag_sqr()
+    }
+}
+
+impl KdPoint for Vector3 {
+    const DIM: usize = 3;
+    fn dist_sqr(&self, rhs: &Self) -> f32 {
+        (*self - *rhs).get_mag_sqr()
+    }
+}
+
+pub type KdTree2 = KdTree<Vector2>;
+pub type KdTree3 = KdTree<Vector3>;
+
+// Static tree over a point set, stored implicitly: the node for a range of
+// order is its middle element, with the halves on either side as children.
+// Queries return indices into points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KdTree<P> {
+    pub points: Vec<P>,
+    order: Vec<usize>,
+    axes: Vec<u8>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+    // Splits each range at its median along the axis of widest spread.
+    pub fn new(points: &[P]) -> Self {
+        let mut tree = Self {
+            points: points.to_vec(),
+            order: (0..points.len()).collect(),
+            axes: vec![0; points.len()],
+        };
+        tree.build(0, points.len());
+        tree
+    }
+
+    fn build(&mut self, lo: usize, hi: usize) {
+        if hi - lo <= 1 {
+            return;
+        }
+        let points = &self.points;
+        let range = &mut self.order[lo..hi];
+        let axis = (0..P::DIM)
+            .map(|axis| {
+                let (min, max) = range.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &i| {
+                    (min.min(points[i][axis]), max.max(points[i][axis]))
+                });
+                (axis, max - min)
+            })
+            .fold((0, f32::NEG_INFINITY), |best, a| if a.1 > best.1 { a } else { best })
+            .0;
+        let mid = (lo + hi) / 2;
+        range.select_nth_unstable_by(mid - lo, |&a, &b| points[a][axis].total_cmp(&points[b][axis]));
+        self.axes[mid] = axis as u8;
+        self.build(lo, mid);
+        self.build(mid + 1, hi);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn nearest(&self, p: &P) -> Option<usize> {
+        self.k_nearest(p, 1).first().copied()
+    }
+
+    // Up to k indices, nearest first.
+    pub fn k_nearest(&self, p: &P, k: usize) -> Vec<usize> {
+        let mut best: Vec<(f32, usize)> = Vec::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(p, k, 0, self.points.len(), &mut best);
+        }
+        best.into_iter().map(|(_, i)| i).collect()
+    }
+
+    // best is kept sorted by distance and no longer than k.
+    fn search_nearest(&self, p: &P, k: usize, lo: usize, hi: usize, best: &mut Vec<(f32, usize)>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let i = self.order[mid];
+        let d = p.dist_sqr(&self.points[i]);
+        if best.len() < k || d < best[best.len() - 1].0 {
+            let at = best.partition_point(|b| b.0 <= d);
+            best.insert(at, (d, i));
+            best.truncate(k);
+        }
+
+        let axis = self.axes[mid] as usize;
+        let delta = p[axis] - self.points[i][axis];
+        let (near, far) = if delta < 0.0 { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+        self.search_nearest(p, k, near.0, near.1, best);
+        if best.len() < k || delta * delta < best[best.len() - 1].0 {
+            self.search_nearest(p, k, far.0, far.1, best);
+        }
+    }
+
+    // Every index within radius of p, inclusive, in no particular order.
+    pub fn within_radius(&self, p: &P, radius: f32) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack = vec![(0, self.points.len())];
+        let radius_sqr = radius * radius;
+        while let Some((lo, hi)) = stack.pop() {
+            if lo >= hi {
+                continue;
+            }
+            let mid = (lo + hi) / 2;
+            let i = self.order[mid];
+            if p.dist_sqr(&self.points[i]) <= radius_sqr {
+                out.push(i);
+            }
+            let axis = self.axes[mid] as usize;
+            let delta = p[axis] - self.points[i][axis];
+            if delta <= radius {
+                stack.push((lo, mid));
+            }
+            if delta >= -radius {
+                stack.push((mid + 1, hi));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    // Distances of the k nearest points by brute force, nearest first.
+    fn brute_k_nearest<P: KdPoint>(points: &[P], p: &P, k: usize) -> Vec<f32> {
+        let mut d: Vec<f32> = points.iter().map(|q| p.dist_sqr(q)).collect();
+        d.sort_by(f32::total_cmp);
+        d.truncate(k);
+        d
+    }
+
+    fn check<P: KdPoint>(points: &[P], queries: &[P]) {
+        let tree = KdTree::new(points);
+        assert_eq!(tree.len(), points.len());
+        for q in queries {
+            for k in [0, 1, 5, points.len() + 3] {
+                let got: Vec<f32> = tree.k_nearest(q, k).iter().map(|&i| q.dist_sqr(&points[i])).collect();
+                assert_eq!(got, brute_k_nearest(points, q, k));
+            }
+            assert_eq!(
+                tree.nearest(q).map(|i| q.dist_sqr(&points[i])),
+                brute_k_nearest(points, q, 1).first().copied()
+            );
+            for radius in [0.0, 1.5, 4.0] {
+                let mut got = tree.within_radius(q, radius);
+                got.sort_unstable();
+                let expected: Vec<usize> =
+                    (0..points.len()).filter(|&i| q.dist_sqr(&points[i]) <= radius * radius).collect();
+                assert_eq!(got, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn queries_match_brute_force_3d() {
+        let mut rng = Rng(1);
+        for n in [0, 1, 2, 3, 10, 500] {
+            let points: Vec<Vector3> = (0..n).map(|_| rng.vec3()).collect();
+            let queries: Vec<Vector3> = (0..30).map(|_| rng.vec3()).collect();
+            check(&points, &queries);
+        }
+    }
+
+    #[test]
+    fn queries_match_brute_force_2d() {
+        let mut rng = Rng(2);
+        let points: Vec<Vector2> = (0..300).map(|_| Vector2::new(rng.next(), rng.next())).collect();
+        let queries: Vec<Vector2> = (0..30).map(|_| Vector2::new(rng.next(), rng.next())).collect();
+        check(&points, &queries);
+    }
+
+    #[test]
+    fn duplicate_and_coplanar_points() {
+        let mut rng = Rng(3);
+        // Many points sharing coordinates exercise ties at the median.
+        let points: Vec<Vector3> = (0..200)
+            .map(|i| Vector3::new((i % 4) as f32, (i % 3) as f32, 0.0))
+            .collect();
+        let queries: Vec<Vector3> = (0..20).map(|_| rng.vec3() * 0.3).collect();
+        check(&points, &queries);
+        let tree = KdTree::new(&points);
+        let on_point = Vector3::new(1.0, 2.0, 0.0);
+        assert_eq!(tree.within_radius(&on_point, 0.0).len(), points.iter().filter(|p| **p == on_point).count());
+    }
+}
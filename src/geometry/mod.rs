@@ -0,0 +1,19 @@
+pub mod aabb;
+pub mod bvh;
+pub mod capsule;
+pub mod closest;
+pub mod dda;
+pub mod delaunay;
+pub mod frustum;
+pub mod gjk;
+pub mod hull;
+pub mod kdtree;
+pub mod morton;
+pub mod obb;
+pub mod octree;
+pub mod plane;
+pub mod polygon;
+pub mod predicates;
+pub mod ray;
+pub mod spatial_hash;
+pub mod sphere;
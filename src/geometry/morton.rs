@@ -0,0 +1,120 @@
+use crate::{geometry::aabb::Aabb3, vector3::Vector3};
+
+// Bits per axis that fit three-way in a u64.
+pub const MORTON_BITS: u32 = 21;
+const MORTON_MAX: u32 = (1 << MORTON_BITS) - 1;
+
+// Spreads the low 21 bits of v so two zero bits follow each one.
+fn spread(v: u32) -> u64 {
+    let mut x = (v & MORTON_MAX) as u64;
+    x = (x | x << 32) & 0x001f_0000_0000_ffff;
+    x = (x | x << 16) & 0x001f_0000_ff00_00ff;
+    x = (x | x << 8) & 0x100f_00f0_0f00_f00f;
+    x = (x | x << 4) & 0x10c3_0c30_c30c_30c3;
+    x = (x | x << 2) & 0x1249_2492_4924_9249;
+    x
+}
+
+fn compact(code: u64) -> u32 {
+    let mut x = code & 0x1249_2492_4924_9249;
+    x = (x | x >> 2) & 0x10c3_0c30_c30c_30c3;
+    x = (x | x >> 4) & 0x100f_00f0_0f00_f00f;
+    x = (x | x >> 8) & 0x001f_0000_ff00_00ff;
+    x = (x | x >> 16) & 0x001f_0000_0000_ffff;
+    x = (x | x >> 32) & MORTON_MAX as u64;
+    x as u32
+}
+
+// Interleaves the low 21 bits of each coordinate, x in the lowest bit.
+pub fn morton_encode(x: u32, y: u32, z: u32) -> u64 {
+    spread(x) | spread(y) << 1 | spread(z) << 2
+}
+
+pub fn morton_decode(code: u64) -> (u32, u32, u32) {
+    (compact(code), compact(code >> 1), compact(code >> 2))
+}
+
+// Quantizes p onto a 2^21 grid over bounds. Points outside are clamped.
+pub fn morton_encode_point(p: &Vector3, bounds: &Aabb3) -> u64 {
+    let size = bounds.get_size();
+    let cell = |v: f32, min: f32, extent: f32| {
+        if extent > 0.0 {
+            ((v - min) / extent * (MORTON_MAX + 1) as f32).clamp(0.0, MORTON_MAX as f32) as u32
+        } else {
+            0
+        }
+    };
+    morton_encode(
+        cell(p.x, bounds.min.x, size.x),
+        cell(p.y, bounds.min.y, size.y),
+        cell(p.z, bounds.min.z, size.z),
+    )
+}
+
+// The center of the grid cell a code names.
+pub fn morton_decode_point(code: u64, bounds: &Aabb3) -> Vector3 {
+    let (x, y, z) = morton_decode(code);
+    let cell = bounds.get_size() * (1.0 / (MORTON_MAX + 1) as f32);
+    bounds.min + Vector3::new((x as f32 + 0.5) * cell.x, (y as f32 + 0.5) * cell.y, (z as f32 + 0.5) * cell.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bit-by-bit reference interleave.
+    fn interleave(x: u32, y: u32, z: u32) -> u64 {
+        (0..MORTON_BITS).fold(0, |code, b| {
+            code | ((x >> b & 1) as u64) << (3 * b) | ((y >> b & 1) as u64) << (3 * b + 1) | ((z >> b & 1) as u64) << (3 * b + 2)
+        })
+    }
+
+    #[test]
+    fn encode_matches_bitwise_interleave() {
+        assert_eq!(morton_encode(1, 0, 0), 1);
+        assert_eq!(morton_encode(0, 1, 0), 2);
+        assert_eq!(morton_encode(0, 0, 1), 4);
+        assert_eq!(morton_encode(MORTON_MAX, MORTON_MAX, MORTON_MAX), (1 << 63) - 1);
+        let mut s = 5u64;
+        for _ in 0..1000 {
+            s = s.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let (x, y, z) = ((s >> 11) as u32 & MORTON_MAX, (s >> 32) as u32 & MORTON_MAX, (s >> 42) as u32 & MORTON_MAX);
+            let code = morton_encode(x, y, z);
+            assert_eq!(code, interleave(x, y, z));
+            assert_eq!(morton_decode(code), (x, y, z));
+        }
+    }
+
+    #[test]
+    fn encode_ignores_bits_above_21() {
+        assert_eq!(morton_encode(1 << MORTON_BITS | 3, u32::MAX, 0), morton_encode(3, MORTON_MAX, 0));
+    }
+
+    #[test]
+    fn point_round_trips_to_its_cell_center() {
+        let bounds = Aabb3::new(Vector3::new(-4.0, 0.0, 10.0), Vector3::new(4.0, 2.0, 12.0));
+        let cell = bounds.get_size() * (1.0 / (MORTON_MAX + 1) as f32);
+        for p in [Vector3::new(0.3, 1.7, 11.1), Vector3::new(-3.9, 0.01, 10.0), Vector3::new(3.99, 1.99, 11.99)] {
+            let q = morton_decode_point(morton_encode_point(&p, &bounds), &bounds);
+            assert!((q.x - p.x).abs() <= cell.x && (q.y - p.y).abs() <= cell.y && (q.z - p.z).abs() <= cell.z, "{p:?} -> {q:?}");
+        }
+    }
+
+    #[test]
+    fn point_outside_bounds_is_clamped() {
+        let bounds = Aabb3::new(Vector3::zero(), Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(morton_encode_point(&Vector3::new(-5.0, -5.0, -5.0), &bounds), 0);
+        assert_eq!(morton_encode_point(&Vector3::new(5.0, 5.0, 5.0), &bounds), (1 << 63) - 1);
+        // A flat axis quantizes to cell zero instead of dividing by zero.
+        let flat = Aabb3::new(Vector3::zero(), Vector3::new(1.0, 0.0, 1.0));
+        assert_eq!(morton_decode(morton_encode_point(&Vector3::new(0.0, 3.0, 0.0), &flat)).1, 0);
+    }
+
+    #[test]
+    fn codes_order_points_within_an_octant_first() {
+        let bounds = Aabb3::new(Vector3::zero(), Vector3::new(2.0, 2.0, 2.0));
+        let low = morton_encode_point(&Vector3::new(0.9, 0.9, 0.9), &bounds);
+        let high = morton_encode_point(&Vector3::new(1.1, 0.0, 0.0), &bounds);
+        assert!(low < high);
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{geometry::aabb::Aabb3, mat4::Mat4, quat::Quat, vector3::Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Obb {
+    pub center: Vector3,
+    pub half_extents: Vector3,
+    pub orientation: Quat,
+}
+
+impl Obb {
+    pub fn new(center: Vector3, half_extents: Vector3, orientation: Quat) -> Self {
+        Self { center, half_extents, orientation }
+    }
+
+    pub fn from_aabb(aabb: &Aabb3) -> Self {
+        Self {
+            center: aabb.get_center(),
+            half_extents: aabb.get_extents(),
+            orientation: Quat::identity(),
+        }
+    }
+
+    // Axes from the principal components of the points' covariance, then
+    // fitted tightly along those axes.
+    pub fn from_points(points: &[Vector3]) -> Self {
+        if points.is_empty() {
+            return Self::new(Vector3::zero(), Vector3::zero(), Quat::identity());
+        }
+        let inv_n = 1.0 / points.len() as f32;
+        let mut mean = Vector3::zero();
+        for p in points {
+            mean += *p;
+        }
+        mean *= inv_n;
+
+        let mut cov = [[0.0f32; 3]; 3];
+        for p in points {
+            let d = *p - mean;
+            for (r, row) in cov.iter_mut().enumerate() {
+                for (c, v) in row.iter_mut().enumerate() {
+                    *v += d[r] * d[c] * inv_n;
+                }
+            }
+        }
+        let [e0, e1, _] = symmetric_eigenvectors(cov);
+        let x = e0.get_norm();
+        let y = e1.get_norm();
+        Self::fit_axes(points, &[x, y, x.cross(&y)])
+    }
+
+    // Tightest box around points with the given orthonormal, right-handed axes.
+    fn fit_axes(points: &[Vector3], axes: &[Vector3; 3]) -> Self {
+        let mut min = Vector3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        let mut max = -min;
+        for p in points {
+            let local = Vector3::new(p.dot(&axes[0]), p.dot(&axes[1]), p.dot(&axes[2]));
+            min = min.min(&local);
+            max = max.max(&local);
+        }
+        let mid = (min + max) * 0.5;
+        Self {
+            center: axes[0] * mid.x + axes[1] * mid.y + axes[2] * mid.z,
+            half_extents: (max - min) * 0.5,
+            orientation: Quat::from_axes(&axes[0], &axes[1], &axes[2]),
+        }
+    }
+
+    pub fn get_axes(&self) -> [Vector3; 3] {
+        [
+            self.orientation.lqcvq(&Vector3::new(1.0, 0.0, 0.0)),
+            self.orientation.lqcvq(&Vector3::new(0.0, 1.0, 0.0)),
+            self.orientation.lqcvq(&Vector3::new(0.0, 0.0, 1.0)),
+        ]
+    }
+
+    pub fn get_corners(&self) -> [Vector3; 8] {
+        let [ax, ay, az] = self.get_axes();
+        let h = self.half_extents;
+        std::array::from_fn(|i| {
+            let sx = if i & 1 == 0 { -h.x } else { h.x };
+            let sy = if i & 2 == 0 { -h.y } else { h.y };
+            let sz = if i & 4 == 0 { -h.z } else { h.z };
+            self.center + ax * sx + ay * sy + az * sz
+        })
+    }
+
+    pub fn to_local(&self, p: &Vector3) -> Vector3 {
+        self.orientation.get_conj().lqcvq(&(*p - self.center))
+    }
+
+    pub fn contains_point(&self, p: &Vector3) -> bool {
+        let local = self.to_local(p).abs();
+        local.x <= self.half_extents.x && local.y <= self.half_extents.y && local.z <= self.half_extents.z
+    }
+
+    pub fn closest_point(&self, p: &Vector3) -> Vector3 {
+        let local = self
+            .to_local(p)
+            .max(&-self.half_extents)
+            .min(&self.half_extents);
+        self.center + self.orientation.lqcvq(&local)
+    }
+
+    pub fn merge(&mut self, rhs: &Obb) {
+        *self = self.get_merge(rhs)
+    }
+
+    // Refits a box around both sets of corners.
+    pub fn get_merge(&self, rhs: &Obb) -> Self {
+        let mut corners = self.get_corners().to_vec();
+        corners.extend_from_slice(&rhs.get_corners());
+        Self::from_points(&corners)
+    }
+
+    pub fn transform(&mut self, m: &Mat4) {
+        *self = self.get_transform(m)
+    }
+
+    // Scale is folded into the half extents; shear is not representable.
+    pub fn get_transform(&self, m: &Mat4) -> Self {
+        let axes = self.get_axes().map(|a| m.transform_vector(&a));
+        let scale = Vector3::new(axes[0].get_mag(), axes[1].get_mag(), axes[2].get_mag());
+        let x = axes[0].get_norm();
+        let y = axes[1].get_norm();
+        Self {
+            center: m.transform_point(&self.center),
+            half_extents: Vector3::new(
+                self.half_extents.x * scale.x,
+                self.half_extents.y * scale.y,
+                self.half_extents.z * scale.z,
+            ),
+            orientation: Quat::from_axes(&x, &y, &x.cross(&y)),
+        }
+    }
+
+    pub fn rotate(&mut self, q: &Quat) {
+        *self = self.get_rotate(q)
+    }
+
+    // Rotates about the origin.
+    pub fn get_rotate(&self, q: &Quat) -> Self {
+        Self {
+            center: q.lqcvq(&self.center),
+            half_extents: self.half_extents,
+            orientation: *q * self.orientation,
+        }
+    }
+
+    // Separating axis test over the 15 candidate axes (Ericson, RTCD 4.4.1).
+    pub fn intersects_obb(&self, rhs: &Obb) -> bool {
+        const EPSILON: f32 = 1e-6;
+        let a = self.get_axes();
+        let b = rhs.get_axes();
+        let ea = self.half_extents;
+        let eb = rhs.half_extents;
+
+        let r: [[f32; 3]; 3] = std::array::from_fn(|i| std::array::from_fn(|j| a[i].dot(&b[j])));
+        let abs_r: [[f32; 3]; 3] = std::array::from_fn(|i| std::array::from_fn(|j| r[i][j].abs() + EPSILON));
+        let d = rhs.center - self.center;
+        let t = Vector3::new(d.dot(&a[0]), d.dot(&a[1]), d.dot(&a[2]));
+
+        for i in 0..3 {
+            let ra = ea[i];
+            let rb = eb[0] * abs_r[i][0] + eb[1] * abs_r[i][1] + eb[2] * abs_r[i][2];
+            if t[i].abs() > ra + rb {
+                return false;
+            }
+        }
+
+        for j in 0..3 {
+            let ra = ea[0] * abs_r[0][j] + ea[1] * abs_r[1][j] + ea[2] * abs_r[2][j];
+            let rb = eb[j];
+            if (t[0] * r[0][j] + t[1] * r[1][j] + t[2] * r[2][j]).abs() > ra + rb {
+                return false;
+            }
+        }
+
+        // a[i] x b[j]
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let ra = ea[i1] * abs_r[i2][j] + ea[i2] * abs_r[i1][j];
+                let rb = eb[j1] * abs_r[i][j2] + eb[j2] * abs_r[i][j1];
+                if (t[i2] * r[i1][j] - t[i1] * r[i2][j]).abs() > ra + rb {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+// Cyclic Jacobi rotations on a symmetric 3x3 matrix. Returns the eigenvectors
+// sorted by descending eigenvalue.
+fn symmetric_eigenvectors(mut a: [[f32; 3]; 3]) -> [Vector3; 3] {
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..32 {
+        let (p, q) = [(0, 1), (0, 2), (1, 2)]
+            .into_iter()
+            .max_by(|x, y| a[x.0][x.1].abs().total_cmp(&a[y.0][y.1].abs()))
+            .unwrap();
+        if a[p][q].abs() < 1e-9 {
+            break;
+        }
+        let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+        let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+        let c = 1.0 / (t * t + 1.0).sqrt();
+        let s = t * c;
+        for row in a.iter_mut() {
+            let (kp, kq) = (row[p], row[q]);
+            row[p] = c * kp - s * kq;
+            row[q] = s * kp + c * kq;
+        }
+        let (rp, rq) = (a[p], a[q]);
+        a[p] = std::array::from_fn(|k| c * rp[k] - s * rq[k]);
+        a[q] = std::array::from_fn(|k| s * rp[k] + c * rq[k]);
+        for row in v.iter_mut() {
+            let (kp, kq) = (row[p], row[q]);
+            row[p] = c * kp - s * kq;
+            row[q] = s * kp + c * kq;
+        }
+    }
+    let mut order = [0, 1, 2];
+    order.sort_by(|x, y| a[*y][*y].total_cmp(&a[*x][*x]));
+    order.map(|c| Vector3::new(v[0][c], v[1][c], v[2][c]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Deg;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    fn grown(obb: &Obb, amount: f32) -> Obb {
+        Obb::new(obb.center, obb.half_extents + v(amount, amount, amount), obb.orientation)
+    }
+
+    fn rotated() -> Obb {
+        let q = Quat::from_axis_angle(&v(1.0, 2.0, -1.0), Deg(35.0));
+        Obb::new(v(1.0, -2.0, 3.0), v(3.0, 2.0, 1.0), q)
+    }
+
+    #[test]
+    fn corners_are_on_the_surface() {
+        let obb = rotated();
+        for c in obb.get_corners() {
+            assert!(grown(&obb, 1e-3).contains_point(&c));
+            assert!(!grown(&obb, -1e-3).contains_point(&c));
+            crate::assert_approx_eq!(obb.to_local(&c).abs(), obb.half_extents);
+        }
+        let [x, y, z] = obb.get_axes();
+        crate::assert_approx_eq!(x.cross(&y), z);
+    }
+
+    #[test]
+    fn closest_point_clamps_in_local_space() {
+        let obb = Obb::new(v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0), Quat::from_axis_angle(&v(0.0, 0.0, 1.0), Deg(45.0)));
+        let h = 2f32.sqrt();
+        crate::assert_approx_eq!(obb.closest_point(&v(5.0, 0.0, 0.0)), v(h, 0.0, 0.0));
+        crate::assert_approx_eq!(obb.closest_point(&v(0.0, 0.0, 3.0)), v(0.0, 0.0, 1.0));
+        crate::assert_approx_eq!(obb.closest_point(&v(0.1, 0.2, 0.3)), v(0.1, 0.2, 0.3));
+    }
+
+    #[test]
+    fn from_points_recovers_a_rotated_box() {
+        let obb = rotated();
+        let mut points = obb.get_corners().to_vec();
+        points.push(obb.center);
+        let fit = Obb::from_points(&points);
+        crate::assert_approx_eq!(fit.center, obb.center);
+        crate::assert_approx_eq!(fit.half_extents, obb.half_extents);
+        assert!(points.iter().all(|p| grown(&fit, 1e-3).contains_point(p)));
+    }
+
+    #[test]
+    fn transform_folds_scale_into_the_extents() {
+        let m = Mat4::from([
+            0.0, 2.0, 0.0, 0.0,
+            -2.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 2.0, 0.0,
+            1.0, 0.0, 0.0, 1.0,
+        ]);
+        let obb = Obb::new(v(1.0, 0.0, 0.0), v(1.0, 2.0, 3.0), Quat::identity());
+        let moved = obb.get_transform(&m);
+        crate::assert_approx_eq!(moved.center, v(1.0, 2.0, 0.0));
+        crate::assert_approx_eq!(moved.half_extents, v(2.0, 4.0, 6.0));
+        for c in obb.get_corners() {
+            assert!(grown(&moved, 1e-3).contains_point(&m.transform_point(&c)));
+        }
+    }
+
+    #[test]
+    fn separating_axes() {
+        let a = Obb::new(v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0), Quat::identity());
+        let q = Quat::from_axis_angle(&v(0.0, 0.0, 1.0), Deg(45.0));
+        // A face axis of a separates these.
+        assert!(!a.intersects_obb(&Obb::new(v(3.0, 0.0, 0.0), v(1.0, 1.0, 1.0), Quat::identity())));
+        assert!(a.intersects_obb(&Obb::new(v(1.9, 0.0, 0.0), v(1.0, 1.0, 1.0), Quat::identity())));
+        // The rotated box reaches sqrt(2) along x.
+        assert!(a.intersects_obb(&Obb::new(v(2.3, 0.0, 0.0), v(1.0, 1.0, 1.0), q)));
+        assert!(!a.intersects_obb(&Obb::new(v(2.5, 0.0, 0.0), v(1.0, 1.0, 1.0), q)));
+        // Tilted 45 degrees about x, b faces a's edge along the y = z diagonal.
+        let tilt = Quat::from_axis_angle(&v(1.0, 0.0, 0.0), Deg(45.0));
+        let b = Obb::new(v(0.0, 2.3, 2.3), v(1.0, 1.0, 1.0), tilt);
+        assert!(!a.intersects_obb(&b));
+        let b = Obb::new(v(0.0, 1.6, 1.6), v(1.0, 1.0, 1.0), tilt);
+        assert!(a.intersects_obb(&b));
+    }
+}
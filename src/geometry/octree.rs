@@ -0,0 +1,365 @@
+use crate::{
+    geometry::{
+        aabb::Aabb3,
+        frustum::{Containment, Frustum, FRUSTUM_ALL_PLANES},
+        sphere::Sphere,
+    },
+    vector3::Vector3,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+struct OctNode {
+    center: Vector3,
+    half_size: f32,
+    parent: Option<usize>,
+    children: [Option<usize>; 8],
+    items: Vec<usize>,
+}
+
+impl OctNode {
+    // Twice the cell, so an item only has to fit by size and have its center
+    // in the cell, never straddling into a neighbour.
+    fn get_loose(&self) -> Aabb3 {
+        let e = self.half_size * 2.0;
+        Aabb3::from_center_extents(&self.center, &Vector3::new(e, e, e))
+    }
+}
+
+// Loose octree over items with boxes, for objects that move every frame.
+// Nodes are created on demand and pruned when they empty. Items that do not
+// fit the root stay in the root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LooseOctree {
+    nodes: Vec<Option<OctNode>>,
+    free_nodes: Vec<usize>,
+    items: Vec<Option<(Aabb3, usize)>>,
+    free_items: Vec<usize>,
+    max_depth: u32,
+}
+
+impl LooseOctree {
+    // bounds is grown to a cube about its center.
+    pub fn new(bounds: &Aabb3, max_depth: u32) -> Self {
+        let size = bounds.get_size();
+        let root = OctNode {
+            center: bounds.get_center(),
+            half_size: size.x.max(size.y).max(size.z) * 0.5,
+            parent: None,
+            children: [None; 8],
+            items: Vec::new(),
+        };
+        Self {
+            nodes: vec![Some(root)],
+            free_nodes: Vec::new(),
+            items: Vec::new(),
+            free_items: Vec::new(),
+            max_depth,
+        }
+    }
+
+    fn node(&self, n: usize) -> &OctNode {
+        self.nodes[n].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, n: usize) -> &mut OctNode {
+        self.nodes[n].as_mut().unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len() - self.free_items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_aabb(&self, item: usize) -> Option<Aabb3> {
+        self.items.get(item).copied().flatten().map(|(aabb, _)| aabb)
+    }
+
+    pub fn insert(&mut self, aabb: Aabb3) -> usize {
+        let item = match self.free_items.pop() {
+            Some(i) => i,
+            None => {
+                self.items.push(None);
+                self.items.len() - 1
+            }
+        };
+        self.place(item, aabb);
+        item
+    }
+
+    // Returns false if the item was not in the tree. Its index may be handed
+    // out again by a later insert.
+    pub fn remove(&mut self, item: usize) -> bool {
+        match self.items.get(item).copied().flatten() {
+            Some(_) => {
+                self.unlink(item);
+                self.items[item] = None;
+                self.free_items.push(item);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Moves an item, keeping its index. Does nothing if it is not in the tree.
+    pub fn update(&mut self, item: usize, aabb: Aabb3) {
+        if let Some(Some(_)) = self.items.get(item) {
+            self.unlink(item);
+            self.place(item, aabb);
+        }
+    }
+
+    fn place(&mut self, item: usize, aabb: Aabb3) {
+        let center = aabb.get_center();
+        let mut n = 0;
+        for _ in 0..self.max_depth {
+            let octant = octant_of(&self.node(n).center, &center);
+            let mut child = child_node(self.node(n), octant);
+            if !child.get_loose().contains_aabb(&aabb) {
+                break;
+            }
+            n = match self.node(n).children[octant] {
+                Some(c) => c,
+                None => {
+                    child.parent = Some(n);
+                    let c = match self.free_nodes.pop() {
+                        Some(c) => {
+                            self.nodes[c] = Some(child);
+                            c
+                        }
+                        None => {
+                            self.nodes.push(Some(child));
+                            self.nodes.len() - 1
+                        }
+                    };
+                    self.node_mut(n).children[octant] = Some(c);
+                    c
+                }
+            };
+        }
+        self.node_mut(n).items.push(item);
+        self.items[item] = Some((aabb, n));
+    }
+
+    // Takes the item out of its node and frees any nodes left empty.
+    fn unlink(&mut self, item: usize) {
+        let (_, mut n) = self.items[item].unwrap();
+        let items = &mut self.node_mut(n).items;
+        let at = items.iter().position(|&i| i == item).unwrap();
+        items.swap_remove(at);
+        while let Some(parent) = self.node(n).parent {
+            let node = self.node(n);
+            if !node.items.is_empty() || node.children.iter().any(Option::is_some) {
+                break;
+            }
+            self.nodes[n] = None;
+            self.free_nodes.push(n);
+            for c in self.node_mut(parent).children.iter_mut().filter(|c| **c == Some(n)) {
+                *c = None;
+            }
+            n = parent;
+        }
+    }
+
+    pub fn query_aabb(&self, aabb: &Aabb3) -> Vec<usize> {
+        self.query(|b| aabb.intersects(b))
+    }
+
+    pub fn query_sphere(&self, sphere: &Sphere) -> Vec<usize> {
+        self.query(|b| sphere.intersects_aabb(b))
+    }
+
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack = vec![(0, FRUSTUM_ALL_PLANES)];
+        while let Some((n, mask)) = stack.pop() {
+            let node = self.node(n);
+            // The root is always searched, since items too big for it live there.
+            let (containment, mask) = if n == 0 {
+                (Containment::Intersect, mask)
+            } else {
+                frustum.test_aabb_masked(&node.get_loose(), mask)
+            };
+            if containment == Containment::Outside {
+                continue;
+            }
+            for &i in &node.items {
+                let (aabb, _) = self.items[i].unwrap();
+                if containment == Containment::Inside || frustum.test_aabb_masked(&aabb, mask).0 != Containment::Outside {
+                    out.push(i);
+                }
+            }
+            stack.extend(node.children.iter().flatten().map(|&c| (c, mask)));
+        }
+        out
+    }
+
+    fn query<F>(&self, overlaps: F) -> Vec<usize>
+    where
+        F: Fn(&Aabb3) -> bool,
+    {
+        let mut out = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = self.node(n);
+            if n != 0 && !overlaps(&node.get_loose()) {
+                continue;
+            }
+            for &i in &node.items {
+                let (aabb, _) = self.items[i].unwrap();
+                if overlaps(&aabb) {
+                    out.push(i);
+                }
+            }
+            stack.extend(node.children.iter().flatten());
+        }
+        out
+    }
+}
+
+// Bit 0 set for +x, bit 1 for +y, bit 2 for +z.
+fn octant_of(center: &Vector3, p: &Vector3) -> usize {
+    (p.x >= center.x) as usize | ((p.y >= center.y) as usize) << 1 | ((p.z >= center.z) as usize) << 2
+}
+
+fn child_node(node: &OctNode, octant: usize) -> OctNode {
+    let quarter = node.half_size * 0.5;
+    let sign = |bit: usize| if octant & bit != 0 { quarter } else { -quarter };
+    OctNode {
+        center: node.center + Vector3::new(sign(1), sign(2), sign(4)),
+        half_size: quarter,
+        parent: None,
+        children: [None; 8],
+        items: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::frustum::DepthRange, mat4::Mat4, test_util::Rng};
+
+    // Mostly small boxes, with the odd large one and some outside the root.
+    fn aabb(rng: &mut Rng) -> Aabb3 {
+        let c = rng.vec3() * 1.2;
+        let scale = if rng.next() > 8.0 { 0.8 } else { 0.1 };
+        let e = Vector3::new(rng.next().abs(), rng.next().abs(), rng.next().abs()) * scale;
+        Aabb3::from_center_extents(&c, &e)
+    }
+
+    // Each item sits in the node it records, and every non-root item fits its
+    // node's loose bounds.
+    fn check_tree(tree: &LooseOctree, reference: &[Option<Aabb3>]) {
+        for (item, r) in reference.iter().enumerate() {
+            assert_eq!(tree.get_aabb(item), *r);
+            if let Some((aabb, n)) = tree.items[item] {
+                assert!(tree.node(n).items.contains(&item));
+                if n != 0 {
+                    assert!(tree.node(n).get_loose().contains_aabb(&aabb));
+                }
+            }
+        }
+        assert_eq!(tree.len(), reference.iter().flatten().count());
+        // Pruning leaves no empty leaves below the root.
+        for (n, node) in tree.nodes.iter().enumerate() {
+            if let (Some(node), true) = (node, n != 0) {
+                assert!(!node.items.is_empty() || node.children.iter().any(Option::is_some));
+            }
+        }
+    }
+
+    fn brute<F: Fn(&Aabb3) -> bool>(reference: &[Option<Aabb3>], keep: F) -> Vec<usize> {
+        (0..reference.len()).filter(|&i| reference[i].as_ref().is_some_and(&keep)).collect()
+    }
+
+    fn sorted(mut v: Vec<usize>) -> Vec<usize> {
+        v.sort_unstable();
+        v
+    }
+
+    fn check_queries(tree: &LooseOctree, reference: &[Option<Aabb3>], rng: &mut Rng) {
+        let frustum = Frustum::from_matrix(
+            &Mat4::from([
+                0.25, 0.0, 0.0, 0.0,
+                0.0, 0.25, 0.0, 0.0,
+                0.0, 0.0, 0.2, 0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ]),
+            DepthRange::NegOneToOne,
+        );
+        assert_eq!(
+            sorted(tree.query_frustum(&frustum)),
+            brute(reference, |b| frustum.test_aabb(b) != Containment::Outside)
+        );
+        for _ in 0..20 {
+            let query = Aabb3::from_center_extents(&rng.vec3(), &Vector3::new(2.0, 2.0, 2.0));
+            assert_eq!(sorted(tree.query_aabb(&query)), brute(reference, |b| b.intersects(&query)));
+            let sphere = Sphere::new(rng.vec3(), 2.5);
+            assert_eq!(sorted(tree.query_sphere(&sphere)), brute(reference, |b| sphere.intersects_aabb(b)));
+        }
+    }
+
+    #[test]
+    fn insert_remove_update_match_brute_force() {
+        let mut rng = Rng(49);
+        let bounds = Aabb3::new(Vector3::new(-10.0, -10.0, -10.0), Vector3::new(10.0, 10.0, 10.0));
+        let mut tree = LooseOctree::new(&bounds, 5);
+        let mut reference: Vec<Option<Aabb3>> = Vec::new();
+        for round in 0..6 {
+            for _ in 0..60 {
+                let b = aabb(&mut rng);
+                let item = tree.insert(b);
+                if item == reference.len() {
+                    reference.push(Some(b));
+                } else {
+                    assert!(reference[item].is_none(), "live index {item} handed out again");
+                    reference[item] = Some(b);
+                }
+            }
+            for i in (round..reference.len()).step_by(3) {
+                assert_eq!(tree.remove(i), reference[i].is_some());
+                reference[i] = None;
+            }
+            for i in (0..reference.len()).step_by(5) {
+                if reference[i].is_some() {
+                    let b = aabb(&mut rng);
+                    tree.update(i, b);
+                    reference[i] = Some(b);
+                }
+            }
+            check_tree(&tree, &reference);
+            check_queries(&tree, &reference, &mut rng);
+        }
+        assert!(!tree.remove(reference.len() + 10));
+    }
+
+    #[test]
+    fn removing_everything_prunes_to_the_root() {
+        let mut rng = Rng(50);
+        let bounds = Aabb3::new(Vector3::new(-10.0, -10.0, -10.0), Vector3::new(10.0, 10.0, 10.0));
+        let mut tree = LooseOctree::new(&bounds, 6);
+        let items: Vec<usize> = (0..100).map(|_| tree.insert(aabb(&mut rng))).collect();
+        for i in items {
+            assert!(tree.remove(i));
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.nodes.iter().flatten().count(), 1);
+        assert!(tree.node(0).items.is_empty());
+    }
+
+    #[test]
+    fn oversized_items_stay_in_the_root() {
+        let bounds = Aabb3::new(Vector3::zero(), Vector3::new(4.0, 4.0, 4.0));
+        let mut tree = LooseOctree::new(&bounds, 4);
+        let huge = tree.insert(Aabb3::new(Vector3::new(-50.0, -50.0, -50.0), Vector3::new(50.0, 50.0, 50.0)));
+        let far = tree.insert(Aabb3::new(Vector3::new(100.0, 0.0, 0.0), Vector3::new(101.0, 1.0, 1.0)));
+        assert_eq!(tree.items[huge].unwrap().1, 0);
+        assert_eq!(tree.items[far].unwrap().1, 0);
+        let probe = Aabb3::new(Vector3::new(100.5, 0.5, 0.5), Vector3::new(100.6, 0.6, 0.6));
+        assert_eq!(tree.query_aabb(&probe), vec![far]);
+        let probe = Aabb3::new(Vector3::new(1.0, 1.0, 1.0), Vector3::new(1.1, 1.1, 1.1));
+        assert_eq!(tree.query_aabb(&probe), vec![huge]);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    geometry::ray::Ray,
+    mat4::Mat4,
+    util::is_zero,
+    vector3::Vector3,
+    vector4::Vector4,
+};
+
+const PLANE_EPSILON: f32 = 1e-6;
+
+// Points p on the plane satisfy normal.dot(p) + d == 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: Vector3,
+    pub d: f32,
+}
+
+impl Plane {
+    pub fn new(normal: Vector3, d: f32) -> Self {
+        Self { normal, d }
+    }
+
+    pub fn from_point_normal(point: &Vector3, normal: &Vector3) -> Self {
+        let normal = normal.get_norm();
+        Self {
+            normal,
+            d: -normal.dot(point),
+        }
+    }
+
+    // Counter-clockwise a, b, c face along the normal.
+    pub fn from_points(a: &Vector3, b: &Vector3, c: &Vector3) -> Self {
+        Self::from_point_normal(a, &(*b - *a).cross(&(*c - *a)))
+    }
+
+    pub fn norm(&mut self) {
+        *self = self.get_norm()
+    }
+
+    pub fn get_norm(&self) -> Self {
+        let inv_mag = 1.0 / self.normal.get_mag();
+        Self {
+            normal: self.normal * inv_mag,
+            d: self.d * inv_mag,
+        }
+    }
+
+    pub fn get_flip(&self) -> Self {
+        Self {
+            normal: -self.normal,
+            d: -self.d,
+        }
+    }
+
+    // Positive in front of the plane; a true distance when the normal is unit length.
+    pub fn signed_distance(&self, p: &Vector3) -> f32 {
+        self.normal.dot(p) + self.d
+    }
+
+    pub fn project_point(&self, p: &Vector3) -> Vector3 {
+        let dist = self.signed_distance(p) / self.normal.get_mag_sqr();
+        *p - self.normal * dist
+    }
+
+    // The line shared by both planes, or None when they are parallel.
+    pub fn intersect_plane(&self, rhs: &Plane) -> Option<Ray> {
+        let dir = self.normal.cross(&rhs.normal);
+        let denom = dir.get_mag_sqr();
+        if is_zero(denom, PLANE_EPSILON) {
+            return None;
+        }
+        let origin = (self.normal * rhs.d - rhs.normal * self.d).cross(&dir) * (1.0 / denom);
+        Some(Ray::new(origin, dir.get_norm()))
+    }
+
+    pub fn intersect_planes(a: &Plane, b: &Plane, c: &Plane) -> Option<Vector3> {
+        let bc = b.normal.cross(&c.normal);
+        let denom = a.normal.dot(&bc);
+        if is_zero(denom, PLANE_EPSILON) {
+            return None;
+        }
+        let ca = c.normal.cross(&a.normal);
+        let ab = a.normal.cross(&b.normal);
+        Some((bc * a.d + ca * b.d + ab * c.d) * (-1.0 / denom))
+    }
+
+    pub fn transform(&mut self, m: &Mat4) {
+        *self = self.get_transform(m)
+    }
+
+    // Planes transform by the inverse-transpose so non-uniform scale keeps
+    // the normal perpendicular to the surface.
+    pub fn get_transform(&self, m: &Mat4) -> Self {
+        let p = Vector4::new(self.normal.x, self.normal.y, self.normal.z, self.d) * m.get_inv().get_t();
+        Self {
+            normal: Vector3::from_vec4(&p),
+            d: p.w,
+        }
+        .get_norm()
+    }
+
+    // Sutherland–Hodgman against a single plane, keeping the part in front.
+    pub fn clip_polygon(&self, polygon: &[Vector3]) -> Vec<Vector3> {
+        let mut out = Vec::with_capacity(polygon.len() + 1);
+        for (i, cur) in polygon.iter().enumerate() {
+            let next = &polygon[(i + 1) % polygon.len()];
+            let d_cur = self.signed_distance(cur);
+            let d_next = self.signed_distance(next);
+            if d_cur >= 0.0 {
+                out.push(*cur);
+            }
+            if (d_cur >= 0.0) != (d_next >= 0.0) {
+                let t = d_cur / (d_cur - d_next);
+                out.push(*cur + (*next - *cur) * t);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    #[test]
+    fn from_points_faces_counter_clockwise() {
+        let plane = Plane::from_points(&v(0.0, 0.0, 2.0), &v(1.0, 0.0, 2.0), &v(0.0, 1.0, 2.0));
+        assert_eq!(plane.normal, v(0.0, 0.0, 1.0));
+        assert_eq!(plane.d, -2.0);
+        assert_eq!(plane.signed_distance(&v(5.0, -3.0, 5.0)), 3.0);
+        assert_eq!(plane.get_flip().signed_distance(&v(5.0, -3.0, 5.0)), -3.0);
+    }
+
+    #[test]
+    fn project_point_lands_on_plane() {
+        // Unnormalised, so project_point has to divide by |n|^2.
+        let plane = Plane::new(v(0.0, 2.0, 0.0), -2.0);
+        assert_eq!(plane.project_point(&v(3.0, 7.0, -1.0)), v(3.0, 1.0, -1.0));
+        let n = plane.get_norm();
+        assert_eq!(n.normal, v(0.0, 1.0, 0.0));
+        assert_eq!(n.d, -1.0);
+    }
+
+    #[test]
+    fn two_planes_meet_in_a_line() {
+        let a = Plane::new(v(1.0, 0.0, 0.0), -1.0);
+        let b = Plane::new(v(0.0, 1.0, 0.0), -2.0);
+        let line = a.intersect_plane(&b).unwrap();
+        for t in [-3.0, 0.0, 5.0] {
+            let p = line.at(t);
+            assert!(a.signed_distance(&p).abs() < 1e-5 && b.signed_distance(&p).abs() < 1e-5);
+        }
+        crate::assert_approx_eq!(line.dir, v(0.0, 0.0, 1.0));
+        assert!(a.intersect_plane(&Plane::new(v(2.0, 0.0, 0.0), 4.0)).is_none());
+    }
+
+    #[test]
+    fn three_planes_meet_in_a_point() {
+        let p = Plane::intersect_planes(
+            &Plane::new(v(1.0, 0.0, 0.0), -1.0),
+            &Plane::new(v(0.0, 1.0, 0.0), -2.0),
+            &Plane::new(v(0.0, 0.0, 1.0), 3.0),
+        );
+        crate::assert_approx_eq!(p.unwrap(), v(1.0, 2.0, -3.0));
+        let parallel = Plane::new(v(1.0, 0.0, 0.0), 5.0);
+        assert!(Plane::intersect_planes(&parallel, &Plane::new(v(1.0, 0.0, 0.0), -1.0), &Plane::new(v(0.0, 1.0, 0.0), 0.0)).is_none());
+    }
+
+    #[test]
+    fn transform_keeps_points_on_the_plane() {
+        // Non-uniform scale then a translation of (1, 2, 3).
+        let m = Mat4::from([
+            2.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 0.5, 0.0,
+            1.0, 2.0, 3.0, 1.0,
+        ]);
+        let plane = Plane::from_points(&v(1.0, 0.0, 0.0), &v(0.0, 1.0, 0.0), &v(0.0, 0.0, 1.0));
+        let moved = plane.get_transform(&m);
+        for p in [v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0), v(0.0, 0.0, 1.0), v(0.5, 0.25, 0.25)] {
+            assert!(moved.signed_distance(&m.transform_point(&p)).abs() < 1e-5);
+        }
+        crate::assert_approx_eq!(moved.normal.get_mag(), 1.0);
+        assert!(moved.signed_distance(&m.transform_point(&v(1.0, 1.0, 1.0))) > 0.0);
+    }
+
+    #[test]
+    fn clip_polygon_keeps_the_front() {
+        let plane = Plane::new(v(1.0, 0.0, 0.0), 0.0);
+        let square = [v(-1.0, -1.0, 0.0), v(1.0, -1.0, 0.0), v(1.0, 1.0, 0.0), v(-1.0, 1.0, 0.0)];
+        let clipped = plane.clip_polygon(&square);
+        assert_eq!(clipped, vec![v(0.0, -1.0, 0.0), v(1.0, -1.0, 0.0), v(1.0, 1.0, 0.0), v(0.0, 1.0, 0.0)]);
+        assert_eq!(plane.get_flip().clip_polygon(&square).len(), 4);
+        assert!(Plane::new(v(1.0, 0.0, 0.0), -2.0).clip_polygon(&square).is_empty());
+        assert_eq!(Plane::new(v(1.0, 0.0, 0.0), 2.0).clip_polygon(&square), square.to_vec());
+    }
+}
@@ -0,0 +1,693 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::vector2::Vector2;
+
+// Polygons are slices of vertices with an implicit closing edge. Unless noted,
+// functions accept either winding.
+
+const POLYGON_EPSILON: f32 = 1e-6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+// Shoelace formula; positive for counter-clockwise polygons.
+pub fn signed_area(poly: &[Vector2]) -> f32 {
+    let mut sum = 0.0;
+    for (i, a) in poly.iter().enumerate() {
+        sum += a.cross(&poly[(i + 1) % poly.len()]);
+    }
+    sum * 0.5
+}
+
+pub fn winding(poly: &[Vector2]) -> Winding {
+    if signed_area(poly) < 0.0 {
+        Winding::Clockwise
+    } else {
+        Winding::CounterClockwise
+    }
+}
+
+pub fn set_winding(poly: &mut [Vector2], target: Winding) {
+    if winding(poly) != target {
+        poly.reverse();
+    }
+}
+
+// Collinear vertices are allowed.
+pub fn is_convex(poly: &[Vector2]) -> bool {
+    let n = poly.len();
+    if n < 3 {
+        return false;
+    }
+    let mut sign = 0.0;
+    for i in 0..n {
+        let a = poly[i];
+        let b = poly[(i + 1) % n];
+        let c = poly[(i + 2) % n];
+        let turn = (b - a).cross(&(c - b));
+        if turn.abs() <= POLYGON_EPSILON {
+            continue;
+        }
+        if sign == 0.0 {
+            sign = turn.signum();
+        } else if turn.signum() != sign {
+            return false;
+        }
+    }
+    // Convex turns that wind more than once still make a star.
+    signed_area(poly).abs() > POLYGON_EPSILON
+}
+
+// Winding number from signed edge crossings (Sunday); non-zero means inside.
+pub fn winding_number(poly: &[Vector2], p: &Vector2) -> i32 {
+    let mut wn = 0;
+    for (i, a) in poly.iter().enumerate() {
+        let b = &poly[(i + 1) % poly.len()];
+        let side = (*b - *a).cross(&(*p - *a));
+        if a.y <= p.y {
+            if b.y > p.y && side > 0.0 {
+                wn += 1;
+            }
+        } else if b.y <= p.y && side < 0.0 {
+            wn -= 1;
+        }
+    }
+    wn
+}
+
+pub fn contains_point(poly: &[Vector2], p: &Vector2) -> bool {
+    winding_number(poly, p) != 0
+}
+
+// Ear clipping, with holes bridged into the outer ring first (Eberly,
+// "Triangulation by Ear Clipping"). Indices address outer followed by each
+// hole in order. Triangles are counter-clockwise.
+pub fn triangulate(outer: &[Vector2], holes: &[&[Vector2]]) -> Vec<[usize; 3]> {
+    let mut verts: Vec<Vector2> = outer.to_vec();
+    let mut ring: Vec<usize> = (0..outer.len()).collect();
+    if signed_area(outer) < 0.0 {
+        ring.reverse();
+    }
+
+    let mut hole_rings: Vec<Vec<usize>> = Vec::with_capacity(holes.len());
+    for hole in holes {
+        let start = verts.len();
+        verts.extend_from_slice(hole);
+        let mut hole_ring: Vec<usize> = (start..verts.len()).collect();
+        if signed_area(hole) > 0.0 {
+            hole_ring.reverse();
+        }
+        if hole_ring.len() >= 3 {
+            hole_rings.push(hole_ring);
+        }
+    }
+    let max_x = |r: &Vec<usize>| r.iter().map(|i| verts[*i].x).fold(f32::NEG_INFINITY, f32::max);
+    hole_rings.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+    for hole_ring in &hole_rings {
+        bridge_hole(&verts, &mut ring, hole_ring);
+    }
+
+    ear_clip(&verts, ring)
+}
+
+// Joins a clockwise hole to the counter-clockwise ring through a mutually
+// visible pair of vertices, leaving a zero-width seam.
+fn bridge_hole(verts: &[Vector2], ring: &mut Vec<usize>, hole: &[usize]) {
+    let hole_start = (0..hole.len())
+        .max_by(|a, b| verts[hole[*a]].x.total_cmp(&verts[hole[*b]].x))
+        .unwrap();
+    let m = verts[hole[hole_start]];
+
+    // Nearest ring edge hit by a ray from m towards +x.
+    let mut best: Option<(f32, usize)> = None;
+    for i in 0..ring.len() {
+        let a = verts[ring[i]];
+        let b = verts[ring[(i + 1) % ring.len()]];
+        if (a.y > m.y) == (b.y > m.y) {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= m.x && best.is_none_or(|(bx, _)| x < bx) {
+            best = Some((x, i));
+        }
+    }
+    let Some((hit_x, edge)) = best else {
+        return;
+    };
+    let i0 = edge;
+    let i1 = (edge + 1) % ring.len();
+    let mut bridge = if verts[ring[i0]].x > verts[ring[i1]].x { i0 } else { i1 };
+
+    // Reflex ring vertices inside (m, hit, candidate) would block the bridge;
+    // take the one at the smallest angle from the ray instead.
+    let hit = Vector2::new(hit_x, m.y);
+    let p = verts[ring[bridge]];
+    let mut best_angle = f32::INFINITY;
+    for i in 0..ring.len() {
+        let v = verts[ring[i]];
+        if ring[i] == ring[bridge] || !in_triangle(&v, &m, &hit, &p) || !is_reflex(verts, ring, ring[i]) {
+            continue;
+        }
+        let d = v - m;
+        let angle = d.y.abs().atan2(d.x);
+        if angle < best_angle || (angle == best_angle && d.get_mag_sqr() < (verts[ring[bridge]] - m).get_mag_sqr()) {
+            best_angle = angle;
+            bridge = i;
+        }
+    }
+
+    // A vertex on an earlier seam appears once per wedge it was split into;
+    // the hole must join the copy whose wedge the bridge runs through.
+    let to_m = m - verts[ring[bridge]];
+    let through = |i: usize| {
+        let (next, interior) = wedge(verts, ring, i);
+        ccw_angle(&next, &to_m) <= interior
+    };
+    if let Some(copy) = (0..ring.len()).find(|&i| ring[i] == ring[bridge] && through(i)) {
+        bridge = copy;
+    }
+
+    let mut merged = Vec::with_capacity(ring.len() + hole.len() + 2);
+    merged.extend_from_slice(&ring[..=bridge]);
+    for k in 0..=hole.len() {
+        merged.push(hole[(hole_start + k) % hole.len()]);
+    }
+    merged.extend_from_slice(&ring[bridge..]);
+    *ring = merged;
+}
+
+// The interior wedge at ring position i, as the direction of the outgoing
+// edge and the counter-clockwise angle from it to the incoming edge.
+fn wedge(verts: &[Vector2], ring: &[usize], i: usize) -> (Vector2, f32) {
+    let n = ring.len();
+    let v = verts[ring[i]];
+    let next = verts[ring[(i + 1) % n]] - v;
+    let prev = verts[ring[(i + n - 1) % n]] - v;
+    (next, ccw_angle(&next, &prev))
+}
+
+// Counter-clockwise angle from a to b in [0, 2pi).
+fn ccw_angle(a: &Vector2, b: &Vector2) -> f32 {
+    let angle = a.cross(b).atan2(a.dot(b));
+    if angle < 0.0 { angle + std::f32::consts::TAU } else { angle }
+}
+
+// Seams split a vertex into one copy per wedge, so whether it is reflex
+// depends on all of its copies together rather than on the seam edges.
+fn is_reflex(verts: &[Vector2], ring: &[usize], vertex: usize) -> bool {
+    let interior: f32 = (0..ring.len()).filter(|&i| ring[i] == vertex).map(|i| wedge(verts, ring, i).1).sum();
+    interior >= std::f32::consts::PI
+}
+
+fn in_triangle(p: &Vector2, a: &Vector2, b: &Vector2, c: &Vector2) -> bool {
+    let d0 = (*b - *a).cross(&(*p - *a));
+    let d1 = (*c - *b).cross(&(*p - *b));
+    let d2 = (*a - *c).cross(&(*p - *c));
+    let has_neg = d0 < 0.0 || d1 < 0.0 || d2 < 0.0;
+    let has_pos = d0 > 0.0 || d1 > 0.0 || d2 > 0.0;
+    !(has_neg && has_pos)
+}
+
+fn ear_clip(verts: &[Vector2], mut ring: Vec<usize>) -> Vec<[usize; 3]> {
+    let turn = |ring: &[usize], i: usize| {
+        let n = ring.len();
+        let [a, b, c] = [ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]].map(|j| verts[j]);
+        (b - a).cross(&(c - b))
+    };
+    let mut out = Vec::with_capacity(ring.len().saturating_sub(2));
+    while ring.len() > 3 {
+        let n = ring.len();
+        let ear = (0..n).find(|&i| {
+            if turn(&ring, i) <= 0.0 {
+                return false;
+            }
+            let [a, b, c] = [ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]].map(|j| verts[j]);
+            // Bridge seams repeat positions, so compare by position not index.
+            ring.iter().all(|&j| {
+                let p = verts[j];
+                same_point(&p, &a) || same_point(&p, &b) || same_point(&p, &c) || !in_triangle(&p, &a, &b, &c)
+            })
+        });
+        let i = match ear {
+            Some(i) => i,
+            // Degenerate input can leave no strict ear. Dropping a collinear
+            // vertex loses nothing; otherwise clip any convex one.
+            None => match (0..n).find(|&i| turn(&ring, i) == 0.0) {
+                Some(i) => {
+                    ring.remove(i);
+                    continue;
+                }
+                None => (0..n).find(|&i| turn(&ring, i) > 0.0).unwrap_or(0),
+            },
+        };
+        out.push([ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]]);
+        ring.remove(i);
+    }
+    if ring.len() == 3 && turn(&ring, 1) > 0.0 {
+        out.push([ring[0], ring[1], ring[2]]);
+    }
+    out
+}
+
+fn same_point(a: &Vector2, b: &Vector2) -> bool {
+    a.x == b.x && a.y == b.y
+}
+
+// Moves every edge outwards by distance (inwards when negative) with mitered
+// corners, bevelled where the miter would exceed miter_limit * distance.
+// Large inward offsets can self-intersect; the result is not cleaned up.
+pub fn offset(poly: &[Vector2], distance: f32, miter_limit: f32) -> Vec<Vector2> {
+    let n = poly.len();
+    let orient = if signed_area(poly) < 0.0 { -1.0 } else { 1.0 };
+    let normal = |a: &Vector2, b: &Vector2| {
+        let d = (*b - *a).get_norm();
+        Vector2::new(d.y, -d.x) * orient
+    };
+    let mut out = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = poly[(i + n - 1) % n];
+        let p = poly[i];
+        let next = poly[(i + 1) % n];
+        let n0 = normal(&prev, &p);
+        let n1 = normal(&p, &next);
+        // 1 / cos(half the turn) stretches the miter to keep both edges at distance.
+        let miter = (2.0 / (1.0 + n0.dot(&n1))).sqrt();
+        if miter.is_finite() && miter <= miter_limit {
+            out.push(p + (n0 + n1).get_norm() * (distance * miter));
+        } else {
+            out.push(p + n0 * distance);
+            out.push(p + n1 * distance);
+        }
+    }
+    out
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BooleanOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Class {
+    Inside,
+    Outside,
+    SharedSame,
+    SharedOpposite,
+}
+
+// Boolean operations split both boundaries at their crossings, keep the pieces
+// each operation needs and relink them. Outer rings come back
+// counter-clockwise and holes clockwise.
+pub fn union(a: &[Vector2], b: &[Vector2]) -> Vec<Vec<Vector2>> {
+    boolean(a, b, BooleanOp::Union)
+}
+
+pub fn intersection(a: &[Vector2], b: &[Vector2]) -> Vec<Vec<Vector2>> {
+    boolean(a, b, BooleanOp::Intersection)
+}
+
+// a with b removed.
+pub fn difference(a: &[Vector2], b: &[Vector2]) -> Vec<Vec<Vector2>> {
+    boolean(a, b, BooleanOp::Difference)
+}
+
+type PointKey = (u32, u32);
+
+// A directed piece of boundary, start to end.
+type Piece = (Vector2, Vector2);
+
+// Adding zero folds -0.0 into 0.0 so equal points hash equally.
+fn key(p: &Vector2) -> PointKey {
+    ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits())
+}
+
+fn boolean(a: &[Vector2], b: &[Vector2], op: BooleanOp) -> Vec<Vec<Vector2>> {
+    if a.len() < 3 || b.len() < 3 {
+        return match op {
+            BooleanOp::Union | BooleanOp::Difference if a.len() >= 3 => vec![ccw(a)],
+            BooleanOp::Union if b.len() >= 3 => vec![ccw(b)],
+            _ => Vec::new(),
+        };
+    }
+    let a = ccw(a);
+    let b = ccw(b);
+    let mut scale = 0.0f32;
+    for p in a.iter().chain(&b) {
+        scale = scale.max(p.x.abs()).max(p.y.abs());
+    }
+    let eps = POLYGON_EPSILON * scale.max(1.0);
+
+    let (frags_a, frags_b) = split_edges(&a, &b, eps);
+    let set_a: HashSet<(PointKey, PointKey)> = frags_a.iter().map(|(s, e)| (key(s), key(e))).collect();
+    let set_b: HashSet<(PointKey, PointKey)> = frags_b.iter().map(|(s, e)| (key(s), key(e))).collect();
+    let classify = |s: &Vector2, e: &Vector2, other: &[Vector2], other_set: &HashSet<(PointKey, PointKey)>| {
+        if other_set.contains(&(key(s), key(e))) {
+            Class::SharedSame
+        } else if other_set.contains(&(key(e), key(s))) {
+            Class::SharedOpposite
+        } else if contains_point(other, &((*s + *e) * 0.5)) {
+            Class::Inside
+        } else {
+            Class::Outside
+        }
+    };
+
+    let mut kept = Vec::new();
+    for (s, e) in &frags_a {
+        let keep = matches!(
+            (op, classify(s, e, &b, &set_b)),
+            (BooleanOp::Union, Class::Outside | Class::SharedSame)
+                | (BooleanOp::Intersection, Class::Inside | Class::SharedSame)
+                | (BooleanOp::Difference, Class::Outside | Class::SharedOpposite)
+        );
+        if keep {
+            kept.push((*s, *e));
+        }
+    }
+    // Shared pieces were already decided from a's side.
+    for (s, e) in &frags_b {
+        match (op, classify(s, e, &a, &set_a)) {
+            (BooleanOp::Union, Class::Outside) | (BooleanOp::Intersection, Class::Inside) => kept.push((*s, *e)),
+            (BooleanOp::Difference, Class::Inside) => kept.push((*e, *s)),
+            _ => {}
+        }
+    }
+
+    link_rings(&kept, eps)
+}
+
+fn ccw(poly: &[Vector2]) -> Vec<Vector2> {
+    let mut out = poly.to_vec();
+    set_winding(&mut out, Winding::CounterClockwise);
+    out
+}
+
+// Splits every edge of a and b where it meets the other polygon, including
+// T-junctions and collinear overlaps. Shared split points are bit-identical.
+fn split_edges(a: &[Vector2], b: &[Vector2], eps: f32) -> (Vec<Piece>, Vec<Piece>) {
+    let mut splits_a: Vec<Vec<(f32, Vector2)>> = vec![Vec::new(); a.len()];
+    let mut splits_b: Vec<Vec<(f32, Vector2)>> = vec![Vec::new(); b.len()];
+    for i in 0..a.len() {
+        let (p0, p1) = (a[i], a[(i + 1) % a.len()]);
+        let r = p1 - p0;
+        let r_len = r.get_mag();
+        if r_len <= eps {
+            continue;
+        }
+        let tol_t = eps / r_len;
+        for j in 0..b.len() {
+            let (q0, q1) = (b[j], b[(j + 1) % b.len()]);
+            let s = q1 - q0;
+            let s_len = s.get_mag();
+            if s_len <= eps {
+                continue;
+            }
+            let tol_u = eps / s_len;
+            let qp = q0 - p0;
+            let denom = r.cross(&s);
+
+            if denom.abs() > eps * r_len * s_len {
+                let t = qp.cross(&s) / denom;
+                let u = qp.cross(&r) / denom;
+                if t < -tol_t || t > 1.0 + tol_t || u < -tol_u || u > 1.0 + tol_u {
+                    continue;
+                }
+                // Snap crossings near a vertex onto that vertex.
+                let point = if t <= tol_t {
+                    p0
+                } else if t >= 1.0 - tol_t {
+                    p1
+                } else if u <= tol_u {
+                    q0
+                } else if u >= 1.0 - tol_u {
+                    q1
+                } else {
+                    p0 + r * t
+                };
+                if t > tol_t && t < 1.0 - tol_t {
+                    splits_a[i].push((t, point));
+                }
+                if u > tol_u && u < 1.0 - tol_u {
+                    splits_b[j].push((u, point));
+                }
+            } else if qp.cross(&r).abs() <= eps * r_len {
+                for q in [q0, q1] {
+                    let t = (q - p0).dot(&r) / (r_len * r_len);
+                    if t > tol_t && t < 1.0 - tol_t {
+                        splits_a[i].push((t, q));
+                    }
+                }
+                for p in [p0, p1] {
+                    let u = (p - q0).dot(&s) / (s_len * s_len);
+                    if u > tol_u && u < 1.0 - tol_u {
+                        splits_b[j].push((u, p));
+                    }
+                }
+            }
+        }
+    }
+    (fragments(a, splits_a), fragments(b, splits_b))
+}
+
+fn fragments(poly: &[Vector2], mut splits: Vec<Vec<(f32, Vector2)>>) -> Vec<Piece> {
+    let mut out = Vec::new();
+    for (i, edge_splits) in splits.iter_mut().enumerate() {
+        edge_splits.sort_by(|x, y| x.0.total_cmp(&y.0));
+        let mut prev = poly[i];
+        for (_, p) in edge_splits.iter().chain(std::iter::once(&(1.0, poly[(i + 1) % poly.len()]))) {
+            if key(p) != key(&prev) {
+                out.push((prev, *p));
+                prev = *p;
+            }
+        }
+    }
+    out
+}
+
+// Chains directed pieces end to start into closed rings and drops the
+// vertices the splitting left in the middle of straight runs.
+fn link_rings(pieces: &[Piece], eps: f32) -> Vec<Vec<Vector2>> {
+    let mut by_start: HashMap<PointKey, Vec<usize>> = HashMap::new();
+    for (i, (s, _)) in pieces.iter().enumerate() {
+        by_start.entry(key(s)).or_default().push(i);
+    }
+    let mut used = vec![false; pieces.len()];
+    let mut out = Vec::new();
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut cur = first;
+        let closed = loop {
+            used[cur] = true;
+            ring.push(pieces[cur].0);
+            let end = key(&pieces[cur].1);
+            if end == key(&pieces[first].0) {
+                break true;
+            }
+            match by_start.get(&end).and_then(|c| c.iter().find(|i| !used[**i])) {
+                Some(&next) => cur = next,
+                None => break false,
+            }
+        };
+        if !closed {
+            continue;
+        }
+        let ring = remove_collinear(&ring, eps);
+        if ring.len() >= 3 {
+            out.push(ring);
+        }
+    }
+    out
+}
+
+fn remove_collinear(ring: &[Vector2], eps: f32) -> Vec<Vector2> {
+    let n = ring.len();
+    let mut out = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = ring[(i + n - 1) % n];
+        let p = ring[i];
+        let next = ring[(i + 1) % n];
+        let d0 = p - prev;
+        let d1 = next - p;
+        if d0.cross(&d1).abs() <= eps * (d0.get_mag() + d1.get_mag()) && d0.dot(&d1) > 0.0 {
+            continue;
+        }
+        out.push(p);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f32, y: f32) -> Vector2 {
+        Vector2::new(x, y)
+    }
+
+    fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Vec<Vector2> {
+        vec![p(x0, y0), p(x1, y0), p(x1, y1), p(x0, y1)]
+    }
+
+    fn total_area(rings: &[Vec<Vector2>]) -> f32 {
+        rings.iter().map(|r| signed_area(r)).sum()
+    }
+
+    fn triangles_area(verts: &[Vector2], tris: &[[usize; 3]]) -> f32 {
+        tris.iter().map(|t| signed_area(&t.map(|i| verts[i]))).sum()
+    }
+
+    #[test]
+    fn area_and_winding() {
+        let mut square = rect(0.0, 0.0, 2.0, 3.0);
+        assert_eq!(signed_area(&square), 6.0);
+        assert_eq!(winding(&square), Winding::CounterClockwise);
+        set_winding(&mut square, Winding::Clockwise);
+        assert_eq!(signed_area(&square), -6.0);
+        assert_eq!(winding(&square), Winding::Clockwise);
+    }
+
+    #[test]
+    fn convexity() {
+        assert!(is_convex(&rect(0.0, 0.0, 1.0, 1.0)));
+        assert!(is_convex(&[p(0.0, 0.0), p(1.0, 0.0), p(2.0, 0.0), p(2.0, 2.0)]));
+        let mut notch = vec![p(0.0, 0.0), p(2.0, 0.0), p(1.0, 0.5), p(2.0, 2.0), p(0.0, 2.0)];
+        assert!(!is_convex(&notch));
+        notch.reverse();
+        assert!(!is_convex(&notch));
+    }
+
+    #[test]
+    fn points_in_polygons() {
+        // An L shape.
+        let l = [p(0.0, 0.0), p(2.0, 0.0), p(2.0, 1.0), p(1.0, 1.0), p(1.0, 2.0), p(0.0, 2.0)];
+        assert!(contains_point(&l, &p(0.5, 1.5)));
+        assert!(contains_point(&l, &p(1.5, 0.5)));
+        assert!(!contains_point(&l, &p(1.5, 1.5)));
+        assert!(!contains_point(&l, &p(-0.5, 0.5)));
+        assert_eq!(winding_number(&l, &p(0.5, 0.5)), 1);
+        let mut cw = l.to_vec();
+        cw.reverse();
+        assert_eq!(winding_number(&cw, &p(0.5, 0.5)), -1);
+    }
+
+    #[test]
+    fn triangulation_covers_the_polygon() {
+        let l = [p(0.0, 0.0), p(2.0, 0.0), p(2.0, 1.0), p(1.0, 1.0), p(1.0, 2.0), p(0.0, 2.0)];
+        let tris = triangulate(&l, &[]);
+        assert_eq!(tris.len(), 4);
+        assert!((triangles_area(&l, &tris) - 3.0).abs() < 1e-5);
+        assert!(tris.iter().all(|t| signed_area(&t.map(|i| l[i])) > 0.0));
+
+        let outer = rect(0.0, 0.0, 4.0, 4.0);
+        let hole_a = rect(1.0, 1.0, 2.0, 2.0);
+        let hole_b = [p(2.5, 2.5), p(3.5, 2.5), p(3.0, 3.5)];
+        let tris = triangulate(&outer, &[&hole_a, &hole_b]);
+        let verts: Vec<Vector2> = outer.iter().chain(&hole_a).chain(&hole_b).copied().collect();
+        // n - 2 + 2h triangles for n vertices in total and h holes.
+        assert_eq!(tris.len(), verts.len() - 2 + 2 * 2);
+        assert!((triangles_area(&verts, &tris) - (16.0 - 1.0 - 0.5)).abs() < 1e-4);
+        for t in &tris {
+            let centroid = (verts[t[0]] + verts[t[1]] + verts[t[2]]) * (1.0 / 3.0);
+            assert!(!contains_point(&hole_a, &centroid) && !contains_point(&hole_b, &centroid));
+        }
+    }
+
+    #[test]
+    fn triangulation_bridges_holes_through_earlier_seams() {
+        // The ray from the second hole crosses the first hole's seam, so the
+        // bridge joins the outer corner that now appears twice in the ring.
+        let outer = rect(0.0, 0.0, 10.0, 10.0);
+        let hole_a = [p(7.0, 6.0), p(6.0, 7.0), p(6.0, 5.0)];
+        let hole_b = [p(5.0, 8.0), p(4.0, 9.0), p(4.0, 7.0)];
+        let tris = triangulate(&outer, &[&hole_a, &hole_b]);
+        let verts: Vec<Vector2> = outer.iter().chain(&hole_a).chain(&hole_b).copied().collect();
+        assert_eq!(tris.len(), verts.len() - 2 + 2 * 2);
+        assert!((triangles_area(&verts, &tris) - (100.0 - 1.0 - 1.0)).abs() < 1e-4);
+        assert!(tris.iter().all(|t| signed_area(&t.map(|i| verts[i])) > 0.0));
+    }
+
+    #[test]
+    fn offset_square() {
+        let square = rect(0.0, 0.0, 2.0, 2.0);
+        let grown = offset(&square, 0.5, 2.0);
+        assert_eq!(grown.len(), 4);
+        assert!((signed_area(&grown) - 9.0).abs() < 1e-4);
+        let shrunk = offset(&square, -0.5, 2.0);
+        assert!((signed_area(&shrunk) - 1.0).abs() < 1e-4);
+        // A miter limit below sqrt(2) bevels every corner.
+        let bevelled = offset(&square, 0.5, 1.2);
+        assert_eq!(bevelled.len(), 8);
+        assert!((signed_area(&bevelled) - (9.0 - 4.0 * 0.125)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn booleans_of_overlapping_squares() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+        let b = rect(1.0, 1.0, 3.0, 3.0);
+        assert!((total_area(&union(&a, &b)) - 7.0).abs() < 1e-5);
+        assert!((total_area(&intersection(&a, &b)) - 1.0).abs() < 1e-5);
+        assert!((total_area(&difference(&a, &b)) - 3.0).abs() < 1e-5);
+        assert!((total_area(&difference(&b, &a)) - 3.0).abs() < 1e-5);
+        assert_eq!(union(&a, &b).len(), 1);
+        assert_eq!(union(&a, &b)[0].len(), 8);
+        // Either input winding gives the same result.
+        let mut cw = b.clone();
+        cw.reverse();
+        assert!((total_area(&union(&a, &cw)) - 7.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn booleans_with_a_shared_edge() {
+        let a = rect(0.0, 0.0, 1.0, 1.0);
+        let b = rect(1.0, 0.0, 2.0, 1.0);
+        let u = union(&a, &b);
+        assert_eq!(u.len(), 1);
+        assert_eq!(u[0].len(), 4);
+        assert!((signed_area(&u[0]) - 2.0).abs() < 1e-5);
+        assert!(intersection(&a, &b).is_empty());
+        assert!((total_area(&difference(&a, &b)) - 1.0).abs() < 1e-5);
+
+        // Part of an edge shared, meeting in T-junctions.
+        let c = rect(1.0, 0.5, 2.0, 2.0);
+        assert!((total_area(&union(&a, &c)) - 2.5).abs() < 1e-5);
+        assert!(intersection(&a, &c).is_empty());
+    }
+
+    #[test]
+    fn booleans_of_identical_inputs() {
+        let a = rect(0.0, 0.0, 1.0, 2.0);
+        let u = union(&a, &a);
+        assert_eq!(u.len(), 1);
+        assert!((signed_area(&u[0]) - 2.0).abs() < 1e-5);
+        let i = intersection(&a, &a);
+        assert_eq!(i.len(), 1);
+        assert!((signed_area(&i[0]) - 2.0).abs() < 1e-5);
+        assert!(difference(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn booleans_with_containment_and_disjoint_inputs() {
+        let outer = rect(0.0, 0.0, 4.0, 4.0);
+        let inner = rect(1.0, 1.0, 2.0, 2.0);
+        let d = difference(&outer, &inner);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d.iter().filter(|r| winding(r) == Winding::Clockwise).count(), 1);
+        assert!((total_area(&d) - 15.0).abs() < 1e-5);
+        assert!((total_area(&union(&outer, &inner)) - 16.0).abs() < 1e-5);
+        assert!((total_area(&intersection(&outer, &inner)) - 1.0).abs() < 1e-5);
+        assert!(difference(&inner, &outer).is_empty());
+
+        let far = rect(10.0, 10.0, 11.0, 11.0);
+        assert_eq!(union(&inner, &far).len(), 2);
+        assert!(intersection(&inner, &far).is_empty());
+        assert!((total_area(&difference(&inner, &far)) - 1.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::vector2::Vector2;
+
+// Orientation and incircle tests with exact signs, after Shewchuk's "Adaptive
+// Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates".
+// A plain f64 evaluation is used when its error bound proves the sign; the
+// rest fall back to exact expansion arithmetic. Magnitudes are approximate.
+
+const HALF_EPSILON: f64 = f64::EPSILON * 0.5;
+const CCW_ERROR_BOUND: f64 = (3.0 + 16.0 * HALF_EPSILON) * HALF_EPSILON;
+const ICC_ERROR_BOUND: f64 = (10.0 + 96.0 * HALF_EPSILON) * HALF_EPSILON;
+
+// Positive when a, b, c wind counter-clockwise, negative when clockwise and
+// zero when collinear.
+pub fn orient2d(a: &Vector2, b: &Vector2, c: &Vector2) -> f64 {
+    let left = (a.x as f64 - c.x as f64) * (b.y as f64 - c.y as f64);
+    let right = (a.y as f64 - c.y as f64) * (b.x as f64 - c.x as f64);
+    let det = left - right;
+    if det.abs() > CCW_ERROR_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+
+    let acx = diff(a.x, c.x);
+    let bcy = diff(b.y, c.y);
+    let acy = diff(a.y, c.y);
+    let bcx = diff(b.x, c.x);
+    estimate(&sum(&mul(&acx, &bcy), &neg(&mul(&acy, &bcx))))
+}
+
+// Positive when d lies inside the circle through the counter-clockwise a, b,
+// c, negative outside and zero on it.
+pub fn incircle(a: &Vector2, b: &Vector2, c: &Vector2, d: &Vector2) -> f64 {
+    let (dx, dy) = (d.x as f64, d.y as f64);
+    let (adx, ady) = (a.x as f64 - dx, a.y as f64 - dy);
+    let (bdx, bdy) = (b.x as f64 - dx, b.y as f64 - dy);
+    let (cdx, cdy) = (c.x as f64 - dx, c.y as f64 - dy);
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > ICC_ERROR_BOUND * permanent {
+        return det;
+    }
+
+    let [adx, ady, bdx, bdy, cdx, cdy] = [
+        diff(a.x, d.x),
+        diff(a.y, d.y),
+        diff(b.x, d.x),
+        diff(b.y, d.y),
+        diff(c.x, d.x),
+        diff(c.y, d.y),
+    ];
+    let lift = |x: &[f64], y: &[f64]| sum(&mul(x, x), &mul(y, y));
+    let cross = |x0: &[f64], y1: &[f64], x1: &[f64], y0: &[f64]| sum(&mul(x0, y1), &neg(&mul(x1, y0)));
+    let a_term = mul(&lift(&adx, &ady), &cross(&bdx, &cdy, &cdx, &bdy));
+    let b_term = mul(&lift(&bdx, &bdy), &cross(&cdx, &ady, &adx, &cdy));
+    let c_term = mul(&lift(&cdx, &cdy), &cross(&adx, &bdy, &bdx, &ady));
+    estimate(&sum(&sum(&a_term, &b_term), &c_term))
+}
+
+// Expansions are lists of non-overlapping f64s in increasing magnitude whose
+// exact sum is the value. Every operation below builds its result by growing
+// from empty, which keeps that property.
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bv = s - a;
+    let av = s - bv;
+    (s, (a - av) + (b - bv))
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+fn grow(e: &[f64], b: f64) -> Vec<f64> {
+    let mut out = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &x in e {
+        let (s, err) = two_sum(q, x);
+        if err != 0.0 {
+            out.push(err);
+        }
+        q = s;
+    }
+    if q != 0.0 {
+        out.push(q);
+    }
+    out
+}
+
+// a - b for f32 inputs, exactly.
+fn diff(a: f32, b: f32) -> Vec<f64> {
+    let (s, err) = two_sum(a as f64, -(b as f64));
+    grow(&grow(&[], err), s)
+}
+
+fn sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |acc, x| grow(&acc, *x))
+}
+
+fn neg(e: &[f64]) -> Vec<f64> {
+    e.iter().map(|x| -x).collect()
+}
+
+fn mul(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut out = Vec::new();
+    for &b in f {
+        for &a in e {
+            let (p, err) = two_product(a, b);
+            out = grow(&grow(&out, err), p);
+        }
+    }
+    out
+}
+
+// The largest component carries the sign.
+fn estimate(e: &[f64]) -> f64 {
+    e.last().copied().unwrap_or(0.0)
+}
@@ -0,0 +1,432 @@
+use crate::{
+    geometry::{aabb::Aabb3, obb::Obb, plane::Plane, sphere::Sphere},
+    mat4::Mat4,
+    util::is_zero,
+    vector3::Vector3,
+};
+
+// Relative tolerance for the near-parallel tests; each use scales it by the
+// magnitudes involved so small shapes and short directions still hit.
+const RAY_EPSILON: f32 = 1e-6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vector3,
+    pub dir: Vector3,
+}
+
+// t is measured in units of the ray's dir, so point == ray.at(t).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    pub t: f32,
+    pub point: Vector3,
+    pub normal: Vector3,
+}
+
+// Barycentric weights (1 - u - v, u, v) of the triangle's (a, b, c).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleHit {
+    pub hit: RayHit,
+    pub u: f32,
+    pub v: f32,
+}
+
+impl Ray {
+    pub fn new(origin: Vector3, dir: Vector3) -> Self {
+        Self { origin, dir }
+    }
+
+    pub fn from_points(from: &Vector3, to: &Vector3) -> Self {
+        Self {
+            origin: *from,
+            dir: (*to - *from).get_norm(),
+        }
+    }
+
+    pub fn at(&self, t: f32) -> Vector3 {
+        self.origin + self.dir * t
+    }
+
+    pub fn transform(&mut self, m: &Mat4) {
+        *self = self.get_transform(m)
+    }
+
+    pub fn get_transform(&self, m: &Mat4) -> Self {
+        Self {
+            origin: m.transform_point(&self.origin),
+            dir: m.transform_vector(&self.dir),
+        }
+    }
+
+    fn hit(&self, t: f32, normal: Vector3) -> RayHit {
+        RayHit {
+            t,
+            point: self.at(t),
+            normal,
+        }
+    }
+
+    // Hits either side; the normal faces back towards the ray.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<RayHit> {
+        let denom = plane.normal.dot(&self.dir);
+        if denom == 0.0 {
+            return None;
+        }
+        let t = -(plane.normal.dot(&self.origin) + plane.d) / denom;
+        if t < 0.0 {
+            return None;
+        }
+        let normal = if denom > 0.0 { -plane.normal } else { plane.normal };
+        Some(self.hit(t, normal))
+    }
+
+    // From inside the sphere this returns the exit point.
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<RayHit> {
+        let oc = self.origin - sphere.center;
+        let a = self.dir.dot(&self.dir);
+        let b = oc.dot(&self.dir);
+        let c = oc.dot(&oc) - sphere.radius * sphere.radius;
+        let disc = b * b - a * c;
+        if disc < 0.0 || a == 0.0 {
+            return None;
+        }
+        let sqrt_disc = disc.sqrt();
+        let mut t = (-b - sqrt_disc) / a;
+        if t < 0.0 {
+            t = (-b + sqrt_disc) / a;
+        }
+        if t < 0.0 {
+            return None;
+        }
+        let point = self.at(t);
+        Some(RayHit {
+            t,
+            point,
+            normal: (point - sphere.center).get_norm(),
+        })
+    }
+
+    // Slab method. From inside the box this returns the exit point.
+    pub fn intersect_aabb(&self, aabb: &Aabb3) -> Option<RayHit> {
+        let mut t_min = f32::NEG_INFINITY;
+        let mut t_max = f32::INFINITY;
+        let mut min_axis = 0;
+        let mut max_axis = 0;
+        for axis in 0..3 {
+            let o = self.origin[axis];
+            let d = self.dir[axis];
+            if d == 0.0 {
+                if o < aabb.min[axis] || o > aabb.max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let inv = 1.0 / d;
+            let mut t1 = (aabb.min[axis] - o) * inv;
+            let mut t2 = (aabb.max[axis] - o) * inv;
+            if t1 > t2 {
+                std::mem::swap(&mut t1, &mut t2);
+            }
+            if t1 > t_min {
+                t_min = t1;
+                min_axis = axis;
+            }
+            if t2 < t_max {
+                t_max = t2;
+                max_axis = axis;
+            }
+            if t_min > t_max {
+                return None;
+            }
+        }
+        if t_max < 0.0 {
+            return None;
+        }
+        let (t, axis, sign) = if t_min >= 0.0 {
+            (t_min, min_axis, -self.dir[min_axis].signum())
+        } else {
+            (t_max, max_axis, self.dir[max_axis].signum())
+        };
+        let mut normal = Vector3::zero();
+        normal[axis] = sign;
+        Some(self.hit(t, normal))
+    }
+
+    pub fn intersect_obb(&self, obb: &Obb) -> Option<RayHit> {
+        let inv = obb.orientation.get_conj();
+        let local = Ray {
+            origin: inv.lqcvq(&(self.origin - obb.center)),
+            dir: inv.lqcvq(&self.dir),
+        };
+        let hit = local.intersect_aabb(&Aabb3::new(-obb.half_extents, obb.half_extents))?;
+        Some(self.hit(hit.t, obb.orientation.lqcvq(&hit.normal)))
+    }
+
+    // Möller–Trumbore. Both faces are hit; the normal faces back towards the ray.
+    pub fn intersect_triangle(&self, a: &Vector3, b: &Vector3, c: &Vector3) -> Option<TriangleHit> {
+        let e1 = *b - *a;
+        let e2 = *c - *a;
+        let p = self.dir.cross(&e2);
+        let det = e1.dot(&p);
+        if is_zero(det, RAY_EPSILON * self.dir.get_mag() * e1.get_mag() * e2.get_mag()) {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let s = self.origin - *a;
+        let u = s.dot(&p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&e1);
+        let v = self.dir.dot(&q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = e2.dot(&q) * inv_det;
+        if t < 0.0 {
+            return None;
+        }
+        let mut normal = e1.cross(&e2).get_norm();
+        if normal.dot(&self.dir) > 0.0 {
+            normal = -normal;
+        }
+        Some(TriangleHit {
+            hit: self.hit(t, normal),
+            u,
+            v,
+        })
+    }
+
+    pub fn intersect_disc(&self, center: &Vector3, normal: &Vector3, radius: f32) -> Option<RayHit> {
+        let n = normal.get_norm();
+        let hit = self.intersect_plane(&Plane::new(n, -n.dot(center)))?;
+        if (hit.point - *center).get_mag_sqr() > radius * radius {
+            return None;
+        }
+        Some(hit)
+    }
+
+    // Capsule around the segment a-b (Inigo Quilez's formulation). The
+    // capsule is convex, so the ray's span inside it runs from the earliest
+    // entry to the latest exit over the clipped cylinder and the two cap
+    // spheres. From inside the capsule this returns the exit point.
+    pub fn intersect_capsule(&self, a: &Vector3, b: &Vector3, radius: f32) -> Option<RayHit> {
+        let dir_mag = self.dir.get_mag();
+        if dir_mag == 0.0 {
+            return None;
+        }
+        let rd = self.dir * (1.0 / dir_mag);
+        let ba = *b - *a;
+        let oa = self.origin - *a;
+        let baba = ba.dot(&ba);
+        let bard = ba.dot(&rd);
+        let baoa = ba.dot(&oa);
+        let rdoa = rd.dot(&oa);
+        let oaoa = oa.dot(&oa);
+
+        let mut span: Option<(f32, f32)> = None;
+        let mut add = |t0: f32, t1: f32| {
+            if t0 <= t1 {
+                span = Some(span.map_or((t0, t1), |(s0, s1)| (s0.min(t0), s1.max(t1))));
+            }
+        };
+
+        // Infinite cylinder around the axis, clipped to the slab between the caps.
+        let qa = baba - bard * bard;
+        let qb = baba * rdoa - baoa * bard;
+        let qc = baba * oaoa - baoa * baoa - radius * radius * baba;
+        let cylinder = if is_zero(qa, RAY_EPSILON * baba) {
+            (qc <= 0.0).then_some((f32::NEG_INFINITY, f32::INFINITY))
+        } else {
+            let h = qb * qb - qa * qc;
+            (h >= 0.0).then(|| ((-qb - h.sqrt()) / qa, (-qb + h.sqrt()) / qa))
+        };
+        let slab = if bard == 0.0 {
+            (baoa > 0.0 && baoa < baba).then_some((f32::NEG_INFINITY, f32::INFINITY))
+        } else {
+            let t0 = -baoa / bard;
+            let t1 = (baba - baoa) / bard;
+            Some((t0.min(t1), t0.max(t1)))
+        };
+        if let (Some(c), Some(s)) = (cylinder, slab) {
+            add(c.0.max(s.0), c.1.min(s.1));
+        }
+
+        for cap in [a, b] {
+            let oc = self.origin - *cap;
+            let hb = oc.dot(&rd);
+            let disc = hb * hb - (oc.dot(&oc) - radius * radius);
+            if disc >= 0.0 {
+                add(-hb - disc.sqrt(), -hb + disc.sqrt());
+            }
+        }
+
+        let (t_in, t_out) = span?;
+        let t = if t_in >= 0.0 { t_in } else { t_out };
+        if t < 0.0 {
+            return None;
+        }
+        let point = self.origin + rd * t;
+        let along = if baba > 0.0 { ((point - *a).dot(&ba) / baba).clamp(0.0, 1.0) } else { 0.0 };
+        let axis_point = *a + ba * along;
+        Some(RayHit {
+            t: t / dir_mag,
+            point,
+            normal: (point - axis_point).get_norm(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Deg, quat::Quat};
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} != {b}");
+    }
+
+    #[test]
+    fn plane_hits_either_side_facing_the_ray() {
+        let plane = Plane::new(v(0.0, 1.0, 0.0), -2.0);
+        let hit = Ray::new(v(0.0, 5.0, 0.0), v(0.0, -2.0, 0.0)).intersect_plane(&plane).unwrap();
+        assert_close(hit.t, 1.5);
+        assert_eq!(hit.normal, v(0.0, 1.0, 0.0));
+        let hit = Ray::new(v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0)).intersect_plane(&plane).unwrap();
+        assert_close(hit.t, 2.0);
+        assert_eq!(hit.normal, v(0.0, -1.0, 0.0));
+        assert!(Ray::new(v(0.0, 5.0, 0.0), v(0.0, 1.0, 0.0)).intersect_plane(&plane).is_none());
+        assert!(Ray::new(v(0.0, 5.0, 0.0), v(1.0, 0.0, 0.0)).intersect_plane(&plane).is_none());
+    }
+
+    #[test]
+    fn sphere_entry_exit_and_miss() {
+        let sphere = Sphere::new(v(0.0, 0.0, 5.0), 1.0);
+        let hit = Ray::new(Vector3::zero(), v(0.0, 0.0, 1.0)).intersect_sphere(&sphere).unwrap();
+        assert_close(hit.t, 4.0);
+        assert_eq!(hit.normal, v(0.0, 0.0, -1.0));
+        let hit = Ray::new(v(0.0, 0.0, 5.0), v(0.0, 0.0, 1.0)).intersect_sphere(&sphere).unwrap();
+        assert_close(hit.t, 1.0);
+        assert_eq!(hit.normal, v(0.0, 0.0, 1.0));
+        assert!(Ray::new(Vector3::zero(), v(0.0, 0.0, -1.0)).intersect_sphere(&sphere).is_none());
+        assert!(Ray::new(v(2.0, 0.0, 0.0), v(0.0, 0.0, 1.0)).intersect_sphere(&sphere).is_none());
+    }
+
+    #[test]
+    fn aabb_face_normals() {
+        let aabb = Aabb3::new(v(-1.0, -1.0, -1.0), v(1.0, 1.0, 1.0));
+        let hit = Ray::new(v(-5.0, 0.5, 0.0), v(1.0, 0.0, 0.0)).intersect_aabb(&aabb).unwrap();
+        assert_close(hit.t, 4.0);
+        assert_eq!(hit.normal, v(-1.0, 0.0, 0.0));
+        let hit = Ray::new(Vector3::zero(), v(0.0, 0.0, -2.0)).intersect_aabb(&aabb).unwrap();
+        assert_close(hit.t, 0.5);
+        assert_eq!(hit.normal, v(0.0, 0.0, -1.0));
+        assert!(Ray::new(v(-5.0, 2.0, 0.0), v(1.0, 0.0, 0.0)).intersect_aabb(&aabb).is_none());
+        assert!(Ray::new(v(-5.0, 0.0, 0.0), v(-1.0, 0.0, 0.0)).intersect_aabb(&aabb).is_none());
+    }
+
+    #[test]
+    fn obb_hit_is_rotated_back() {
+        let q = Quat::from_axis_angle(&v(0.0, 0.0, 1.0), Deg(45.0));
+        let obb = Obb::new(v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0), q);
+        let hit = Ray::new(v(-5.0, 0.0, 0.0), v(1.0, 0.0, 0.0)).intersect_obb(&obb).unwrap();
+        assert_close(hit.t, 5.0 - 2f32.sqrt());
+        crate::assert_approx_eq!(hit.normal, v(-1.0, 1.0, 0.0).get_norm());
+    }
+
+    #[test]
+    fn triangle_barycentrics() {
+        let (a, b, c) = (v(0.0, 0.0, 0.0), v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0));
+        let hit = Ray::new(v(0.25, 0.5, 2.0), v(0.0, 0.0, -1.0)).intersect_triangle(&a, &b, &c).unwrap();
+        assert_close(hit.hit.t, 2.0);
+        assert_close(hit.u, 0.25);
+        assert_close(hit.v, 0.5);
+        assert_eq!(hit.hit.normal, v(0.0, 0.0, 1.0));
+        let back = Ray::new(v(0.25, 0.25, -1.0), v(0.0, 0.0, 1.0)).intersect_triangle(&a, &b, &c).unwrap();
+        assert_eq!(back.hit.normal, v(0.0, 0.0, -1.0));
+        assert!(Ray::new(v(0.75, 0.75, 2.0), v(0.0, 0.0, -1.0)).intersect_triangle(&a, &b, &c).is_none());
+        assert!(Ray::new(v(0.25, 0.25, 0.0), v(1.0, 0.0, 0.0)).intersect_triangle(&a, &b, &c).is_none());
+    }
+
+    #[test]
+    fn small_triangle_and_short_direction() {
+        let (a, b, c) = (v(0.0, 0.0, 0.0), v(0.01, 0.0, 0.0), v(0.0, 0.01, 0.0));
+        let hit = Ray::new(v(0.0025, 0.0025, 0.005), v(0.0, 0.0, -0.01)).intersect_triangle(&a, &b, &c).unwrap();
+        assert_close(hit.hit.t, 0.5);
+        assert_close(hit.u, 0.25);
+        assert_close(hit.v, 0.25);
+        let (a, b, c) = (v(0.0, 0.0, 0.0), v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0));
+        let hit = Ray::new(v(0.25, 0.25, 1.0), v(0.0, 0.0, -1e-4)).intersect_triangle(&a, &b, &c).unwrap();
+        assert_close(hit.hit.t * 1e-4, 1.0);
+    }
+
+    #[test]
+    fn short_direction_hits() {
+        let dir = v(1e-4, 0.0, 0.0);
+        let origin = v(-5.0, 1.0, 0.0);
+        let hit = Ray::new(origin, dir).intersect_plane(&Plane::new(v(1.0, 0.0, 0.0), 0.0)).unwrap();
+        assert_close(hit.t * 1e-4, 5.0);
+        let hit = Ray::new(origin, dir).intersect_sphere(&Sphere::new(v(0.0, 1.0, 0.0), 1.0)).unwrap();
+        assert_close(hit.t * 1e-4, 4.0);
+        let hit = Ray::new(origin, dir).intersect_aabb(&Aabb3::new(v(-1.0, 0.0, -1.0), v(1.0, 2.0, 1.0))).unwrap();
+        assert_close(hit.t * 1e-4, 4.0);
+        let hit = Ray::new(origin, dir).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t * 1e-4, 4.0);
+        let hit = Ray::new(v(0.0, 5.0, 0.0), v(0.0, -1e-4, 0.0)).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t * 1e-4, 2.0);
+        assert!(Ray::new(origin, v(0.0, 0.0, 0.0)).intersect_sphere(&Sphere::new(origin, 1.0)).is_none());
+    }
+
+    #[test]
+    fn disc_radius() {
+        let (center, normal) = (v(0.0, 0.0, 0.0), v(0.0, 0.0, 1.0));
+        assert!(Ray::new(v(0.9, 0.0, 1.0), v(0.0, 0.0, -1.0)).intersect_disc(&center, &normal, 1.0).is_some());
+        assert!(Ray::new(v(0.8, 0.8, 1.0), v(0.0, 0.0, -1.0)).intersect_disc(&center, &normal, 1.0).is_none());
+    }
+
+    const A: Vector3 = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+    const B: Vector3 = Vector3 { x: 0.0, y: 2.0, z: 0.0 };
+
+    #[test]
+    fn capsule_from_outside() {
+        let hit = Ray::new(v(-5.0, 1.0, 0.0), v(2.0, 0.0, 0.0)).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t, 2.0);
+        crate::assert_approx_eq!(hit.point, v(-1.0, 1.0, 0.0));
+        crate::assert_approx_eq!(hit.normal, v(-1.0, 0.0, 0.0));
+        let hit = Ray::new(v(0.0, 5.0, 0.0), v(0.0, -1.0, 0.0)).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t, 2.0);
+        crate::assert_approx_eq!(hit.normal, v(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn capsule_from_inside_cylinder() {
+        let hit = Ray::new(v(0.0, 0.5, 0.0), v(1.0, 0.0, 0.0)).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t, 1.0);
+        crate::assert_approx_eq!(hit.normal, v(1.0, 0.0, 0.0));
+        // Along the axis the exit is through the far cap.
+        let hit = Ray::new(v(0.0, 0.5, 0.0), v(0.0, 1.0, 0.0)).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t, 2.5);
+        crate::assert_approx_eq!(hit.normal, v(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn capsule_from_inside_cap() {
+        let hit = Ray::new(v(0.0, -0.5, 0.0), v(0.0, -1.0, 0.0)).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t, 0.5);
+        crate::assert_approx_eq!(hit.normal, v(0.0, -1.0, 0.0));
+        // Leaving the cap upwards exits through the top cap instead.
+        let hit = Ray::new(v(0.0, -0.5, 0.0), v(0.0, 1.0, 0.0)).intersect_capsule(&A, &B, 1.0).unwrap();
+        assert_close(hit.t, 3.5);
+    }
+
+    #[test]
+    fn capsule_miss() {
+        assert!(Ray::new(v(-5.0, 1.0, 2.0), v(1.0, 0.0, 0.0)).intersect_capsule(&A, &B, 1.0).is_none());
+        assert!(Ray::new(v(-5.0, 1.0, 0.0), v(-1.0, 0.0, 0.0)).intersect_capsule(&A, &B, 1.0).is_none());
+        assert!(Ray::new(v(0.0, 4.0, 0.0), v(1.0, 0.0, 0.0)).intersect_capsule(&A, &B, 1.0).is_none());
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use crate::{geometry::aabb::Aabb3, vector3::Vector3};
+
+// Uniform grid of points hashed by cell, so only occupied cells cost memory.
+// Radius queries are cheapest with a cell size near the usual query radius.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpatialHash {
+    pub cell_size: f32,
+    cells: HashMap<[i32; 3], Vec<(usize, Vector3)>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn cell_of(&self, p: &Vector3) -> [i32; 3] {
+        [p.x, p.y, p.z].map(|v| (v / self.cell_size).floor() as i32)
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, item: usize, p: &Vector3) {
+        self.cells.entry(self.cell_of(p)).or_default().push((item, *p));
+    }
+
+    // p must be where the item was inserted. Returns false if it was not there.
+    pub fn remove(&mut self, item: usize, p: &Vector3) -> bool {
+        let cell = self.cell_of(p);
+        let Some(entries) = self.cells.get_mut(&cell) else {
+            return false;
+        };
+        let Some(at) = entries.iter().position(|e| e.0 == item) else {
+            return false;
+        };
+        entries.swap_remove(at);
+        if entries.is_empty() {
+            self.cells.remove(&cell);
+        }
+        true
+    }
+
+    pub fn update(&mut self, item: usize, from: &Vector3, to: &Vector3) {
+        let cell = self.cell_of(to);
+        if self.cell_of(from) == cell {
+            if let Some(e) = self.cells.get_mut(&cell).and_then(|c| c.iter_mut().find(|e| e.0 == item)) {
+                e.1 = *to;
+                return;
+            }
+        }
+        self.remove(item, from);
+        self.insert(item, to);
+    }
+
+    // Items within radius of p, inclusive, in no particular order.
+    pub fn query_radius(&self, p: &Vector3, radius: f32) -> Vec<usize> {
+        let radius_sqr = radius * radius;
+        let extents = Vector3::new(radius, radius, radius);
+        self.query(&Aabb3::from_center_extents(p, &extents), |q| (*q - *p).get_mag_sqr() <= radius_sqr)
+    }
+
+    pub fn query_aabb(&self, aabb: &Aabb3) -> Vec<usize> {
+        self.query(aabb, |q| aabb.contains_point(q))
+    }
+
+    fn query<F>(&self, aabb: &Aabb3, keep: F) -> Vec<usize>
+    where
+        F: Fn(&Vector3) -> bool,
+    {
+        let mut out = Vec::new();
+        let min = self.cell_of(&aabb.min);
+        let max = self.cell_of(&aabb.max);
+        let span = (0..3).fold(1i64, |acc, i| acc.saturating_mul(max[i] as i64 - min[i] as i64 + 1));
+        // A box over more cells than are occupied is cheaper to answer by
+        // scanning every occupied cell.
+        if span > self.cells.len() as i64 {
+            for (cell, entries) in &self.cells {
+                if (0..3).all(|i| cell[i] >= min[i] && cell[i] <= max[i]) {
+                    out.extend(entries.iter().filter(|e| keep(&e.1)).map(|e| e.0));
+                }
+            }
+            return out;
+        }
+        for x in min[0]..=max[0] {
+            for y in min[1]..=max[1] {
+                for z in min[2]..=max[2] {
+                    if let Some(entries) = self.cells.get(&[x, y, z]) {
+                        out.extend(entries.iter().filter(|e| keep(&e.1)).map(|e| e.0));
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    fn sorted(mut v: Vec<usize>) -> Vec<usize> {
+        v.sort_unstable();
+        v
+    }
+
+    fn check(hash: &SpatialHash, reference: &[Option<Vector3>], rng: &mut Rng) {
+        for _ in 0..20 {
+            let p = rng.vec3();
+            for radius in [0.5, 2.0, 7.0] {
+                let expected: Vec<usize> = (0..reference.len())
+                    .filter(|&i| reference[i].is_some_and(|q| (q - p).get_mag_sqr() <= radius * radius))
+                    .collect();
+                assert_eq!(sorted(hash.query_radius(&p, radius)), expected);
+            }
+            // Large enough that some boxes take the scan-every-cell path.
+            for size in [1.0, 30.0] {
+                let aabb = Aabb3::from_center_extents(&p, &Vector3::new(size, size * 0.5, size));
+                let expected: Vec<usize> =
+                    (0..reference.len()).filter(|&i| reference[i].is_some_and(|q| aabb.contains_point(&q))).collect();
+                assert_eq!(sorted(hash.query_aabb(&aabb)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn cell_of_floors_negative_coordinates() {
+        let hash = SpatialHash::new(2.0);
+        assert_eq!(hash.cell_of(&Vector3::new(0.0, 1.9, 2.0)), [0, 0, 1]);
+        assert_eq!(hash.cell_of(&Vector3::new(-0.1, -2.0, -2.1)), [-1, -1, -2]);
+    }
+
+    #[test]
+    fn insert_remove_update_match_brute_force() {
+        let mut rng = Rng(4);
+        for cell_size in [0.7, 3.0] {
+            let mut hash = SpatialHash::new(cell_size);
+            let mut reference: Vec<Option<Vector3>> = Vec::new();
+            for i in 0..300 {
+                let p = rng.vec3();
+                hash.insert(i, &p);
+                reference.push(Some(p));
+            }
+            check(&hash, &reference, &mut rng);
+
+            for i in (0..reference.len()).step_by(3) {
+                assert!(hash.remove(i, &reference[i].unwrap()));
+                assert!(!hash.remove(i, &reference[i].unwrap()));
+                reference[i] = None;
+            }
+            for i in (1..reference.len()).step_by(4) {
+                if let Some(from) = reference[i] {
+                    // Alternate small moves within a cell and jumps across the grid.
+                    let to = if i % 8 == 1 { from + Vector3::new(0.01, 0.0, 0.0) } else { rng.vec3() };
+                    hash.update(i, &from, &to);
+                    reference[i] = Some(to);
+                }
+            }
+            check(&hash, &reference, &mut rng);
+
+            hash.clear();
+            assert!(hash.is_empty());
+        }
+    }
+
+    #[test]
+    fn removing_the_last_item_frees_its_cell() {
+        let mut hash = SpatialHash::new(1.0);
+        let p = Vector3::new(0.5, 0.5, 0.5);
+        hash.insert(7, &p);
+        assert!(!hash.remove(3, &p));
+        assert!(!hash.remove(7, &Vector3::new(5.0, 0.0, 0.0)));
+        assert!(hash.remove(7, &p));
+        assert!(hash.is_empty());
+    }
+}
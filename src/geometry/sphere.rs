@@ -0,0 +1,185 @@
+use crate::{geometry::aabb::Aabb3, mat4::Mat4, quat::Quat, vector3::Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere {
+    pub center: Vector3,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub fn new(center: Vector3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    // Ritter's bounding sphere: roughly 5-20% larger than optimal, in two passes.
+    pub fn from_points(points: &[Vector3]) -> Self {
+        let Some(first) = points.first() else {
+            return Self::new(Vector3::zero(), 0.0);
+        };
+        let farthest_from = |p: &Vector3| {
+            *points
+                .iter()
+                .max_by(|a, b| (**a - *p).get_mag_sqr().total_cmp(&(**b - *p).get_mag_sqr()))
+                .unwrap()
+        };
+        let a = farthest_from(first);
+        let b = farthest_from(&a);
+        let mut out = Self::new((a + b) * 0.5, (b - a).get_mag() * 0.5);
+        for p in points {
+            out.expand_to_point(p);
+        }
+        out
+    }
+
+    pub fn from_aabb(aabb: &Aabb3) -> Self {
+        Self {
+            center: aabb.get_center(),
+            radius: aabb.get_extents().get_mag(),
+        }
+    }
+
+    pub fn expand_to_point(&mut self, p: &Vector3) {
+        let d = *p - self.center;
+        let dist = d.get_mag();
+        if dist > self.radius {
+            let new_radius = (self.radius + dist) * 0.5;
+            self.center += d * ((new_radius - self.radius) / dist);
+            self.radius = new_radius;
+        }
+    }
+
+    pub fn contains_point(&self, p: &Vector3) -> bool {
+        (*p - self.center).get_mag_sqr() <= self.radius * self.radius
+    }
+
+    pub fn contains_sphere(&self, rhs: &Sphere) -> bool {
+        (rhs.center - self.center).get_mag() + rhs.radius <= self.radius
+    }
+
+    pub fn merge(&mut self, rhs: &Sphere) {
+        *self = self.get_merge(rhs)
+    }
+
+    // Smallest sphere enclosing both.
+    pub fn get_merge(&self, rhs: &Sphere) -> Self {
+        let d = rhs.center - self.center;
+        let dist = d.get_mag();
+        if dist + rhs.radius <= self.radius {
+            return *self;
+        }
+        if dist + self.radius <= rhs.radius {
+            return *rhs;
+        }
+        let radius = (dist + self.radius + rhs.radius) * 0.5;
+        Self {
+            center: self.center + d * ((radius - self.radius) / dist),
+            radius,
+        }
+    }
+
+    pub fn transform(&mut self, m: &Mat4) {
+        *self = self.get_transform(m)
+    }
+
+    // The radius grows by the largest axis scale so the result still bounds
+    // the transformed sphere under non-uniform scale.
+    pub fn get_transform(&self, m: &Mat4) -> Self {
+        let scale_sqr = (0..3)
+            .map(|r| Vector3::from_vec4(&m.get_row(r)).get_mag_sqr())
+            .fold(0.0, f32::max);
+        Self {
+            center: m.transform_point(&self.center),
+            radius: self.radius * scale_sqr.sqrt(),
+        }
+    }
+
+    pub fn rotate(&mut self, q: &Quat) {
+        *self = self.get_rotate(q)
+    }
+
+    // Rotates about the origin.
+    pub fn get_rotate(&self, q: &Quat) -> Self {
+        Self {
+            center: q.lqcvq(&self.center),
+            radius: self.radius,
+        }
+    }
+
+    pub fn intersects_sphere(&self, rhs: &Sphere) -> bool {
+        let r = self.radius + rhs.radius;
+        (rhs.center - self.center).get_mag_sqr() <= r * r
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        (aabb.closest_point(&self.center) - self.center).get_mag_sqr() <= self.radius * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Deg;
+
+    fn v(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    #[test]
+    fn from_points_bounds_every_point() {
+        let points: Vec<Vector3> = (0..50)
+            .map(|i| {
+                let a = i as f32 * 0.7;
+                v(a.cos() * 3.0, a.sin() * 2.0, (a * 1.3).sin())
+            })
+            .collect();
+        let s = Sphere::from_points(&points);
+        assert!(points.iter().all(|p| (*p - s.center).get_mag() <= s.radius + 1e-4));
+        assert!(s.radius < 3.0 * 1.2);
+        assert_eq!(Sphere::from_points(&[]).radius, 0.0);
+        assert_eq!(Sphere::from_points(&[v(1.0, 2.0, 3.0)]), Sphere::new(v(1.0, 2.0, 3.0), 0.0));
+    }
+
+    #[test]
+    fn from_aabb_passes_through_the_corners() {
+        let s = Sphere::from_aabb(&Aabb3::new(v(-1.0, -2.0, -2.0), v(1.0, 2.0, 2.0)));
+        assert_eq!(s, Sphere::new(Vector3::zero(), 3.0));
+    }
+
+    #[test]
+    fn merge_is_the_smallest_enclosing_sphere() {
+        let a = Sphere::new(v(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(v(4.0, 0.0, 0.0), 1.0);
+        assert_eq!(a.get_merge(&b), Sphere::new(v(2.0, 0.0, 0.0), 3.0));
+        let inner = Sphere::new(v(0.5, 0.0, 0.0), 0.25);
+        assert_eq!(a.get_merge(&inner), a);
+        assert_eq!(inner.get_merge(&a), a);
+        assert!(a.get_merge(&b).contains_sphere(&a) && a.get_merge(&b).contains_sphere(&b));
+    }
+
+    #[test]
+    fn transform_scales_by_the_largest_axis() {
+        let m = Mat4::from([
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 3.0, 0.0, 0.0,
+            0.0, 0.0, 2.0, 0.0,
+            1.0, 1.0, 1.0, 1.0,
+        ]);
+        assert_eq!(Sphere::new(v(1.0, 0.0, 0.0), 2.0).get_transform(&m), Sphere::new(v(2.0, 1.0, 1.0), 6.0));
+        let q = Quat::from_axis_angle(&v(0.0, 0.0, 1.0), Deg(90.0));
+        let rotated = Sphere::new(v(1.0, 0.0, 0.0), 2.0).get_rotate(&q);
+        crate::assert_approx_eq!(rotated.center, v(0.0, 1.0, 0.0));
+        assert_eq!(rotated.radius, 2.0);
+    }
+
+    #[test]
+    fn overlaps() {
+        let s = Sphere::new(v(0.0, 0.0, 0.0), 1.0);
+        assert!(s.intersects_sphere(&Sphere::new(v(1.5, 0.0, 0.0), 0.5)));
+        assert!(!s.intersects_sphere(&Sphere::new(v(1.6, 0.0, 0.0), 0.5)));
+        assert!(s.intersects_aabb(&Aabb3::new(v(0.5, 0.5, 0.5), v(2.0, 2.0, 2.0))));
+        // The box's nearest corner is sqrt(3) * 0.7 away.
+        assert!(!s.intersects_aabb(&Aabb3::new(v(0.7, 0.7, 0.7), v(2.0, 2.0, 2.0))));
+        assert!(s.contains_point(&v(0.0, 1.0, 0.0)));
+        assert!(!s.contains_point(&v(0.0, 1.01, 0.0)));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{mat3::Mat3, mat4::Mat4, quat::Quat, vector2::Vector2, vector3::Vector3, vector4::Vector4};
+
+// glam matrices are column-major and multiply column vectors (M * v), so each
+// pmath row becomes a glam column and the transform is preserved.
+
+impl From<glam::Vec2> for Vector2 {
+    fn from(v: glam::Vec2) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector2> for glam::Vec2 {
+    fn from(v: Vector2) -> Self {
+        glam::Vec2::new(v.x, v.y)
+    }
+}
+
+impl From<glam::Vec3> for Vector3 {
+    fn from(v: glam::Vec3) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for glam::Vec3 {
+    fn from(v: Vector3) -> Self {
+        glam::Vec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<glam::Vec4> for Vector4 {
+    fn from(v: glam::Vec4) -> Self {
+        Vector4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Vector4> for glam::Vec4 {
+    fn from(v: Vector4) -> Self {
+        glam::Vec4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<glam::Quat> for Quat {
+    fn from(q: glam::Quat) -> Self {
+        Quat { x: q.x, y: q.y, z: q.z, real: q.w }
+    }
+}
+
+impl From<Quat> for glam::Quat {
+    fn from(q: Quat) -> Self {
+        glam::Quat::from_xyzw(q.x, q.y, q.z, q.real)
+    }
+}
+
+impl From<glam::Mat3> for Mat3 {
+    fn from(m: glam::Mat3) -> Self {
+        let mut out = Mat3::zero();
+        *out.as_mut_array() = m.to_cols_array();
+        out
+    }
+}
+
+impl From<Mat3> for glam::Mat3 {
+    fn from(m: Mat3) -> Self {
+        glam::Mat3::from_cols_array(m.as_array())
+    }
+}
+
+impl From<glam::Mat4> for Mat4 {
+    fn from(m: glam::Mat4) -> Self {
+        let mut out = Mat4::zero();
+        *out.as_mut_array() = m.to_cols_array();
+        out
+    }
+}
+
+impl From<Mat4> for glam::Mat4 {
+    fn from(m: Mat4) -> Self {
+        glam::Mat4::from_cols_array(m.as_array())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Deg, test_util::Rng};
+
+    // A general matrix, so a transposed conversion moves every off-diagonal term.
+    #[test]
+    fn matrices_transform_like_glam() {
+        let mut rng = Rng(32);
+        for _ in 0..20 {
+            let m = rng.mat4();
+            let v = rng.vec4();
+            let g = glam::Mat4::from(m);
+            crate::assert_approx_eq!(v * m, Vector4::from(g * glam::Vec4::from(v)), 1e-3);
+            assert_eq!(Mat4::from(g), m);
+
+            let m3 = Mat3::from(std::array::from_fn::<f32, 9, _>(|_| rng.next()));
+            let p = rng.vec3();
+            let g3 = glam::Mat3::from(m3);
+            crate::assert_approx_eq!(p * m3, Vector3::from(g3 * glam::Vec3::from(p)), 1e-3);
+            assert_eq!(Mat3::from(g3), m3);
+        }
+    }
+
+    #[test]
+    fn quats_rotate_like_glam() {
+        let mut rng = Rng(33);
+        for _ in 0..20 {
+            let q = Quat::from_axis_angle(&rng.vec3(), Deg(rng.next() * 18.0));
+            let p = rng.vec3();
+            crate::assert_approx_eq!(p * q, Vector3::from(glam::Quat::from(q) * glam::Vec3::from(p)), 1e-3);
+            assert_eq!(Quat::from(glam::Quat::from(q)), q);
+        }
+    }
+}
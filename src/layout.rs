@@ -0,0 +1,378 @@
+use crate::{mat3::Mat3, mat4::Mat4, quat::Quat, vector2::Vector2, vector3::Vector3, vector4::Vector4};
+
+// GLSL block layout rules (OpenGL 4.6 spec, section 7.6.2.2), written
+// little-endian as GPUs expect. Matrices are written with each pmath row as
+// one GLSL column, so `M * v` in a shader matches `v * M` here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutRules {
+    Std140,
+    Std430,
+}
+
+pub trait GpuLayout {
+    fn align(rules: LayoutRules) -> usize;
+    fn size(rules: LayoutRules) -> usize;
+    fn write(&self, w: &mut LayoutWriter);
+}
+
+pub trait AsStd140 {
+    fn as_std140(&self) -> Vec<u8>;
+}
+
+pub trait AsStd430 {
+    fn as_std430(&self) -> Vec<u8>;
+}
+
+impl<T: GpuLayout> AsStd140 for T {
+    fn as_std140(&self) -> Vec<u8> {
+        let mut w = LayoutWriter::new(LayoutRules::Std140);
+        w.write(self);
+        w.into_bytes()
+    }
+}
+
+impl<T: GpuLayout> AsStd430 for T {
+    fn as_std430(&self) -> Vec<u8> {
+        let mut w = LayoutWriter::new(LayoutRules::Std430);
+        w.write(self);
+        w.into_bytes()
+    }
+}
+
+pub fn round_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+// Alignment of a struct with members of the given alignments.
+pub fn struct_align(rules: LayoutRules, members: &[usize]) -> usize {
+    let align = members.iter().copied().max().unwrap_or(1);
+    match rules {
+        LayoutRules::Std140 => round_up(align, 16),
+        LayoutRules::Std430 => align,
+    }
+}
+
+// Size of a struct with members given as (align, size), including tail padding.
+pub fn struct_size(rules: LayoutRules, members: &[(usize, usize)]) -> usize {
+    let mut offset = 0;
+    for (align, size) in members {
+        offset = round_up(offset, *align) + size;
+    }
+    let aligns: Vec<usize> = members.iter().map(|m| m.0).collect();
+    round_up(offset, struct_align(rules, &aligns))
+}
+
+pub struct LayoutWriter {
+    rules: LayoutRules,
+    bytes: Vec<u8>,
+}
+
+impl LayoutWriter {
+    pub fn new(rules: LayoutRules) -> Self {
+        Self { rules, bytes: Vec::new() }
+    }
+
+    pub fn rules(&self) -> LayoutRules {
+        self.rules
+    }
+
+    pub fn offset(&self) -> usize {
+        self.bytes.len()
+    }
+
+    // Aligns to T, writes it, then pads out to T's full size.
+    pub fn write<T: GpuLayout>(&mut self, v: &T) {
+        self.pad_to(round_up(self.offset(), T::align(self.rules)));
+        let start = self.offset();
+        v.write(self);
+        self.pad_to(start + T::size(self.rules));
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn pad_to(&mut self, offset: usize) {
+        if offset > self.bytes.len() {
+            self.bytes.resize(offset, 0);
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+macro_rules! impl_scalar_layout {
+    ($($ty:ty),*) => {
+        $(
+            impl GpuLayout for $ty {
+                fn align(_rules: LayoutRules) -> usize {
+                    4
+                }
+
+                fn size(_rules: LayoutRules) -> usize {
+                    4
+                }
+
+                fn write(&self, w: &mut LayoutWriter) {
+                    w.write_bytes(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_layout!(f32, i32, u32);
+
+impl GpuLayout for Vector2 {
+    fn align(_rules: LayoutRules) -> usize {
+        8
+    }
+
+    fn size(_rules: LayoutRules) -> usize {
+        8
+    }
+
+    fn write(&self, w: &mut LayoutWriter) {
+        w.write(&self.x);
+        w.write(&self.y);
+    }
+}
+
+impl GpuLayout for Vector3 {
+    fn align(_rules: LayoutRules) -> usize {
+        16
+    }
+
+    fn size(_rules: LayoutRules) -> usize {
+        12
+    }
+
+    fn write(&self, w: &mut LayoutWriter) {
+        w.write(&self.x);
+        w.write(&self.y);
+        w.write(&self.z);
+    }
+}
+
+impl GpuLayout for Vector4 {
+    fn align(_rules: LayoutRules) -> usize {
+        16
+    }
+
+    fn size(_rules: LayoutRules) -> usize {
+        16
+    }
+
+    fn write(&self, w: &mut LayoutWriter) {
+        w.write(&self.x);
+        w.write(&self.y);
+        w.write(&self.z);
+        w.write(&self.w);
+    }
+}
+
+impl GpuLayout for Quat {
+    fn align(_rules: LayoutRules) -> usize {
+        16
+    }
+
+    fn size(_rules: LayoutRules) -> usize {
+        16
+    }
+
+    fn write(&self, w: &mut LayoutWriter) {
+        w.write(&self.x);
+        w.write(&self.y);
+        w.write(&self.z);
+        w.write(&self.real);
+    }
+}
+
+// A mat3 is laid out as an array of three vec3 columns, each padded to 16 bytes
+// under both rule sets.
+impl GpuLayout for Mat3 {
+    fn align(_rules: LayoutRules) -> usize {
+        16
+    }
+
+    fn size(_rules: LayoutRules) -> usize {
+        48
+    }
+
+    fn write(&self, w: &mut LayoutWriter) {
+        w.write(&Vector3::new(self.r0c0, self.r0c1, self.r0c2));
+        w.write(&Vector3::new(self.r1c0, self.r1c1, self.r1c2));
+        w.write(&Vector3::new(self.r2c0, self.r2c1, self.r2c2));
+    }
+}
+
+impl GpuLayout for Mat4 {
+    fn align(_rules: LayoutRules) -> usize {
+        16
+    }
+
+    fn size(_rules: LayoutRules) -> usize {
+        64
+    }
+
+    fn write(&self, w: &mut LayoutWriter) {
+        w.write(&Vector4::new(self.r0c0, self.r0c1, self.r0c2, self.r0c3));
+        w.write(&Vector4::new(self.r1c0, self.r1c1, self.r1c2, self.r1c3));
+        w.write(&Vector4::new(self.r2c0, self.r2c1, self.r2c2, self.r2c3));
+        w.write(&Vector4::new(self.r3c0, self.r3c1, self.r3c2, self.r3c3));
+    }
+}
+
+// Array elements are aligned to the element alignment, rounded up to a vec4
+// under std140, and every element occupies a full stride.
+impl<T: GpuLayout, const N: usize> GpuLayout for [T; N] {
+    fn align(rules: LayoutRules) -> usize {
+        match rules {
+            LayoutRules::Std140 => round_up(T::align(rules), 16),
+            LayoutRules::Std430 => T::align(rules),
+        }
+    }
+
+    fn size(rules: LayoutRules) -> usize {
+        round_up(T::size(rules), Self::align(rules)) * N
+    }
+
+    fn write(&self, w: &mut LayoutWriter) {
+        let stride = round_up(T::size(w.rules()), Self::align(w.rules()));
+        let start = w.offset();
+        for (i, v) in self.iter().enumerate() {
+            w.pad_to(start + i * stride);
+            w.write(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floats(bytes: &[u8]) -> Vec<f32> {
+        bytes.chunks(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect()
+    }
+
+    // struct Light { vec3 position; float intensity; }
+    struct Light {
+        position: Vector3,
+        intensity: f32,
+    }
+
+    impl GpuLayout for Light {
+        fn align(rules: LayoutRules) -> usize {
+            struct_align(rules, &[Vector3::align(rules), f32::align(rules)])
+        }
+
+        fn size(rules: LayoutRules) -> usize {
+            struct_size(rules, &[(Vector3::align(rules), Vector3::size(rules)), (f32::align(rules), f32::size(rules))])
+        }
+
+        fn write(&self, w: &mut LayoutWriter) {
+            w.write(&self.position);
+            w.write(&self.intensity);
+        }
+    }
+
+    // struct Inner { float v; };
+    // struct Outer { float a; Inner inner; float b; };
+    struct Inner(f32);
+    struct Outer(f32, Inner, f32);
+
+    impl GpuLayout for Inner {
+        fn align(rules: LayoutRules) -> usize {
+            struct_align(rules, &[f32::align(rules)])
+        }
+
+        fn size(rules: LayoutRules) -> usize {
+            struct_size(rules, &[(f32::align(rules), f32::size(rules))])
+        }
+
+        fn write(&self, w: &mut LayoutWriter) {
+            w.write(&self.0);
+        }
+    }
+
+    impl GpuLayout for Outer {
+        fn align(rules: LayoutRules) -> usize {
+            struct_align(rules, &[f32::align(rules), Inner::align(rules), f32::align(rules)])
+        }
+
+        fn size(rules: LayoutRules) -> usize {
+            struct_size(rules, &[
+                (f32::align(rules), f32::size(rules)),
+                (Inner::align(rules), Inner::size(rules)),
+                (f32::align(rules), f32::size(rules)),
+            ])
+        }
+
+        fn write(&self, w: &mut LayoutWriter) {
+            w.write(&self.0);
+            w.write(&self.1);
+            w.write(&self.2);
+        }
+    }
+
+    #[test]
+    fn scalars_are_little_endian() {
+        assert_eq!(1.0f32.as_std430(), vec![0x00, 0x00, 0x80, 0x3f]);
+        assert_eq!(0x0102_0304u32.as_std140(), vec![0x04, 0x03, 0x02, 0x01]);
+    }
+
+    #[test]
+    fn vec3_then_float_packs_into_16_bytes() {
+        let light = Light { position: Vector3::new(1.0, 2.0, 3.0), intensity: 4.0 };
+        for rules in [LayoutRules::Std140, LayoutRules::Std430] {
+            assert_eq!(Light::size(rules), 16);
+        }
+        assert_eq!(floats(&light.as_std140()), vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(floats(&light.as_std430()), vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn mat3_is_three_padded_columns() {
+        let m = Mat3::from([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        for bytes in [m.as_std140(), m.as_std430()] {
+            assert_eq!(bytes.len(), 48);
+            assert_eq!(floats(&bytes), vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0, 0.0, 7.0, 8.0, 9.0, 0.0]);
+        }
+        assert_eq!(Mat3::size(LayoutRules::Std140), 48);
+        assert_eq!(Mat3::size(LayoutRules::Std430), 48);
+    }
+
+    #[test]
+    fn float_array_stride() {
+        let a = [1.0f32, 2.0, 3.0];
+        let std140 = a.as_std140();
+        assert_eq!(std140.len(), 48);
+        assert_eq!(floats(&std140)[..], [1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0]);
+        assert_eq!(floats(&a.as_std430()), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn vec2_array_stride() {
+        let a = [Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)];
+        assert_eq!(<[Vector2; 2]>::size(LayoutRules::Std140), 32);
+        assert_eq!(<[Vector2; 2]>::size(LayoutRules::Std430), 16);
+        assert_eq!(floats(&a.as_std140()), vec![1.0, 2.0, 0.0, 0.0, 3.0, 4.0, 0.0, 0.0]);
+        assert_eq!(floats(&a.as_std430()), vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn nested_struct_rounds_to_16_in_std140() {
+        let outer = Outer(1.0, Inner(2.0), 3.0);
+        assert_eq!(Inner::align(LayoutRules::Std140), 16);
+        assert_eq!(Inner::size(LayoutRules::Std140), 16);
+        assert_eq!(Outer::size(LayoutRules::Std140), 48);
+        let std140 = floats(&outer.as_std140());
+        assert_eq!((std140[0], std140[4], std140[8]), (1.0, 2.0, 3.0));
+
+        assert_eq!(Inner::align(LayoutRules::Std430), 4);
+        assert_eq!(Outer::size(LayoutRules::Std430), 12);
+        assert_eq!(floats(&outer.as_std430()), vec![1.0, 2.0, 3.0]);
+    }
+}
@@ -1,7 +1,32 @@
 pub mod vector2;
 pub mod vector3;
 pub mod vector4;
+pub mod vector3_wide;
 pub mod mat3;
 pub mod mat4;
 pub mod quat;
-pub mod util;
\ No newline at end of file
+pub mod util;
+pub mod angle;
+pub mod geometry;
+pub mod layout;
+pub mod format;
+pub mod approx_eq;
+mod batch;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impl;
+#[cfg(feature = "mint")]
+mod mint_impl;
+#[cfg(feature = "glam")]
+mod glam_impl;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impl;
+#[cfg(feature = "cgmath")]
+mod cgmath_impl;
+#[cfg(feature = "approx")]
+mod approx_impl;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
+#[cfg(test)]
+mod test_util;
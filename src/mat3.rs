@@ -1,4 +1,12 @@
-#[derive(Clone, Copy)]
+use std::ops::{Index, IndexMut};
+use std::{fmt, str::FromStr};
+
+use crate::approx_eq::ApproxEq;
+use crate::format::{self, ParseError};
+use crate::vector3::Vector3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
 pub struct Mat3 {
     pub r0c0: f32,
     pub r0c1: f32,
@@ -13,6 +21,8 @@ pub struct Mat3 {
     pub r2c2: f32,
 }
 
+const _: () = assert!(std::mem::size_of::<Mat3>() == std::mem::size_of::<[f32; 9]>());
+
 impl Mat3 {
     pub fn zero() -> Self {
         Self {
@@ -45,4 +55,130 @@ impl Mat3 {
             r2c2: 1.0,
         }
     }
-}
\ No newline at end of file
+
+    pub fn get_row(&self, r: usize) -> Vector3 {
+        Vector3::new(self[(r, 0)], self[(r, 1)], self[(r, 2)])
+    }
+
+    pub fn set_row(&mut self, r: usize, v: &Vector3) {
+        self[(r, 0)] = v.x;
+        self[(r, 1)] = v.y;
+        self[(r, 2)] = v.z;
+    }
+
+    pub fn get_col(&self, c: usize) -> Vector3 {
+        Vector3::new(self[(0, c)], self[(1, c)], self[(2, c)])
+    }
+
+    pub fn set_col(&mut self, c: usize, v: &Vector3) {
+        self[(0, c)] = v.x;
+        self[(1, c)] = v.y;
+        self[(2, c)] = v.z;
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.as_array().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.as_mut_array().iter_mut()
+    }
+
+    pub fn is_equal(&self, rhs: &Mat3, epsilon: f32) -> bool {
+        self.abs_eq(rhs, epsilon)
+    }
+
+    pub fn as_array(&self) -> &[f32; 9] {
+        // SAFETY: Mat3 is repr(C) with nine f32 fields, so it has the size and
+        // alignment of [f32; 9] and holds the elements in row-major order.
+        unsafe { &*(self as *const Self as *const [f32; 9]) }
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 9] {
+        // SAFETY: as for as_array, and &mut self makes the borrow unique.
+        unsafe { &mut *(self as *mut Self as *mut [f32; 9]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
+    }
+}
+
+
+impl From<[f32; 9]> for Mat3 {
+    fn from(a: [f32; 9]) -> Self {
+        let mut m = Mat3::zero();
+        *m.as_mut_array() = a;
+        m
+    }
+}
+
+impl From<Mat3> for [f32; 9] {
+    fn from(m: Mat3) -> Self {
+        *m.as_array()
+    }
+}
+
+impl From<[[f32; 3]; 3]> for Mat3 {
+    fn from(rows: [[f32; 3]; 3]) -> Self {
+        let mut m = Mat3::zero();
+        for (r, row) in rows.iter().enumerate() {
+            m.set_row(r, &Vector3::from(*row));
+        }
+        m
+    }
+}
+
+impl From<Mat3> for [[f32; 3]; 3] {
+    fn from(m: Mat3) -> Self {
+        std::array::from_fn(|r| m.get_row(r).into())
+    }
+}
+
+impl Index<(usize, usize)> for Mat3 {
+    type Output = f32;
+    fn index(&self, (r, c): (usize, usize)) -> &f32 {
+        assert!(r < 3 && c < 3, "Mat3 index out of range");
+        &self.as_array()[r * 3 + c]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Mat3 {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut f32 {
+        assert!(r < 3 && c < 3, "Mat3 index out of range");
+        &mut self.as_mut_array()[r * 3 + c]
+    }
+}
+
+impl fmt::Display for Mat3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::fmt_rows(f, self.as_slice(), 3)
+    }
+}
+
+impl FromStr for Mat3 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        format::parse_components::<9>(s).map(Mat3::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_views_are_row_major() {
+        let mut m = Mat3::from(std::array::from_fn::<f32, 9, _>(|i| i as f32));
+        assert_eq!((m.r0c2, m.r1c0, m.r2c1), (2.0, 3.0, 7.0));
+        assert_eq!(m.as_array(), &std::array::from_fn::<f32, 9, _>(|i| i as f32));
+        m.as_mut_array()[5] = -1.0;
+        m.as_mut_slice()[6] = -2.0;
+        assert_eq!((m.r1c2, m.r2c0), (-1.0, -2.0));
+        assert_eq!(m.as_slice().len(), 9);
+    }
+}
@@ -1,14 +1,19 @@
-use std::ops::Mul;
+use std::ops::{Index, IndexMut, Mul, MulAssign};
+use std::{fmt, str::FromStr};
 
-use crate::quat::Quat;
+use crate::{angle::Rad, approx_eq::ApproxEq, batch, format::{self, ParseError}, geometry::plane::Plane, quat::Quat, vector3::Vector3, vector3_wide::Vector3x8, vector4::Vector4};
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use crate::simd;
 
-enum Axis {
+pub enum Axis {
     X,
     Y,
     Z,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "simd", repr(align(16)))]
 pub struct Mat4 {
     pub r0c0: f32,
     pub r0c1: f32,
@@ -31,6 +36,8 @@ pub struct Mat4 {
     pub r3c3: f32,
 }
 
+const _: () = assert!(std::mem::size_of::<Mat4>() == std::mem::size_of::<[f32; 16]>());
+
 impl Mat4 {
 
     pub fn from_quat(q: &Quat) -> Self {
@@ -42,10 +49,10 @@ impl Mat4 {
         let y_z = q.y * q.z;
         let y_w = q.y * q.real;
         let z_z = q.z * q.z;
-        let z_w = q.z * q.z;
+        let z_w = q.z * q.real;
 
         Self {
-            r0c0: 1.0 - (2.0 * y_y + z_z),
+            r0c0: 1.0 - (2.0 * (y_y + z_z)),
             r0c1: 2.0 * (x_y + z_w),
             r0c2: 2.0 * (x_z - y_w),
             r0c3: 0.0,
@@ -67,6 +74,40 @@ impl Mat4 {
         }
     }
 
+    pub fn from_axis_angle(axis: &Vector3, angle: impl Into<Rad>) -> Self {
+        Self::from_quat(&Quat::from_axis_angle(axis, angle))
+    }
+
+    pub fn from_rotation(axis: Axis, angle: impl Into<Rad>) -> Self {
+        Self::from_quat(&Quat::from_rotation(axis, angle))
+    }
+
+    pub fn from_reflection(plane: &Plane) -> Self {
+        let p = plane.get_norm();
+        let n = p.normal;
+        Self {
+            r0c0: 1.0 - 2.0 * n.x * n.x,
+            r0c1: -2.0 * n.x * n.y,
+            r0c2: -2.0 * n.x * n.z,
+            r0c3: 0.0,
+
+            r1c0: -2.0 * n.y * n.x,
+            r1c1: 1.0 - 2.0 * n.y * n.y,
+            r1c2: -2.0 * n.y * n.z,
+            r1c3: 0.0,
+
+            r2c0: -2.0 * n.z * n.x,
+            r2c1: -2.0 * n.z * n.y,
+            r2c2: 1.0 - 2.0 * n.z * n.z,
+            r2c3: 0.0,
+
+            r3c0: -2.0 * p.d * n.x,
+            r3c1: -2.0 * p.d * n.y,
+            r3c2: -2.0 * p.d * n.z,
+            r3c3: 1.0,
+        }
+    }
+
     pub fn zero() -> Self {
         Self {
             r0c0: 0.0,
@@ -135,45 +176,68 @@ impl Mat4 {
     }
 
     pub fn t(&mut self) {
-        let mut tmp = self.r0c1;
-        self.r0c1 = self.r1c0;
-        self.r1c0 = tmp;
-
-        tmp = self.r0c2;
-        self.r0c2 = self.r2c0;
-        self.r2c0 = tmp;
-
-        tmp = self.r0c3;
-        self.r0c3 = self.r3c0;
-        self.r3c0 = tmp;
-
-        tmp = self.r1c2;
-        self.r1c2 = self.r2c1;
-        self.r2c1 = tmp;
-
-        tmp = self.r1c3;
-        self.r1c3 = self.r3c1;
-        self.r3c1 = tmp;
-
-        tmp = self.r2c3;
-        self.r2c3 = self.r3c2;
-        self.r3c2 = tmp;
+        std::mem::swap(&mut self.r0c1, &mut self.r1c0);
+        std::mem::swap(&mut self.r0c2, &mut self.r2c0);
+        std::mem::swap(&mut self.r0c3, &mut self.r3c0);
+        std::mem::swap(&mut self.r1c2, &mut self.r2c1);
+        std::mem::swap(&mut self.r1c3, &mut self.r3c1);
+        std::mem::swap(&mut self.r2c3, &mut self.r3c2);
     }
 
     pub fn get_t(&self) -> Self {
-        Self {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::mat4_t(self);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return Self {
             r0c0: self.r0c0, r0c1: self.r1c0, r0c2: self.r2c0, r0c3: self.r3c0,
             r1c0: self.r0c1, r1c1: self.r1c1, r1c2: self.r2c1, r1c3: self.r3c1,
             r2c0: self.r0c2, r2c1: self.r1c2, r2c2: self.r2c2, r2c3: self.r3c2,
             r3c0: self.r0c3, r3c1: self.r1c3, r3c2: self.r2c3, r3c3: self.r3c3,
+        };
+    }
+
+    pub fn transform_point(&self, p: &Vector3) -> Vector3 {
+        Vector3::from_vec4(&(*p * *self))
+    }
+
+    pub fn transform_vector(&self, v: &Vector3) -> Vector3 {
+        Vector3 {
+            x: v.x * self.r0c0 + v.y * self.r1c0 + v.z * self.r2c0,
+            y: v.x * self.r0c1 + v.y * self.r1c1 + v.z * self.r2c1,
+            z: v.x * self.r0c2 + v.y * self.r1c2 + v.z * self.r2c2,
         }
     }
 
+    pub fn transform_points(&self, src: &[Vector3], dst: &mut [Vector3]) {
+        let m = *self;
+        batch::map_slice(src, dst, |src, dst| {
+            for (s, d) in src.chunks(Vector3x8::LANES).zip(dst.chunks_mut(Vector3x8::LANES)) {
+                (Vector3x8::gather(s) * m).scatter(d);
+            }
+        });
+    }
+
+    pub fn transform_vectors(&self, src: &[Vector3], dst: &mut [Vector3]) {
+        let mut m = *self;
+        m.r3c0 = 0.0;
+        m.r3c1 = 0.0;
+        m.r3c2 = 0.0;
+        m.transform_points(src, dst);
+    }
+
     pub fn inv(&mut self) {
-        todo!()
+        *self = self.get_inv()
     }
 
     pub fn get_inv(&self) -> Self {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::mat4_inv(self);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return self.get_inv_scalar();
+    }
+
+    #[cfg_attr(all(feature = "simd", target_arch = "x86_64"), allow(dead_code))]
+    pub(crate) fn get_inv_scalar(&self) -> Self {
         let m = Mat4{
             r0c0: (self.r1c1 * self.r2c2 * self.r3c3) + (self.r1c2 * self.r2c3 * self.r3c1) + (self.r1c3 * self.r2c1 * self.r3c2) - (self.r1c1 * self.r2c3 * self.r3c2) - (self.r1c2 * self.r2c1 * self.r3c3) - (self.r1c3 * self.r2c2 * self.r3c1),
             r0c1: (self.r0c1 * self.r2c3 * self.r3c2) + (self.r0c2 * self.r2c1 * self.r3c3) + (self.r0c3 * self.r2c2 * self.r3c1) - (self.r0c1 * self.r2c2 * self.r3c3) - (self.r0c2 * self.r2c3 * self.r3c1) - (self.r0c3 * self.r2c1 * self.r3c2),
@@ -194,6 +258,60 @@ impl Mat4 {
         };
 		(1.0 / self.get_det()) * m
     }
+
+    pub fn get_row(&self, r: usize) -> Vector4 {
+        Vector4::new(self[(r, 0)], self[(r, 1)], self[(r, 2)], self[(r, 3)])
+    }
+
+    pub fn set_row(&mut self, r: usize, v: &Vector4) {
+        self[(r, 0)] = v.x;
+        self[(r, 1)] = v.y;
+        self[(r, 2)] = v.z;
+        self[(r, 3)] = v.w;
+    }
+
+    pub fn get_col(&self, c: usize) -> Vector4 {
+        Vector4::new(self[(0, c)], self[(1, c)], self[(2, c)], self[(3, c)])
+    }
+
+    pub fn set_col(&mut self, c: usize, v: &Vector4) {
+        self[(0, c)] = v.x;
+        self[(1, c)] = v.y;
+        self[(2, c)] = v.z;
+        self[(3, c)] = v.w;
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.as_array().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.as_mut_array().iter_mut()
+    }
+
+    pub fn is_equal(&self, rhs: &Mat4, epsilon: f32) -> bool {
+        self.abs_eq(rhs, epsilon)
+    }
+
+    pub fn as_array(&self) -> &[f32; 16] {
+        // SAFETY: Mat4 is repr(C) with sixteen f32 fields and no padding, so it holds
+        // the elements of a [f32; 16] in row-major order. The simd feature only
+        // raises the alignment to 16, which still satisfies the array's.
+        unsafe { &*(self as *const Self as *const [f32; 16]) }
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 16] {
+        // SAFETY: as for as_array, and &mut self makes the borrow unique.
+        unsafe { &mut *(self as *mut Self as *mut [f32; 16]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
+    }
 }
 
 impl Mul<Mat4> for f32 {
@@ -221,4 +339,151 @@ impl Mul<Mat4> for f32 {
             r3c3: self * rhs.r3c3,
         }
     }
-}
\ No newline at end of file
+}
+
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+    fn mul(self, rhs: Mat4) -> Mat4 {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::mat4_mul(&self, &rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return Mat4 {
+            r0c0: self.r0c0 * rhs.r0c0 + self.r0c1 * rhs.r1c0 + self.r0c2 * rhs.r2c0 + self.r0c3 * rhs.r3c0,
+            r0c1: self.r0c0 * rhs.r0c1 + self.r0c1 * rhs.r1c1 + self.r0c2 * rhs.r2c1 + self.r0c3 * rhs.r3c1,
+            r0c2: self.r0c0 * rhs.r0c2 + self.r0c1 * rhs.r1c2 + self.r0c2 * rhs.r2c2 + self.r0c3 * rhs.r3c2,
+            r0c3: self.r0c0 * rhs.r0c3 + self.r0c1 * rhs.r1c3 + self.r0c2 * rhs.r2c3 + self.r0c3 * rhs.r3c3,
+
+            r1c0: self.r1c0 * rhs.r0c0 + self.r1c1 * rhs.r1c0 + self.r1c2 * rhs.r2c0 + self.r1c3 * rhs.r3c0,
+            r1c1: self.r1c0 * rhs.r0c1 + self.r1c1 * rhs.r1c1 + self.r1c2 * rhs.r2c1 + self.r1c3 * rhs.r3c1,
+            r1c2: self.r1c0 * rhs.r0c2 + self.r1c1 * rhs.r1c2 + self.r1c2 * rhs.r2c2 + self.r1c3 * rhs.r3c2,
+            r1c3: self.r1c0 * rhs.r0c3 + self.r1c1 * rhs.r1c3 + self.r1c2 * rhs.r2c3 + self.r1c3 * rhs.r3c3,
+
+            r2c0: self.r2c0 * rhs.r0c0 + self.r2c1 * rhs.r1c0 + self.r2c2 * rhs.r2c0 + self.r2c3 * rhs.r3c0,
+            r2c1: self.r2c0 * rhs.r0c1 + self.r2c1 * rhs.r1c1 + self.r2c2 * rhs.r2c1 + self.r2c3 * rhs.r3c1,
+            r2c2: self.r2c0 * rhs.r0c2 + self.r2c1 * rhs.r1c2 + self.r2c2 * rhs.r2c2 + self.r2c3 * rhs.r3c2,
+            r2c3: self.r2c0 * rhs.r0c3 + self.r2c1 * rhs.r1c3 + self.r2c2 * rhs.r2c3 + self.r2c3 * rhs.r3c3,
+
+            r3c0: self.r3c0 * rhs.r0c0 + self.r3c1 * rhs.r1c0 + self.r3c2 * rhs.r2c0 + self.r3c3 * rhs.r3c0,
+            r3c1: self.r3c0 * rhs.r0c1 + self.r3c1 * rhs.r1c1 + self.r3c2 * rhs.r2c1 + self.r3c3 * rhs.r3c1,
+            r3c2: self.r3c0 * rhs.r0c2 + self.r3c1 * rhs.r1c2 + self.r3c2 * rhs.r2c2 + self.r3c3 * rhs.r3c2,
+            r3c3: self.r3c0 * rhs.r0c3 + self.r3c1 * rhs.r1c3 + self.r3c2 * rhs.r2c3 + self.r3c3 * rhs.r3c3,
+        };
+    }
+}
+
+impl MulAssign<Mat4> for Mat4 {
+    fn mul_assign(&mut self, rhs: Mat4) {
+        *self = *self * rhs
+    }
+}
+
+
+impl From<[f32; 16]> for Mat4 {
+    fn from(a: [f32; 16]) -> Self {
+        let mut m = Mat4::zero();
+        *m.as_mut_array() = a;
+        m
+    }
+}
+
+impl From<Mat4> for [f32; 16] {
+    fn from(m: Mat4) -> Self {
+        *m.as_array()
+    }
+}
+
+impl From<[[f32; 4]; 4]> for Mat4 {
+    fn from(rows: [[f32; 4]; 4]) -> Self {
+        let mut m = Mat4::zero();
+        for (r, row) in rows.iter().enumerate() {
+            m.set_row(r, &Vector4::from(*row));
+        }
+        m
+    }
+}
+
+impl From<Mat4> for [[f32; 4]; 4] {
+    fn from(m: Mat4) -> Self {
+        std::array::from_fn(|r| m.get_row(r).into())
+    }
+}
+
+impl Index<(usize, usize)> for Mat4 {
+    type Output = f32;
+    fn index(&self, (r, c): (usize, usize)) -> &f32 {
+        assert!(r < 4 && c < 4, "Mat4 index out of range");
+        &self.as_array()[r * 4 + c]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Mat4 {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut f32 {
+        assert!(r < 4 && c < 4, "Mat4 index out of range");
+        &mut self.as_mut_array()[r * 4 + c]
+    }
+}
+
+impl fmt::Display for Mat4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::fmt_rows(f, self.as_slice(), 4)
+    }
+}
+
+impl FromStr for Mat4 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        format::parse_components::<16>(s).map(Mat4::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Deg;
+
+    const X: Vector3 = Vector3 { x: 1.0, y: 0.0, z: 0.0 };
+    const Y: Vector3 = Vector3 { x: 0.0, y: 1.0, z: 0.0 };
+    const Z: Vector3 = Vector3 { x: 0.0, y: 0.0, z: 1.0 };
+
+    #[test]
+    fn array_views_are_row_major() {
+        let mut m = Mat4::from(std::array::from_fn::<f32, 16, _>(|i| i as f32));
+        assert_eq!((m.r0c3, m.r1c0, m.r3c2), (3.0, 4.0, 14.0));
+        assert_eq!(m.as_array(), &std::array::from_fn::<f32, 16, _>(|i| i as f32));
+        m.as_mut_array()[7] = -1.0;
+        m.as_mut_slice()[12] = -2.0;
+        assert_eq!((m.r1c3, m.r3c0), (-1.0, -2.0));
+        assert_eq!(m.as_slice().len(), 16);
+    }
+
+    #[test]
+    fn quarter_turn_about_z_takes_x_to_y() {
+        let m = Mat4::from_axis_angle(&Z, Deg(90.0));
+        let q = Quat::from_axis_angle(&Z, Deg(90.0));
+        crate::assert_approx_eq!(m.transform_point(&X), Y);
+        crate::assert_approx_eq!(m.transform_vector(&X), Y);
+        crate::assert_approx_eq!(X * q, Y);
+        crate::assert_approx_eq!(m, Mat4::from_quat(&q));
+    }
+
+    #[test]
+    fn from_quat_matches_lqcvq() {
+        let q = Quat::from_axis_angle(&Vector3::new(1.0, -2.0, 0.5), Deg(37.0));
+        let m = Mat4::from_quat(&q);
+        for v in [X, Y, Z, Vector3::new(3.0, -1.0, 2.0)] {
+            crate::assert_approx_eq!(m.transform_vector(&v), q.lqcvq(&v));
+        }
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn axis_angle_matches_glam() {
+        let m = Mat4::from_axis_angle(&Z, Deg(90.0));
+        let g = glam::Mat4::from_axis_angle(glam::Vec3::Z, 90f32.to_radians());
+        crate::assert_approx_eq!(m, Mat4::from(g));
+        let q = Quat::from_axis_angle(&Z, Deg(90.0));
+        crate::assert_approx_eq!(q, Quat::from(glam::Quat::from_axis_angle(glam::Vec3::Z, 90f32.to_radians())));
+        let gy = g.transform_point3(glam::Vec3::X);
+        crate::assert_approx_eq!(Vector3::from(gy), Y);
+    }
+}
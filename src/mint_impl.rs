@@ -0,0 +1,120 @@
+use crate::{mat3::Mat3, mat4::Mat4, quat::Quat, vector2::Vector2, vector3::Vector3, vector4::Vector4};
+
+// pmath multiplies row vectors on the left (v * M) while mint follows the
+// column-vector convention (M * v), so each pmath row becomes a mint column.
+
+impl From<mint::Vector2<f32>> for Vector2 {
+    fn from(v: mint::Vector2<f32>) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector2> for mint::Vector2<f32> {
+    fn from(v: Vector2) -> Self {
+        mint::Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<mint::Vector3<f32>> for Vector3 {
+    fn from(v: mint::Vector3<f32>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for mint::Vector3<f32> {
+    fn from(v: Vector3) -> Self {
+        mint::Vector3 { x: v.x, y: v.y, z: v.z }
+    }
+}
+
+impl From<mint::Vector4<f32>> for Vector4 {
+    fn from(v: mint::Vector4<f32>) -> Self {
+        Vector4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Vector4> for mint::Vector4<f32> {
+    fn from(v: Vector4) -> Self {
+        mint::Vector4 { x: v.x, y: v.y, z: v.z, w: v.w }
+    }
+}
+
+impl From<mint::Quaternion<f32>> for Quat {
+    fn from(q: mint::Quaternion<f32>) -> Self {
+        Quat { x: q.v.x, y: q.v.y, z: q.v.z, real: q.s }
+    }
+}
+
+impl From<Quat> for mint::Quaternion<f32> {
+    fn from(q: Quat) -> Self {
+        mint::Quaternion { v: q.get_v().into(), s: q.real }
+    }
+}
+
+impl From<mint::ColumnMatrix3<f32>> for Mat3 {
+    fn from(m: mint::ColumnMatrix3<f32>) -> Self {
+        let mut out = Mat3::zero();
+        *out.as_mut_array() = m.into();
+        out
+    }
+}
+
+impl From<Mat3> for mint::ColumnMatrix3<f32> {
+    fn from(m: Mat3) -> Self {
+        mint::ColumnMatrix3::from(*m.as_array())
+    }
+}
+
+impl From<mint::ColumnMatrix4<f32>> for Mat4 {
+    fn from(m: mint::ColumnMatrix4<f32>) -> Self {
+        let mut out = Mat4::zero();
+        *out.as_mut_array() = m.into();
+        out
+    }
+}
+
+impl From<Mat4> for mint::ColumnMatrix4<f32> {
+    fn from(m: Mat4) -> Self {
+        mint::ColumnMatrix4::from(*m.as_array())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    // mint has no arithmetic, so the column-vector product is spelled out:
+    // M * v is the sum of each column scaled by the matching component of v.
+    #[test]
+    fn matrices_transform_as_column_matrices() {
+        let mut rng = Rng(38);
+        for _ in 0..20 {
+            let m = rng.mat4();
+            let v = rng.vec4();
+            let c = mint::ColumnMatrix4::from(m);
+            let cols = [c.x, c.y, c.z, c.w].map(Vector4::from);
+            let product = cols[0] * v.x + cols[1] * v.y + cols[2] * v.z + cols[3] * v.w;
+            crate::assert_approx_eq!(v * m, product, 1e-3);
+            assert_eq!(Mat4::from(c), m);
+
+            let m3 = Mat3::from(std::array::from_fn::<f32, 9, _>(|_| rng.next()));
+            let p = rng.vec3();
+            let c3 = mint::ColumnMatrix3::from(m3);
+            let cols = [c3.x, c3.y, c3.z].map(Vector3::from);
+            crate::assert_approx_eq!(p * m3, cols[0] * p.x + cols[1] * p.y + cols[2] * p.z, 1e-3);
+            assert_eq!(Mat3::from(c3), m3);
+        }
+    }
+
+    #[test]
+    fn vectors_and_quats_round_trip() {
+        let mut rng = Rng(39);
+        let q = rng.quat();
+        let mq = mint::Quaternion::from(q);
+        assert_eq!((mq.v.x, mq.v.y, mq.v.z, mq.s), (q.x, q.y, q.z, q.real));
+        assert_eq!(Quat::from(mq), q);
+        let v = rng.vec4();
+        assert_eq!(Vector4::from(mint::Vector4::from(v)).as_array(), v.as_array());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{mat3::Mat3, mat4::Mat4, quat::Quat, vector2::Vector2, vector3::Vector3, vector4::Vector4};
+
+// nalgebra stores matrices column-major and multiplies column vectors
+// (M * v), so each pmath row becomes a nalgebra column.
+
+impl From<nalgebra::Vector2<f32>> for Vector2 {
+    fn from(v: nalgebra::Vector2<f32>) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector2> for nalgebra::Vector2<f32> {
+    fn from(v: Vector2) -> Self {
+        nalgebra::Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<nalgebra::Vector3<f32>> for Vector3 {
+    fn from(v: nalgebra::Vector3<f32>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for nalgebra::Vector3<f32> {
+    fn from(v: Vector3) -> Self {
+        nalgebra::Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<nalgebra::Vector4<f32>> for Vector4 {
+    fn from(v: nalgebra::Vector4<f32>) -> Self {
+        Vector4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Vector4> for nalgebra::Vector4<f32> {
+    fn from(v: Vector4) -> Self {
+        nalgebra::Vector4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<nalgebra::Quaternion<f32>> for Quat {
+    fn from(q: nalgebra::Quaternion<f32>) -> Self {
+        Quat { x: q.i, y: q.j, z: q.k, real: q.w }
+    }
+}
+
+impl From<Quat> for nalgebra::Quaternion<f32> {
+    fn from(q: Quat) -> Self {
+        nalgebra::Quaternion::new(q.real, q.x, q.y, q.z)
+    }
+}
+
+impl From<nalgebra::UnitQuaternion<f32>> for Quat {
+    fn from(q: nalgebra::UnitQuaternion<f32>) -> Self {
+        q.into_inner().into()
+    }
+}
+
+impl From<Quat> for nalgebra::UnitQuaternion<f32> {
+    fn from(q: Quat) -> Self {
+        nalgebra::UnitQuaternion::new_normalize(q.into())
+    }
+}
+
+impl From<nalgebra::Matrix3<f32>> for Mat3 {
+    fn from(m: nalgebra::Matrix3<f32>) -> Self {
+        let mut out = Mat3::zero();
+        out.as_mut_array().copy_from_slice(m.as_slice());
+        out
+    }
+}
+
+impl From<Mat3> for nalgebra::Matrix3<f32> {
+    fn from(m: Mat3) -> Self {
+        nalgebra::Matrix3::from_column_slice(m.as_slice())
+    }
+}
+
+impl From<nalgebra::Matrix4<f32>> for Mat4 {
+    fn from(m: nalgebra::Matrix4<f32>) -> Self {
+        let mut out = Mat4::zero();
+        out.as_mut_array().copy_from_slice(m.as_slice());
+        out
+    }
+}
+
+impl From<Mat4> for nalgebra::Matrix4<f32> {
+    fn from(m: Mat4) -> Self {
+        nalgebra::Matrix4::from_column_slice(m.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle::Deg, test_util::Rng};
+
+    // A general matrix, so a transposed conversion moves every off-diagonal term.
+    #[test]
+    fn matrices_transform_like_nalgebra() {
+        let mut rng = Rng(34);
+        for _ in 0..20 {
+            let m = rng.mat4();
+            let v = rng.vec4();
+            let n = nalgebra::Matrix4::from(m);
+            crate::assert_approx_eq!(v * m, Vector4::from(n * nalgebra::Vector4::from(v)), 1e-3);
+            assert_eq!(Mat4::from(n), m);
+
+            let m3 = Mat3::from(std::array::from_fn::<f32, 9, _>(|_| rng.next()));
+            let p = rng.vec3();
+            let n3 = nalgebra::Matrix3::from(m3);
+            crate::assert_approx_eq!(p * m3, Vector3::from(n3 * nalgebra::Vector3::from(p)), 1e-3);
+            assert_eq!(Mat3::from(n3), m3);
+        }
+    }
+
+    #[test]
+    fn quats_rotate_like_nalgebra() {
+        let mut rng = Rng(35);
+        for _ in 0..20 {
+            let q = Quat::from_axis_angle(&rng.vec3(), Deg(rng.next() * 18.0));
+            let p = rng.vec3();
+            let n = nalgebra::UnitQuaternion::from(q);
+            crate::assert_approx_eq!(p * q, Vector3::from(n * nalgebra::Vector3::from(p)), 1e-3);
+            assert_eq!(Quat::from(nalgebra::Quaternion::from(q)), q);
+        }
+    }
+}
@@ -1,19 +1,21 @@
-use std::ops::{Div, DivAssign};
+use std::ops::{Div, DivAssign, Index, IndexMut, Mul, MulAssign};
+use std::{fmt, str::FromStr};
 
-use crate::{mat4::Mat4, vector3::Vector3};
+use crate::{angle::Rad, approx_eq::ApproxEq, batch, format::{self, ParseError}, mat4::Mat4, vector3::Vector3, vector4::Vector4};
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use crate::simd;
 
-enum Axis {
-    X,
-    Y,
-    Z,
-}
+pub use crate::mat4::Axis;
 
+#[allow(dead_code)]
 enum Orient {
     LocalToWorld,
     WorldToLocal,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "simd", repr(align(16)))]
 pub struct Quat {
     pub x: f32,
     pub y: f32,
@@ -21,6 +23,10 @@ pub struct Quat {
     pub real: f32
 }
 
+const _: () = assert!(std::mem::size_of::<Quat>() == std::mem::size_of::<[f32; 4]>());
+
+// Some private helpers here are not called from the rest of the crate yet.
+#[allow(dead_code)]
 impl Quat {
 
     pub fn zero() -> Self {
@@ -41,6 +47,36 @@ impl Quat {
         }
     }
 
+    pub fn from_axis_angle(axis: &Vector3, angle: impl Into<Rad>) -> Self {
+        let (s, c) = (angle.into() * 0.5).sin_cos();
+        let v = axis.get_norm() * s;
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            real: c,
+        }
+    }
+
+    pub fn from_rotation(axis: Axis, angle: impl Into<Rad>) -> Self {
+        let axis = match axis {
+            Axis::X => Vector3::new(1.0, 0.0, 0.0),
+            Axis::Y => Vector3::new(0.0, 1.0, 0.0),
+            Axis::Z => Vector3::new(0.0, 0.0, 1.0),
+        };
+        Self::from_axis_angle(&axis, angle)
+    }
+
+    // Rotation taking the unit x, y and z axes onto the given orthonormal,
+    // right-handed axes.
+    pub fn from_axes(x: &Vector3, y: &Vector3, z: &Vector3) -> Self {
+        let mut m = Mat4::identity();
+        m.set_row(0, &Vector4::new(x.x, x.y, x.z, 0.0));
+        m.set_row(1, &Vector4::new(y.x, y.y, y.z, 0.0));
+        m.set_row(2, &Vector4::new(z.x, z.y, z.z, 0.0));
+        Self::from_mat4(&m)
+    }
+
     pub fn from_mat4(m: &Mat4) -> Self {
         let t = m.r0c0 + m.r1c1 + m.r2c2;
         if t > 0.0 {
@@ -93,6 +129,15 @@ impl Quat {
         v_in + 2.0 * qv.dot(v_in) * qv
     }
 
+    pub fn rotate_slice(&self, src: &[Vector3], dst: &mut [Vector3]) {
+        let q = *self;
+        batch::map_slice(src, dst, |src, dst| {
+            for (s, d) in src.iter().zip(dst.iter_mut()) {
+                *d = q.lqcvq(s);
+            }
+        });
+    }
+
     fn lqvqc(&self, v_in: &Vector3) -> Vector3 {
         let qv = self.get_v();
         2.0 * self.real * v_in.cross(&qv) +
@@ -106,23 +151,23 @@ impl Quat {
         self.z = -self.z;
     }
 
-    fn get_conj(&self) -> Self {
+    pub(crate) fn get_conj(&self) -> Self {
         Self {
             x: -self.x,
             y: -self.y,
             z: -self.z,
-            real: 1.0,
+            real: self.real,
         }
     }
 
     fn t(&mut self) {
-        let mut m = Mat4::from_quat(&self);
+        let mut m = Mat4::from_quat(self);
         m.t();
         *self = Quat::from_mat4(&m);
     }
 
     fn get_t(&self) {
-        let mut m = Mat4::from_quat(&self);
+        let mut m = Mat4::from_quat(self);
         m.t();
         Quat::from_mat4(&m);
     }
@@ -173,10 +218,13 @@ impl Quat {
     }
 
     fn dot(&self, rhs: &Quat) -> f32 {
-        self.x * rhs.x +
-        self.y * rhs.y +
-        self.z * rhs.z +
-        self.real * rhs.real
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::quat_dot(self, rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return self.x * rhs.x +
+            self.y * rhs.y +
+            self.z * rhs.z +
+            self.real * rhs.real;
     }
 
     fn get_angle(&self) -> f32 {
@@ -192,6 +240,38 @@ impl Quat {
         v.norm();
         v
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.as_array().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.as_mut_array().iter_mut()
+    }
+
+    pub fn is_equal(&self, rhs: &Quat, epsilon: f32) -> bool {
+        self.abs_eq(rhs, epsilon)
+    }
+
+    pub fn as_array(&self) -> &[f32; 4] {
+        // SAFETY: Quat is repr(C) with four f32 fields and no padding, so x, y, z,
+        // real are the elements of a [f32; 4] in order. The simd feature only
+        // raises the alignment to 16, which still satisfies the array's.
+        unsafe { &*(self as *const Self as *const [f32; 4]) }
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 4] {
+        // SAFETY: as for as_array, and &mut self makes the borrow unique.
+        unsafe { &mut *(self as *mut Self as *mut [f32; 4]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
+    }
 }
 
 impl Div<f32> for Quat {
@@ -213,4 +293,91 @@ impl DivAssign<f32> for Quat {
         self.z /= rhs;
         self.real /= rhs;
     }
-}
\ No newline at end of file
+}
+
+impl Mul<Quat> for Quat {
+    type Output = Self;
+    fn mul(self, rhs: Quat) -> Self {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::quat_mul(&self, &rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return Self {
+            x: self.real * rhs.x + self.x * rhs.real + self.y * rhs.z - self.z * rhs.y,
+            y: self.real * rhs.y - self.x * rhs.z + self.y * rhs.real + self.z * rhs.x,
+            z: self.real * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.real,
+            real: self.real * rhs.real - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        };
+    }
+}
+
+impl MulAssign<Quat> for Quat {
+    fn mul_assign(&mut self, rhs: Quat) {
+        *self = *self * rhs
+    }
+}
+
+
+impl From<[f32; 4]> for Quat {
+    fn from(a: [f32; 4]) -> Self {
+        Self { x: a[0], y: a[1], z: a[2], real: a[3] }
+    }
+}
+
+impl From<Quat> for [f32; 4] {
+    fn from(q: Quat) -> Self {
+        [q.x, q.y, q.z, q.real]
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for Quat {
+    fn from(t: (f32, f32, f32, f32)) -> Self {
+        Self { x: t.0, y: t.1, z: t.2, real: t.3 }
+    }
+}
+
+impl From<Quat> for (f32, f32, f32, f32) {
+    fn from(q: Quat) -> Self {
+        (q.x, q.y, q.z, q.real)
+    }
+}
+
+impl Index<usize> for Quat {
+    type Output = f32;
+    fn index(&self, i: usize) -> &f32 {
+        &self.as_array()[i]
+    }
+}
+
+impl IndexMut<usize> for Quat {
+    fn index_mut(&mut self, i: usize) -> &mut f32 {
+        &mut self.as_mut_array()[i]
+    }
+}
+
+impl fmt::Display for Quat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::fmt_components(f, self.as_slice())
+    }
+}
+
+impl FromStr for Quat {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        format::parse_components::<4>(s).map(Quat::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_views_alias_the_fields() {
+        let mut q = Quat { x: 1.0, y: 2.0, z: 3.0, real: 4.0 };
+        assert_eq!(q.as_array(), &[1.0, 2.0, 3.0, 4.0]);
+        q.as_mut_array()[3] = 0.5;
+        q.as_mut_slice()[0] = -1.0;
+        assert_eq!((q.x, q.y, q.z, q.real), (-1.0, 2.0, 3.0, 0.5));
+        assert_eq!(q.as_slice(), [-1.0, 2.0, 3.0, 0.5]);
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    mat3::Mat3,
+    mat4::Mat4,
+    quat::Quat,
+    vector2::Vector2,
+    vector3::Vector3,
+    vector3_wide::{Vector3x4, Vector3x8},
+    vector4::Vector4,
+};
+
+// Every type is written as a flat array of its components, matrices in
+// row-major order.
+macro_rules! impl_serde_array {
+    ($ty:ty, $arr:ty, |$v:ident| $to:expr, |$a:ident| $from:expr) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let $v = self;
+                let arr: $arr = $to;
+                arr.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let $a = <$arr>::deserialize(deserializer)?;
+                Ok($from)
+            }
+        }
+    };
+}
+
+impl_serde_array!(Vector2, [f32; 2], |v| [v.x, v.y], |a| Vector2::new(a[0], a[1]));
+
+impl_serde_array!(Vector3, [f32; 3], |v| [v.x, v.y, v.z], |a| Vector3::new(a[0], a[1], a[2]));
+
+impl_serde_array!(Vector4, [f32; 4], |v| [v.x, v.y, v.z, v.w], |a| Vector4::new(a[0], a[1], a[2], a[3]));
+
+impl_serde_array!(Quat, [f32; 4], |q| [q.x, q.y, q.z, q.real], |a| Quat {
+    x: a[0],
+    y: a[1],
+    z: a[2],
+    real: a[3],
+});
+
+impl_serde_array!(Vector3x4, [[f32; 4]; 3], |v| [v.x, v.y, v.z], |a| Vector3x4::new(a[0], a[1], a[2]));
+
+impl_serde_array!(Vector3x8, [[f32; 8]; 3], |v| [v.x, v.y, v.z], |a| Vector3x8::new(a[0], a[1], a[2]));
+
+impl_serde_array!(Mat3, [f32; 9], |m| [
+    m.r0c0, m.r0c1, m.r0c2,
+    m.r1c0, m.r1c1, m.r1c2,
+    m.r2c0, m.r2c1, m.r2c2,
+], |a| Mat3 {
+    r0c0: a[0], r0c1: a[1], r0c2: a[2],
+    r1c0: a[3], r1c1: a[4], r1c2: a[5],
+    r2c0: a[6], r2c1: a[7], r2c2: a[8],
+});
+
+impl_serde_array!(Mat4, [f32; 16], |m| [
+    m.r0c0, m.r0c1, m.r0c2, m.r0c3,
+    m.r1c0, m.r1c1, m.r1c2, m.r1c3,
+    m.r2c0, m.r2c1, m.r2c2, m.r2c3,
+    m.r3c0, m.r3c1, m.r3c2, m.r3c3,
+], |a| Mat4 {
+    r0c0: a[0], r0c1: a[1], r0c2: a[2], r0c3: a[3],
+    r1c0: a[4], r1c1: a[5], r1c2: a[6], r1c3: a[7],
+    r2c0: a[8], r2c1: a[9], r2c2: a[10], r2c3: a[11],
+    r3c0: a[12], r3c1: a[13], r3c2: a[14], r3c3: a[15],
+});
+
+#[cfg(test)]
+mod tests {
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+
+    fn round_trip<T: Serialize + DeserializeOwned>(v: &T) -> T {
+        serde_json::from_str(&serde_json::to_string(v).unwrap()).unwrap()
+    }
+
+    // Values without short decimal forms, so lossy float printing would show.
+    fn values<const N: usize>() -> [f32; N] {
+        std::array::from_fn(|i| (i as f32 + 1.0) / 3.0 - 0.1)
+    }
+
+    fn bits(a: &[f32]) -> Vec<u32> {
+        a.iter().map(|v| v.to_bits()).collect()
+    }
+
+    #[test]
+    fn vectors_round_trip() {
+        let v = Vector2::from(values::<2>());
+        assert_eq!(bits(round_trip(&v).as_slice()), bits(v.as_slice()));
+        let v = Vector3::from(values::<3>());
+        assert_eq!(bits(round_trip(&v).as_slice()), bits(v.as_slice()));
+        let v = Vector4::from(values::<4>());
+        assert_eq!(bits(round_trip(&v).as_slice()), bits(v.as_slice()));
+    }
+
+    #[test]
+    fn quat_round_trips() {
+        let q = Quat::from(values::<4>());
+        assert_eq!(bits(round_trip(&q).as_slice()), bits(q.as_slice()));
+    }
+
+    #[test]
+    fn matrices_round_trip() {
+        let m = Mat3::from(values::<9>());
+        assert_eq!(bits(round_trip(&m).as_slice()), bits(m.as_slice()));
+        let m = Mat4::from(values::<16>());
+        assert_eq!(bits(round_trip(&m).as_slice()), bits(m.as_slice()));
+    }
+
+    #[test]
+    fn wide_vectors_round_trip() {
+        let v = Vector3x4::new(values::<4>(), values::<4>().map(|x| -x), values::<4>().map(|x| x * 7.0));
+        let back = round_trip(&v);
+        assert_eq!([bits(&back.x), bits(&back.y), bits(&back.z)], [bits(&v.x), bits(&v.y), bits(&v.z)]);
+        let v = Vector3x8::new(values::<8>(), values::<8>().map(|x| -x), values::<8>().map(|x| x * 7.0));
+        let back = round_trip(&v);
+        assert_eq!([bits(&back.x), bits(&back.y), bits(&back.z)], [bits(&v.x), bits(&v.y), bits(&v.z)]);
+    }
+
+    #[test]
+    fn compact_array_forms() {
+        assert_eq!(serde_json::to_string(&Vector3::new(1.0, 2.0, 3.0)).unwrap(), "[1.0,2.0,3.0]");
+        assert_eq!(serde_json::to_string(&Quat::from([1.0, 2.0, 3.0, 4.0])).unwrap(), "[1.0,2.0,3.0,4.0]");
+        let v = Vector3x4::new([1.0; 4], [2.0; 4], [3.0; 4]);
+        assert_eq!(serde_json::to_string(&v).unwrap(), "[[1.0,1.0,1.0,1.0],[2.0,2.0,2.0,2.0],[3.0,3.0,3.0,3.0]]");
+    }
+
+    #[test]
+    fn mat4_is_row_major() {
+        let m = Mat4 {
+            r0c0: 0.0, r0c1: 1.0, r0c2: 2.0, r0c3: 3.0,
+            r1c0: 4.0, r1c1: 5.0, r1c2: 6.0, r1c3: 7.0,
+            r2c0: 8.0, r2c1: 9.0, r2c2: 10.0, r2c3: 11.0,
+            r3c0: 12.0, r3c1: 13.0, r3c2: 14.0, r3c3: 15.0,
+        };
+        let json: Vec<f32> = serde_json::from_str(&serde_json::to_string(&m).unwrap()).unwrap();
+        assert_eq!(json, (0..16).map(|i| i as f32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn wrong_length_is_an_error() {
+        assert!(serde_json::from_str::<Vector3>("[1.0,2.0]").is_err());
+        assert!(serde_json::from_str::<Mat3>("[1.0,2.0,3.0]").is_err());
+    }
+}
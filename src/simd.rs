@@ -0,0 +1,389 @@
+use std::arch::x86_64::*;
+
+use crate::{mat4::Mat4, quat::Quat, vector4::Vector4};
+
+const fn mask(x: i32, y: i32, z: i32, w: i32) -> i32 {
+    x | (y << 2) | (z << 4) | (w << 6)
+}
+
+macro_rules! swizzle {
+    ($v:expr, $x:expr, $y:expr, $z:expr, $w:expr) => {
+        _mm_shuffle_ps::<{ mask($x, $y, $z, $w) }>($v, $v)
+    };
+}
+
+macro_rules! shuffle {
+    ($a:expr, $b:expr, $x:expr, $y:expr, $z:expr, $w:expr) => {
+        _mm_shuffle_ps::<{ mask($x, $y, $z, $w) }>($a, $b)
+    };
+}
+
+// Vector4, Quat and Mat4 are 16-byte aligned with the simd feature, so every
+// row of four floats can be moved with aligned loads and stores.
+
+#[inline]
+fn load_vec4(v: &Vector4) -> __m128 {
+    unsafe { _mm_load_ps(v as *const Vector4 as *const f32) }
+}
+
+#[inline]
+fn store_vec4(v: __m128) -> Vector4 {
+    let mut out = Vector4::new(0.0, 0.0, 0.0, 0.0);
+    unsafe { _mm_store_ps(&mut out as *mut Vector4 as *mut f32, v) };
+    out
+}
+
+#[inline]
+fn load_quat(q: &Quat) -> __m128 {
+    unsafe { _mm_load_ps(q as *const Quat as *const f32) }
+}
+
+#[inline]
+fn store_quat(v: __m128) -> Quat {
+    let mut out = Quat::zero();
+    unsafe { _mm_store_ps(&mut out as *mut Quat as *mut f32, v) };
+    out
+}
+
+#[inline]
+fn load_mat4(m: &Mat4) -> [__m128; 4] {
+    let p = m as *const Mat4 as *const f32;
+    unsafe {
+        [
+            _mm_load_ps(p),
+            _mm_load_ps(p.add(4)),
+            _mm_load_ps(p.add(8)),
+            _mm_load_ps(p.add(12)),
+        ]
+    }
+}
+
+#[inline]
+fn store_mat4(rows: [__m128; 4]) -> Mat4 {
+    let mut out = Mat4::zero();
+    let p = &mut out as *mut Mat4 as *mut f32;
+    unsafe {
+        _mm_store_ps(p, rows[0]);
+        _mm_store_ps(p.add(4), rows[1]);
+        _mm_store_ps(p.add(8), rows[2]);
+        _mm_store_ps(p.add(12), rows[3]);
+    }
+    out
+}
+
+#[inline]
+fn hsum(v: __m128) -> f32 {
+    unsafe {
+        let s = _mm_add_ps(v, swizzle!(v, 2, 3, 0, 1));
+        let s = _mm_add_ps(s, swizzle!(s, 1, 0, 3, 2));
+        _mm_cvtss_f32(s)
+    }
+}
+
+#[inline]
+fn row_mul(v: __m128, m: &[__m128; 4]) -> __m128 {
+    unsafe {
+        let x = _mm_mul_ps(swizzle!(v, 0, 0, 0, 0), m[0]);
+        let y = _mm_mul_ps(swizzle!(v, 1, 1, 1, 1), m[1]);
+        let z = _mm_mul_ps(swizzle!(v, 2, 2, 2, 2), m[2]);
+        let w = _mm_mul_ps(swizzle!(v, 3, 3, 3, 3), m[3]);
+        _mm_add_ps(_mm_add_ps(x, y), _mm_add_ps(z, w))
+    }
+}
+
+pub(crate) fn vec4_add(a: &Vector4, b: &Vector4) -> Vector4 {
+    store_vec4(unsafe { _mm_add_ps(load_vec4(a), load_vec4(b)) })
+}
+
+pub(crate) fn vec4_sub(a: &Vector4, b: &Vector4) -> Vector4 {
+    store_vec4(unsafe { _mm_sub_ps(load_vec4(a), load_vec4(b)) })
+}
+
+pub(crate) fn vec4_scale(a: &Vector4, s: f32) -> Vector4 {
+    store_vec4(unsafe { _mm_mul_ps(load_vec4(a), _mm_set1_ps(s)) })
+}
+
+pub(crate) fn vec4_dot(a: &Vector4, b: &Vector4) -> f32 {
+    hsum(unsafe { _mm_mul_ps(load_vec4(a), load_vec4(b)) })
+}
+
+pub(crate) fn vec4_mul_mat4(v: &Vector4, m: &Mat4) -> Vector4 {
+    store_vec4(row_mul(load_vec4(v), &load_mat4(m)))
+}
+
+pub(crate) fn quat_dot(a: &Quat, b: &Quat) -> f32 {
+    hsum(unsafe { _mm_mul_ps(load_quat(a), load_quat(b)) })
+}
+
+pub(crate) fn quat_mul(a: &Quat, b: &Quat) -> Quat {
+    let a = load_quat(a);
+    let b = load_quat(b);
+    unsafe {
+        let w = _mm_mul_ps(swizzle!(a, 3, 3, 3, 3), b);
+        let x = _mm_mul_ps(
+            _mm_mul_ps(swizzle!(a, 0, 0, 0, 0), swizzle!(b, 3, 2, 1, 0)),
+            _mm_setr_ps(1.0, -1.0, 1.0, -1.0),
+        );
+        let y = _mm_mul_ps(
+            _mm_mul_ps(swizzle!(a, 1, 1, 1, 1), swizzle!(b, 2, 3, 0, 1)),
+            _mm_setr_ps(1.0, 1.0, -1.0, -1.0),
+        );
+        let z = _mm_mul_ps(
+            _mm_mul_ps(swizzle!(a, 2, 2, 2, 2), swizzle!(b, 1, 0, 3, 2)),
+            _mm_setr_ps(-1.0, 1.0, 1.0, -1.0),
+        );
+        store_quat(_mm_add_ps(_mm_add_ps(w, x), _mm_add_ps(y, z)))
+    }
+}
+
+pub(crate) fn mat4_mul(a: &Mat4, b: &Mat4) -> Mat4 {
+    let a = load_mat4(a);
+    let b = load_mat4(b);
+    store_mat4([
+        row_mul(a[0], &b),
+        row_mul(a[1], &b),
+        row_mul(a[2], &b),
+        row_mul(a[3], &b),
+    ])
+}
+
+pub(crate) fn mat4_t(m: &Mat4) -> Mat4 {
+    let [r0, r1, r2, r3] = load_mat4(m);
+    unsafe {
+        let t0 = _mm_unpacklo_ps(r0, r1);
+        let t1 = _mm_unpacklo_ps(r2, r3);
+        let t2 = _mm_unpackhi_ps(r0, r1);
+        let t3 = _mm_unpackhi_ps(r2, r3);
+        store_mat4([
+            _mm_movelh_ps(t0, t1),
+            _mm_movehl_ps(t1, t0),
+            _mm_movelh_ps(t2, t3),
+            _mm_movehl_ps(t3, t2),
+        ])
+    }
+}
+
+// 2x2 row-major helpers for the block inverse, each matrix packed as (c0, c1, c2, c3)
+#[inline]
+fn mat2_mul(a: __m128, b: __m128) -> __m128 {
+    unsafe {
+        _mm_add_ps(
+            _mm_mul_ps(a, swizzle!(b, 0, 3, 0, 3)),
+            _mm_mul_ps(swizzle!(a, 1, 0, 3, 2), swizzle!(b, 2, 1, 2, 1)),
+        )
+    }
+}
+
+// adj(a) * b
+#[inline]
+fn mat2_adj_mul(a: __m128, b: __m128) -> __m128 {
+    unsafe {
+        _mm_sub_ps(
+            _mm_mul_ps(swizzle!(a, 3, 3, 0, 0), b),
+            _mm_mul_ps(swizzle!(a, 1, 1, 2, 2), swizzle!(b, 2, 3, 0, 1)),
+        )
+    }
+}
+
+// a * adj(b)
+#[inline]
+fn mat2_mul_adj(a: __m128, b: __m128) -> __m128 {
+    unsafe {
+        _mm_sub_ps(
+            _mm_mul_ps(a, swizzle!(b, 3, 0, 3, 0)),
+            _mm_mul_ps(swizzle!(a, 1, 0, 3, 2), swizzle!(b, 2, 1, 2, 1)),
+        )
+    }
+}
+
+// Block-wise inverse: M = | A B |, each block 2x2
+//                         | C D |
+pub(crate) fn mat4_inv(m: &Mat4) -> Mat4 {
+    let [r0, r1, r2, r3] = load_mat4(m);
+    unsafe {
+        let a = _mm_movelh_ps(r0, r1);
+        let b = _mm_movehl_ps(r1, r0);
+        let c = _mm_movelh_ps(r2, r3);
+        let d = _mm_movehl_ps(r3, r2);
+
+        let det_sub = _mm_sub_ps(
+            _mm_mul_ps(shuffle!(r0, r2, 0, 2, 0, 2), shuffle!(r1, r3, 1, 3, 1, 3)),
+            _mm_mul_ps(shuffle!(r0, r2, 1, 3, 1, 3), shuffle!(r1, r3, 0, 2, 0, 2)),
+        );
+        let det_a = swizzle!(det_sub, 0, 0, 0, 0);
+        let det_b = swizzle!(det_sub, 1, 1, 1, 1);
+        let det_c = swizzle!(det_sub, 2, 2, 2, 2);
+        let det_d = swizzle!(det_sub, 3, 3, 3, 3);
+
+        let d_c = mat2_adj_mul(d, c);
+        let a_b = mat2_adj_mul(a, b);
+        let x = _mm_sub_ps(_mm_mul_ps(det_d, a), mat2_mul(b, d_c));
+        let w = _mm_sub_ps(_mm_mul_ps(det_a, d), mat2_mul(c, a_b));
+        let y = _mm_sub_ps(_mm_mul_ps(det_b, c), mat2_mul_adj(d, a_b));
+        let z = _mm_sub_ps(_mm_mul_ps(det_c, b), mat2_mul_adj(a, d_c));
+
+        let tr = _mm_mul_ps(a_b, swizzle!(d_c, 0, 2, 1, 3));
+        let det = _mm_sub_ps(
+            _mm_add_ps(_mm_mul_ps(det_a, det_d), _mm_mul_ps(det_b, det_c)),
+            _mm_set1_ps(hsum(tr)),
+        );
+        let r_det = _mm_div_ps(_mm_setr_ps(1.0, -1.0, -1.0, 1.0), det);
+
+        let x = _mm_mul_ps(x, r_det);
+        let y = _mm_mul_ps(y, r_det);
+        let z = _mm_mul_ps(z, r_det);
+        let w = _mm_mul_ps(w, r_det);
+
+        store_mat4([
+            shuffle!(x, y, 3, 1, 3, 1),
+            shuffle!(x, y, 2, 0, 2, 0),
+            shuffle!(z, w, 3, 1, 3, 1),
+            shuffle!(z, w, 2, 0, 2, 0),
+        ])
+    }
+}
+
+#[cfg(all(test, feature = "simd", target_arch = "x86_64"))]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    // Tolerance for a sum of products whose absolute terms add up to scale.
+    fn assert_close(a: f32, b: f32, scale: f32) {
+        assert!((a - b).abs() <= 8.0 * f32::EPSILON * scale.max(1.0), "{a} != {b}");
+    }
+
+    fn scalar_vec4_mul_mat4(v: &Vector4, m: &Mat4) -> (Vector4, Vector4) {
+        let [x, y, z, w] = [v.x, v.y, v.z, v.w];
+        let col = |c: usize| {
+            let terms = [x * m[(0, c)], y * m[(1, c)], z * m[(2, c)], w * m[(3, c)]];
+            (terms.iter().sum::<f32>(), terms.iter().map(|t| t.abs()).sum::<f32>())
+        };
+        let [c0, c1, c2, c3] = [col(0), col(1), col(2), col(3)];
+        (Vector4::new(c0.0, c1.0, c2.0, c3.0), Vector4::new(c0.1, c1.1, c2.1, c3.1))
+    }
+
+    #[test]
+    fn vec4_mul_mat4_matches_scalar() {
+        let mut rng = Rng(1);
+        for _ in 0..1000 {
+            let (v, m) = (rng.vec4(), rng.mat4());
+            let got = vec4_mul_mat4(&v, &m);
+            let (want, scale) = scalar_vec4_mul_mat4(&v, &m);
+            for i in 0..4 {
+                assert_close(got[i], want[i], scale[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn mat4_mul_matches_scalar() {
+        let mut rng = Rng(2);
+        for _ in 0..1000 {
+            let (a, b) = (rng.mat4(), rng.mat4());
+            let got = mat4_mul(&a, &b);
+            for r in 0..4 {
+                let row = Vector4::new(a[(r, 0)], a[(r, 1)], a[(r, 2)], a[(r, 3)]);
+                let (want, scale) = scalar_vec4_mul_mat4(&row, &b);
+                for c in 0..4 {
+                    assert_close(got[(r, c)], want[c], scale[c]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mat4_t_bit_matches_scalar() {
+        let mut rng = Rng(3);
+        for _ in 0..100 {
+            let m = rng.mat4();
+            let got = mat4_t(&m);
+            for r in 0..4 {
+                for c in 0..4 {
+                    assert_eq!(got[(r, c)].to_bits(), m[(c, r)].to_bits());
+                }
+            }
+        }
+    }
+
+    // Largest absolute entry of got - want, relative to the largest of want.
+    fn relative_error(got: &Mat4, want: &Mat4) -> f32 {
+        let scale = want.as_array().iter().fold(0.0f32, |a, v| a.max(v.abs()));
+        let err = got.as_array().iter().zip(want.as_array()).fold(0.0f32, |a, (g, w)| a.max((g - w).abs()));
+        err / scale
+    }
+
+    #[test]
+    fn mat4_inv_matches_scalar() {
+        let mut rng = Rng(4);
+        let mut tested = 0;
+        while tested < 1000 {
+            let m = rng.mat4();
+            // Keep to reasonably conditioned matrices; near-singular ones are below.
+            if m.get_det().abs() < 100.0 {
+                continue;
+            }
+            assert!(relative_error(&mat4_inv(&m), &m.get_inv_scalar()) < 1e-4);
+            tested += 1;
+        }
+    }
+
+    #[test]
+    fn mat4_inv_near_singular() {
+        let mut rng = Rng(5);
+        for _ in 0..200 {
+            // Tiny determinant but well conditioned.
+            let m = 1e-3 * rng.mat4();
+            if m.get_det().abs() < 1e-12 * 100.0 {
+                continue;
+            }
+            assert!(relative_error(&mat4_inv(&m), &m.get_inv_scalar()) < 1e-4);
+
+            // Two rows nearly parallel: both paths lose precision together,
+            // but each must still invert m to within its conditioning.
+            let mut m = rng.mat4();
+            for c in 0..4 {
+                m[(3, c)] = m[(2, c)] * 2.0 + 1e-2 * rng.next();
+            }
+            let inv = mat4_inv(&m);
+            let scale = inv.as_array().iter().fold(0.0f32, |a, v| a.max(v.abs()))
+                * m.as_array().iter().fold(0.0f32, |a, v| a.max(v.abs()));
+            let identity = mat4_mul(&m, &inv);
+            for r in 0..4 {
+                for c in 0..4 {
+                    let want = if r == c { 1.0 } else { 0.0 };
+                    assert!((identity[(r, c)] - want).abs() < 1e-4 * scale);
+                }
+            }
+            assert!(relative_error(&inv, &m.get_inv_scalar()) < 1e-2);
+        }
+    }
+
+    #[test]
+    fn quat_mul_matches_scalar() {
+        let mut rng = Rng(6);
+        for _ in 0..1000 {
+            let (a, b) = (rng.quat(), rng.quat());
+            let got = quat_mul(&a, &b);
+            let terms = [
+                [a.real * b.x, a.x * b.real, a.y * b.z, -a.z * b.y],
+                [a.real * b.y, -a.x * b.z, a.y * b.real, a.z * b.x],
+                [a.real * b.z, a.x * b.y, -a.y * b.x, a.z * b.real],
+                [a.real * b.real, -a.x * b.x, -a.y * b.y, -a.z * b.z],
+            ];
+            for (i, t) in terms.iter().enumerate() {
+                assert_close(got[i], t.iter().sum(), t.iter().map(|v| v.abs()).sum());
+            }
+        }
+    }
+
+    #[test]
+    fn quat_dot_matches_scalar() {
+        let mut rng = Rng(7);
+        for _ in 0..1000 {
+            let (a, b) = (rng.quat(), rng.quat());
+            let terms = [a.x * b.x, a.y * b.y, a.z * b.z, a.real * b.real];
+            assert_close(quat_dot(&a, &b), terms.iter().sum(), terms.iter().map(|v| v.abs()).sum());
+        }
+    }
+}
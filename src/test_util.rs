@@ -0,0 +1,32 @@
+use crate::{mat4::Mat4, quat::Quat, vector3::Vector3, vector4::Vector4};
+
+// Small LCG shared by the tests, so they need no extra dependencies and a
+// failure reproduces from its seed.
+pub(crate) struct Rng(pub(crate) u64);
+
+// Some of these are only used by feature-gated tests.
+#[allow(dead_code)]
+impl Rng {
+    // Uniform in [-10, 10).
+    pub(crate) fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 40) as f32 / (1u64 << 24) as f32) * 20.0 - 10.0
+    }
+
+    pub(crate) fn vec3(&mut self) -> Vector3 {
+        Vector3::new(self.next(), self.next(), self.next())
+    }
+
+    pub(crate) fn vec4(&mut self) -> Vector4 {
+        Vector4::new(self.next(), self.next(), self.next(), self.next())
+    }
+
+    // Not normalized.
+    pub(crate) fn quat(&mut self) -> Quat {
+        Quat::from([self.next(), self.next(), self.next(), self.next()])
+    }
+
+    pub(crate) fn mat4(&mut self) -> Mat4 {
+        Mat4::from(std::array::from_fn::<f32, 16, _>(|_| self.next()))
+    }
+}
@@ -1,3 +1,8 @@
+use std::f32::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+use crate::{vector2::Vector2, vector3::Vector3, vector4::Vector4};
+
 pub const MATH_TOLERANCE: f32 = 0.001;
 
 pub fn is_equal(a: f32, b: f32, epsilon: f32) -> bool {
@@ -22,3 +27,424 @@ pub fn is_zero(a: f32, epsilon: f32) -> bool {
 pub fn is_non_zero(a: f32, epsilon: f32) -> bool {
     a < -epsilon || a > epsilon
 }
+
+pub fn is_equal_rel(a: f32, b: f32, epsilon: f32, max_relative: f32) -> bool {
+    if a == b {
+        return true;
+    }
+    // An infinity only equals itself, however loose max_relative is.
+    if a.is_infinite() || b.is_infinite() {
+        return false;
+    }
+    let diff = (a - b).abs();
+    if diff <= epsilon {
+        return true;
+    }
+    diff <= a.abs().max(b.abs()) * max_relative
+}
+
+pub fn is_equal_ulps(a: f32, b: f32, epsilon: f32, max_ulps: u32) -> bool {
+    if (a - b).abs() <= epsilon {
+        return true;
+    }
+    if a.is_nan() || b.is_nan() || a.is_sign_negative() != b.is_sign_negative() {
+        return a == b;
+    }
+    let a_bits = a.to_bits() as i64;
+    let b_bits = b.to_bits() as i64;
+    (a_bits - b_bits).unsigned_abs() <= max_ulps as u64
+}
+
+pub fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+pub fn inverse_lerp(a: f32, b: f32, v: f32) -> f32 {
+    if a == b {
+        return 0.0;
+    }
+    (v - a) / (b - a)
+}
+
+pub fn remap(v: f32, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> f32 {
+    lerp(out_min, out_max, inverse_lerp(in_min, in_max, v))
+}
+
+pub fn step(edge: f32, x: f32) -> f32 {
+    if x < edge { 0.0 } else { 1.0 }
+}
+
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = inverse_lerp(edge0, edge1, x).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+pub fn smootherstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = inverse_lerp(edge0, edge1, x).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+pub fn fract(x: f32) -> f32 {
+    x - x.floor()
+}
+
+// Wraps v into [min, max). An empty range gives min.
+pub fn wrap(v: f32, min: f32, max: f32) -> f32 {
+    if max <= min {
+        return min;
+    }
+    let r = min + (v - min).rem_euclid(max - min);
+    // Rounding can land a tiny negative offset exactly on max.
+    if r >= max { min } else { r }
+}
+
+// Bounces t back and forth between 0 and length. A zero length gives 0.
+pub fn ping_pong(t: f32, length: f32) -> f32 {
+    if length <= 0.0 {
+        return 0.0;
+    }
+    length - (t.rem_euclid(2.0 * length) - length).abs()
+}
+
+pub trait SmoothDamp: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self> {
+    fn damp_dot(&self, rhs: &Self) -> f32;
+}
+
+impl SmoothDamp for f32 {
+    fn damp_dot(&self, rhs: &Self) -> f32 {
+        self * rhs
+    }
+}
+
+impl SmoothDamp for Vector2 {
+    fn damp_dot(&self, rhs: &Self) -> f32 {
+        self.dot(rhs)
+    }
+}
+
+impl SmoothDamp for Vector3 {
+    fn damp_dot(&self, rhs: &Self) -> f32 {
+        self.dot(rhs)
+    }
+}
+
+impl SmoothDamp for Vector4 {
+    fn damp_dot(&self, rhs: &Self) -> f32 {
+        self.dot(rhs)
+    }
+}
+
+// Critically damped spring towards target (Game Programming Gems 4, 1.10).
+// velocity carries state between calls; the step never overshoots target.
+pub fn smooth_damp<T: SmoothDamp>(
+    current: T,
+    target: T,
+    velocity: &mut T,
+    smooth_time: f32,
+    max_speed: f32,
+    dt: f32,
+) -> T {
+    let smooth_time = smooth_time.max(0.0001);
+    let omega = 2.0 / smooth_time;
+    let x = omega * dt;
+    let exp = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+
+    let mut change = current - target;
+    let max_change = max_speed * smooth_time;
+    let mag_sqr = change.damp_dot(&change);
+    if mag_sqr > max_change * max_change {
+        change = change * (max_change / mag_sqr.sqrt());
+    }
+    let clamped_target = current - change;
+
+    let temp = (*velocity + change * omega) * dt;
+    *velocity = (*velocity - temp * omega) * exp;
+    let mut out = clamped_target + (change + temp) * exp;
+
+    let to_target = target - current;
+    let past_target = out - target;
+    if to_target.damp_dot(&past_target) > 0.0 {
+        out = target;
+        *velocity = past_target * 0.0;
+    }
+    out
+}
+
+// Robert Penner's easing curves, each mapping t in [0, 1] to [0, 1] at the ends.
+
+pub fn ease_in_quad(t: f32) -> f32 {
+    t * t
+}
+
+pub fn ease_out_quad(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+pub fn ease_in_out_quad(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+    }
+}
+
+pub fn ease_in_cubic(t: f32) -> f32 {
+    t * t * t
+}
+
+pub fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+pub fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+pub fn ease_in_quart(t: f32) -> f32 {
+    t.powi(4)
+}
+
+pub fn ease_out_quart(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(4)
+}
+
+pub fn ease_in_out_quart(t: f32) -> f32 {
+    if t < 0.5 {
+        8.0 * t.powi(4)
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(4) / 2.0
+    }
+}
+
+pub fn ease_in_quint(t: f32) -> f32 {
+    t.powi(5)
+}
+
+pub fn ease_out_quint(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(5)
+}
+
+pub fn ease_in_out_quint(t: f32) -> f32 {
+    if t < 0.5 {
+        16.0 * t.powi(5)
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(5) / 2.0
+    }
+}
+
+pub fn ease_in_sine(t: f32) -> f32 {
+    1.0 - (t * PI / 2.0).cos()
+}
+
+pub fn ease_out_sine(t: f32) -> f32 {
+    (t * PI / 2.0).sin()
+}
+
+pub fn ease_in_out_sine(t: f32) -> f32 {
+    -((PI * t).cos() - 1.0) / 2.0
+}
+
+pub fn ease_in_expo(t: f32) -> f32 {
+    if t <= 0.0 { 0.0 } else { 2f32.powf(10.0 * t - 10.0) }
+}
+
+pub fn ease_out_expo(t: f32) -> f32 {
+    if t >= 1.0 { 1.0 } else { 1.0 - 2f32.powf(-10.0 * t) }
+}
+
+pub fn ease_in_out_expo(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else if t < 0.5 {
+        2f32.powf(20.0 * t - 10.0) / 2.0
+    } else {
+        (2.0 - 2f32.powf(-20.0 * t + 10.0)) / 2.0
+    }
+}
+
+pub fn ease_in_circ(t: f32) -> f32 {
+    1.0 - (1.0 - t * t).sqrt()
+}
+
+pub fn ease_out_circ(t: f32) -> f32 {
+    (1.0 - (t - 1.0) * (t - 1.0)).sqrt()
+}
+
+pub fn ease_in_out_circ(t: f32) -> f32 {
+    if t < 0.5 {
+        (1.0 - (1.0 - (2.0 * t).powi(2)).sqrt()) / 2.0
+    } else {
+        ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) / 2.0
+    }
+}
+
+const BACK_C1: f32 = 1.70158;
+const BACK_C2: f32 = BACK_C1 * 1.525;
+const BACK_C3: f32 = BACK_C1 + 1.0;
+
+pub fn ease_in_back(t: f32) -> f32 {
+    BACK_C3 * t * t * t - BACK_C1 * t * t
+}
+
+pub fn ease_out_back(t: f32) -> f32 {
+    1.0 + BACK_C3 * (t - 1.0).powi(3) + BACK_C1 * (t - 1.0).powi(2)
+}
+
+pub fn ease_in_out_back(t: f32) -> f32 {
+    if t < 0.5 {
+        ((2.0 * t).powi(2) * ((BACK_C2 + 1.0) * 2.0 * t - BACK_C2)) / 2.0
+    } else {
+        ((2.0 * t - 2.0).powi(2) * ((BACK_C2 + 1.0) * (t * 2.0 - 2.0) + BACK_C2) + 2.0) / 2.0
+    }
+}
+
+const ELASTIC_C4: f32 = (2.0 * PI) / 3.0;
+const ELASTIC_C5: f32 = (2.0 * PI) / 4.5;
+
+pub fn ease_in_elastic(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        -(2f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC_C4).sin()
+    }
+}
+
+pub fn ease_out_elastic(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * ELASTIC_C4).sin() + 1.0
+    }
+}
+
+pub fn ease_in_out_elastic(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else if t < 0.5 {
+        -(2f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * ELASTIC_C5).sin()) / 2.0
+    } else {
+        (2f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * ELASTIC_C5).sin()) / 2.0 + 1.0
+    }
+}
+
+pub fn ease_out_bounce(t: f32) -> f32 {
+    const N1: f32 = 7.5625;
+    const D1: f32 = 2.75;
+    if t < 1.0 / D1 {
+        N1 * t * t
+    } else if t < 2.0 / D1 {
+        let t = t - 1.5 / D1;
+        N1 * t * t + 0.75
+    } else if t < 2.5 / D1 {
+        let t = t - 2.25 / D1;
+        N1 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D1;
+        N1 * t * t + 0.984375
+    }
+}
+
+pub fn ease_in_bounce(t: f32) -> f32 {
+    1.0 - ease_out_bounce(1.0 - t)
+}
+
+pub fn ease_in_out_bounce(t: f32) -> f32 {
+    if t < 0.5 {
+        (1.0 - ease_out_bounce(1.0 - 2.0 * t)) / 2.0
+    } else {
+        (1.0 + ease_out_bounce(2.0 * t - 1.0)) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lerp_and_inverse_lerp() {
+        assert_eq!(lerp(2.0, 6.0, 0.25), 3.0);
+        assert_eq!(inverse_lerp(2.0, 6.0, 3.0), 0.25);
+        assert_eq!(inverse_lerp(2.0, 2.0, 3.0), 0.0);
+        assert_eq!(remap(5.0, 0.0, 10.0, 100.0, 200.0), 150.0);
+    }
+
+    #[test]
+    fn steps_clamp_outside_the_edges() {
+        assert_eq!(step(1.0, 0.5), 0.0);
+        assert_eq!(step(1.0, 1.0), 1.0);
+        for f in [smoothstep, smootherstep] {
+            assert_eq!(f(1.0, 3.0, 0.0), 0.0);
+            assert_eq!(f(1.0, 3.0, 2.0), 0.5);
+            assert_eq!(f(1.0, 3.0, 4.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn fract_is_positive() {
+        assert_eq!(fract(2.25), 0.25);
+        assert_eq!(fract(-0.25), 0.75);
+    }
+
+    #[test]
+    fn wrap_stays_in_range() {
+        assert_eq!(wrap(5.0, 0.0, 4.0), 1.0);
+        assert_eq!(wrap(-1.0, 0.0, 4.0), 3.0);
+        assert_eq!(wrap(4.0, 0.0, 4.0), 0.0);
+        assert_eq!(wrap(-7.0, -2.0, 2.0), 1.0);
+        assert_eq!(wrap(-1e-8, 0.0, 1.0), 0.0);
+        assert_eq!(wrap(3.0, 1.0, 1.0), 1.0);
+        assert_eq!(wrap(3.0, 2.0, 1.0), 2.0);
+    }
+
+    #[test]
+    fn ping_pong_bounces() {
+        assert_eq!(ping_pong(0.5, 2.0), 0.5);
+        assert_eq!(ping_pong(3.0, 2.0), 1.0);
+        assert_eq!(ping_pong(4.0, 2.0), 0.0);
+        assert_eq!(ping_pong(-1.0, 2.0), 1.0);
+        assert_eq!(ping_pong(3.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn smooth_damp_reaches_target_without_overshoot() {
+        let mut v = 0.0;
+        let mut x = 0.0;
+        for _ in 0..600 {
+            x = smooth_damp(x, 10.0, &mut v, 0.3, f32::INFINITY, 1.0 / 60.0);
+            assert!(x <= 10.0);
+        }
+        assert!((x - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn easings_fix_the_ends() {
+        let easings: [fn(f32) -> f32; 30] = [
+            ease_in_quad, ease_out_quad, ease_in_out_quad,
+            ease_in_cubic, ease_out_cubic, ease_in_out_cubic,
+            ease_in_quart, ease_out_quart, ease_in_out_quart,
+            ease_in_quint, ease_out_quint, ease_in_out_quint,
+            ease_in_sine, ease_out_sine, ease_in_out_sine,
+            ease_in_expo, ease_out_expo, ease_in_out_expo,
+            ease_in_circ, ease_out_circ, ease_in_out_circ,
+            ease_in_back, ease_out_back, ease_in_out_back,
+            ease_in_elastic, ease_out_elastic, ease_in_out_elastic,
+            ease_in_bounce, ease_out_bounce, ease_in_out_bounce,
+        ];
+        for (i, f) in easings.iter().enumerate() {
+            assert!(f(0.0).abs() < 1e-6, "easing {i} at 0: {}", f(0.0));
+            assert!((f(1.0) - 1.0).abs() < 1e-6, "easing {i} at 1: {}", f(1.0));
+        }
+    }
+}
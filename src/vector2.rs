@@ -1,17 +1,29 @@
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{fmt, str::FromStr};
+
+use crate::approx_eq::ApproxEq;
+use crate::format::{self, ParseError};
 use crate::vector3::Vector3;
 use crate::vector4::Vector4;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32
 }
 
+const _: () = assert!(std::mem::size_of::<Vector2>() == std::mem::size_of::<[f32; 2]>());
+
 impl Vector2 {
     pub fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
 
+    pub fn zero() -> Self {
+        Self { x: 0.0, y: 0.0 }
+    }
+
     pub fn from_vector3(v: &Vector3) ->  Self {
         Self { x: v.x, y: v.y }
     }
@@ -46,10 +58,210 @@ impl Vector2 {
         self.x * rhs.x + self.y * rhs.y
     }
 
+    // z of the 3D cross product; positive when rhs is counter-clockwise from self.
+    pub fn cross(&self, rhs: &Vector2) -> f32 {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
     pub fn get_angle(&self, rhs: &Vector2) -> f32 {
         let dot = self.dot(rhs);
         let mag_a = self.get_mag();
         let mag_b = rhs.get_mag();
         (dot / (mag_a * mag_b)).acos()
     }
-}
\ No newline at end of file
+
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.as_array().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.as_mut_array().iter_mut()
+    }
+
+    pub fn min(&self, rhs: &Vector2) -> Self {
+        Self {
+            x: self.x.min(rhs.x),
+            y: self.y.min(rhs.y),
+        }
+    }
+
+    pub fn max(&self, rhs: &Vector2) -> Self {
+        Self {
+            x: self.x.max(rhs.x),
+            y: self.y.max(rhs.y),
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+        }
+    }
+
+    pub fn is_equal(&self, rhs: &Vector2, epsilon: f32) -> bool {
+        self.abs_eq(rhs, epsilon)
+    }
+
+    pub fn as_array(&self) -> &[f32; 2] {
+        // SAFETY: Vector2 is repr(C) with two f32 fields, so it has the size and
+        // alignment of [f32; 2] and x, y are its elements in order.
+        unsafe { &*(self as *const Self as *const [f32; 2]) }
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 2] {
+        // SAFETY: as for as_array, and &mut self makes the borrow unique.
+        unsafe { &mut *(self as *mut Self as *mut [f32; 2]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
+    }
+}
+
+
+impl Add for Vector2 {
+    type Output = Self;
+    fn add(self, rhs: Vector2) -> Self {
+        Vector2 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y
+        }
+    }
+}
+
+impl AddAssign for Vector2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y
+        }
+    }
+}
+
+impl Sub for Vector2 {
+    type Output = Self;
+    fn sub(self, rhs: Vector2) -> Self {
+        Vector2 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y
+        }
+    }
+}
+
+impl SubAssign for Vector2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y
+        }
+    }
+}
+
+impl Neg for Vector2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y
+        }
+    }
+}
+
+impl Mul<f32> for Vector2 {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs
+        }
+    }
+}
+
+impl MulAssign<f32> for Vector2 {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = Self {
+            x: self.x * rhs,
+            y: self.y * rhs
+        }
+    }
+}
+
+impl Mul<Vector2> for f32 {
+    type Output = Vector2;
+    fn mul(self, rhs: Vector2) -> Vector2 {
+        Vector2 {
+            x: self * rhs.x,
+            y: self * rhs.y,
+        }
+    }
+}
+
+impl From<[f32; 2]> for Vector2 {
+    fn from(a: [f32; 2]) -> Self {
+        Vector2::new(a[0], a[1])
+    }
+}
+
+impl From<Vector2> for [f32; 2] {
+    fn from(v: Vector2) -> Self {
+        [v.x, v.y]
+    }
+}
+
+impl From<(f32, f32)> for Vector2 {
+    fn from(t: (f32, f32)) -> Self {
+        Vector2::new(t.0, t.1)
+    }
+}
+
+impl From<Vector2> for (f32, f32) {
+    fn from(v: Vector2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl Index<usize> for Vector2 {
+    type Output = f32;
+    fn index(&self, i: usize) -> &f32 {
+        &self.as_array()[i]
+    }
+}
+
+impl IndexMut<usize> for Vector2 {
+    fn index_mut(&mut self, i: usize) -> &mut f32 {
+        &mut self.as_mut_array()[i]
+    }
+}
+
+impl fmt::Display for Vector2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::fmt_components(f, self.as_slice())
+    }
+}
+
+impl FromStr for Vector2 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        format::parse_components::<2>(s).map(Vector2::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_views_alias_the_fields() {
+        let mut v = Vector2::new(1.0, 2.0);
+        assert_eq!(v.as_array(), &[1.0, 2.0]);
+        v.as_mut_array()[0] = -3.0;
+        v.as_mut_slice()[1] = 5.0;
+        assert_eq!((v.x, v.y), (-3.0, 5.0));
+        assert_eq!(v.as_slice(), [-3.0, 5.0]);
+    }
+}
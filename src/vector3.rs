@@ -1,14 +1,18 @@
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{fmt, str::FromStr};
 
-use crate::{mat3::Mat3, mat4::Mat4, quat::Quat, util::{is_equal, MATH_TOLERANCE}, vector4::Vector4};
+use crate::{batch, format::{self, ParseError}, mat3::Mat3, mat4::Mat4, quat::Quat, util::is_equal, vector4::Vector4};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
 pub struct Vector3 {
     pub x: f32,
     pub y: f32,
     pub z: f32
 }
 
+const _: () = assert!(std::mem::size_of::<Vector3>() == std::mem::size_of::<[f32; 3]>());
+
 impl Vector3 {
 
     pub fn new(x: f32, y: f32, z: f32) -> Self {
@@ -45,6 +49,14 @@ impl Vector3 {
         }
     }
 
+    pub fn normalize_slice(v: &mut [Vector3]) {
+        batch::map_slice_mut(v, |v| {
+            for v in v.iter_mut() {
+                v.norm();
+            }
+        });
+    }
+
     pub fn get_mag(&self) -> f32 {
         f32::sqrt(self.x * self.x + self.y * self.y + self.z * self.z)
     }
@@ -72,16 +84,61 @@ impl Vector3 {
         (dot / (mag_a * mag_b)).acos()
     }
 
+    pub fn min(&self, rhs: &Vector3) -> Self {
+        Self {
+            x: self.x.min(rhs.x),
+            y: self.y.min(rhs.y),
+            z: self.z.min(rhs.z),
+        }
+    }
+
+    pub fn max(&self, rhs: &Vector3) -> Self {
+        Self {
+            x: self.x.max(rhs.x),
+            y: self.y.max(rhs.y),
+            z: self.z.max(rhs.z),
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
     pub fn is_equal(&self, rhs: &Vector3, epsilon: f32) -> bool{
         is_equal(self.x, rhs.x, epsilon) && 
         is_equal(self.y, rhs.y, epsilon) && 
         is_equal(self.z, rhs.z, epsilon)
     }
-}
 
-impl PartialEq for Vector3 {
-    fn eq(&self, rhs: &Self) -> bool{
-        self.is_equal(rhs, MATH_TOLERANCE)
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.as_array().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.as_mut_array().iter_mut()
+    }
+
+    pub fn as_array(&self) -> &[f32; 3] {
+        // SAFETY: Vector3 is repr(C) with three f32 fields, so it has the size and
+        // alignment of [f32; 3] and x, y, z are its elements in order.
+        unsafe { &*(self as *const Self as *const [f32; 3]) }
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 3] {
+        // SAFETY: as for as_array, and &mut self makes the borrow unique.
+        unsafe { &mut *(self as *mut Self as *mut [f32; 3]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
     }
 }
 
@@ -188,7 +245,7 @@ impl Mul<Mat3> for Vector3 {
         Self {
             x: self.x * rhs.r0c0 + self.y * rhs.r1c0 + self.z * rhs.r2c0,
             y: self.x * rhs.r0c1 + self.y * rhs.r1c1 + self.z * rhs.r2c1,
-            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z + rhs.r2c2,
+            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z * rhs.r2c2,
         }
     }
 }
@@ -199,7 +256,7 @@ impl MulAssign<Mat3> for Vector3 {
         *self = Self {
             x: self.x * rhs.r0c0 + self.y * rhs.r1c0 + self.z * rhs.r2c0,
             y: self.x * rhs.r0c1 + self.y * rhs.r1c1 + self.z * rhs.r2c1,
-            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z + rhs.r2c2,
+            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z * rhs.r2c2,
         }
     }
 }
@@ -210,8 +267,8 @@ impl Mul<Mat4> for Vector3 {
         Vector4 {
             x: self.x * rhs.r0c0 + self.y * rhs.r1c0 + self.z * rhs.r2c0 + rhs.r3c0,
             y: self.x * rhs.r0c1 + self.y * rhs.r1c1 + self.z * rhs.r2c1 + rhs.r3c1,
-            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z + rhs.r2c2 + rhs.r3c2,
-            w: self.x * rhs.r0c3 + self.y * rhs.r1c3 + self.z + rhs.r2c3 + rhs.r3c3,
+            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z * rhs.r2c2 + rhs.r3c2,
+            w: self.x * rhs.r0c3 + self.y * rhs.r1c3 + self.z * rhs.r2c3 + rhs.r3c3,
         }
     }
 }
@@ -227,4 +284,69 @@ impl MulAssign<Quat> for Vector3 {
     fn mul_assign(&mut self, rhs: Quat) {
         *self = rhs.lqcvq(self)
     }
-}
\ No newline at end of file
+}
+
+impl From<[f32; 3]> for Vector3 {
+    fn from(a: [f32; 3]) -> Self {
+        Vector3::new(a[0], a[1], a[2])
+    }
+}
+
+impl From<Vector3> for [f32; 3] {
+    fn from(v: Vector3) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl From<(f32, f32, f32)> for Vector3 {
+    fn from(t: (f32, f32, f32)) -> Self {
+        Vector3::new(t.0, t.1, t.2)
+    }
+}
+
+impl From<Vector3> for (f32, f32, f32) {
+    fn from(v: Vector3) -> Self {
+        (v.x, v.y, v.z)
+    }
+}
+
+impl Index<usize> for Vector3 {
+    type Output = f32;
+    fn index(&self, i: usize) -> &f32 {
+        &self.as_array()[i]
+    }
+}
+
+impl IndexMut<usize> for Vector3 {
+    fn index_mut(&mut self, i: usize) -> &mut f32 {
+        &mut self.as_mut_array()[i]
+    }
+}
+
+impl fmt::Display for Vector3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::fmt_components(f, self.as_slice())
+    }
+}
+
+impl FromStr for Vector3 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        format::parse_components::<3>(s).map(Vector3::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_views_alias_the_fields() {
+        let mut v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(v.as_array(), &[1.0, 2.0, 3.0]);
+        v.as_mut_array()[2] = -3.0;
+        v.as_mut_slice()[0] = 5.0;
+        assert_eq!((v.x, v.y, v.z), (5.0, 2.0, -3.0));
+        assert_eq!(v.as_slice(), [5.0, 2.0, -3.0]);
+    }
+}
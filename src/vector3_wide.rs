@@ -0,0 +1,273 @@
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{mat3::Mat3, mat4::Mat4, vector3::Vector3};
+
+// Structure-of-arrays packs of Vector3 lanes. Every operation is a plain
+// per-lane loop over fixed-size arrays so the compiler can keep it in
+// vector registers.
+macro_rules! vector3_wide {
+    ($name:ident, $lanes:expr, $align:expr) => {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(C, align($align))]
+        pub struct $name {
+            pub x: [f32; $lanes],
+            pub y: [f32; $lanes],
+            pub z: [f32; $lanes],
+        }
+
+        impl $name {
+            pub const LANES: usize = $lanes;
+
+            pub fn new(x: [f32; $lanes], y: [f32; $lanes], z: [f32; $lanes]) -> Self {
+                Self { x, y, z }
+            }
+
+            pub fn zero() -> Self {
+                Self {
+                    x: [0.0; $lanes],
+                    y: [0.0; $lanes],
+                    z: [0.0; $lanes],
+                }
+            }
+
+            pub fn splat(v: &Vector3) -> Self {
+                Self {
+                    x: [v.x; $lanes],
+                    y: [v.y; $lanes],
+                    z: [v.z; $lanes],
+                }
+            }
+
+            // Loads up to LANES vectors from the front of src, leaving any
+            // remaining lanes zeroed.
+            pub fn gather(src: &[Vector3]) -> Self {
+                let mut out = Self::zero();
+                for (i, v) in src.iter().take($lanes).enumerate() {
+                    out.set_lane(i, v);
+                }
+                out
+            }
+
+            // Writes up to LANES vectors to the front of dst.
+            pub fn scatter(&self, dst: &mut [Vector3]) {
+                for (i, v) in dst.iter_mut().take($lanes).enumerate() {
+                    *v = self.get_lane(i);
+                }
+            }
+
+            pub fn get_lane(&self, i: usize) -> Vector3 {
+                Vector3 {
+                    x: self.x[i],
+                    y: self.y[i],
+                    z: self.z[i],
+                }
+            }
+
+            pub fn set_lane(&mut self, i: usize, v: &Vector3) {
+                self.x[i] = v.x;
+                self.y[i] = v.y;
+                self.z[i] = v.z;
+            }
+
+            pub fn norm(&mut self) {
+                *self = self.get_norm()
+            }
+
+            pub fn get_norm(&self) -> Self {
+                let mag = self.get_mag();
+                Self {
+                    x: std::array::from_fn(|i| self.x[i] / mag[i]),
+                    y: std::array::from_fn(|i| self.y[i] / mag[i]),
+                    z: std::array::from_fn(|i| self.z[i] / mag[i]),
+                }
+            }
+
+            pub fn get_mag(&self) -> [f32; $lanes] {
+                self.get_mag_sqr().map(f32::sqrt)
+            }
+
+            pub fn get_mag_sqr(&self) -> [f32; $lanes] {
+                self.dot(self)
+            }
+
+            pub fn dot(&self, rhs: &$name) -> [f32; $lanes] {
+                std::array::from_fn(|i| {
+                    self.x[i] * rhs.x[i] + self.y[i] * rhs.y[i] + self.z[i] * rhs.z[i]
+                })
+            }
+
+            pub fn cross(&self, rhs: &$name) -> Self {
+                Self {
+                    x: std::array::from_fn(|i| self.y[i] * rhs.z[i] - self.z[i] * rhs.y[i]),
+                    y: std::array::from_fn(|i| -(self.x[i] * rhs.z[i] - self.z[i] * rhs.x[i])),
+                    z: std::array::from_fn(|i| self.x[i] * rhs.y[i] - self.y[i] * rhs.x[i]),
+                }
+            }
+
+            pub fn get_angle(&self, rhs: &$name) -> [f32; $lanes] {
+                let dot = self.dot(rhs);
+                let mag_a = self.get_mag();
+                let mag_b = rhs.get_mag();
+                std::array::from_fn(|i| (dot[i] / (mag_a[i] * mag_b[i])).acos())
+            }
+        }
+
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: $name) -> Self {
+                Self {
+                    x: std::array::from_fn(|i| self.x[i] + rhs.x[i]),
+                    y: std::array::from_fn(|i| self.y[i] + rhs.y[i]),
+                    z: std::array::from_fn(|i| self.z[i] + rhs.z[i]),
+                }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: $name) -> Self {
+                Self {
+                    x: std::array::from_fn(|i| self.x[i] - rhs.x[i]),
+                    y: std::array::from_fn(|i| self.y[i] - rhs.y[i]),
+                    z: std::array::from_fn(|i| self.z[i] - rhs.z[i]),
+                }
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self {
+                    x: self.x.map(|v| -v),
+                    y: self.y.map(|v| -v),
+                    z: self.z.map(|v| -v),
+                }
+            }
+        }
+
+        impl Mul<f32> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f32) -> Self {
+                Self {
+                    x: self.x.map(|v| v * rhs),
+                    y: self.y.map(|v| v * rhs),
+                    z: self.z.map(|v| v * rhs),
+                }
+            }
+        }
+
+        impl MulAssign<f32> for $name {
+            fn mul_assign(&mut self, rhs: f32) {
+                *self = *self * rhs
+            }
+        }
+
+        impl Mul<$name> for f32 {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                rhs * self
+            }
+        }
+
+        impl Mul<Mat3> for $name {
+            type Output = Self;
+            fn mul(self, rhs: Mat3) -> Self {
+                Self {
+                    x: std::array::from_fn(|i| self.x[i] * rhs.r0c0 + self.y[i] * rhs.r1c0 + self.z[i] * rhs.r2c0),
+                    y: std::array::from_fn(|i| self.x[i] * rhs.r0c1 + self.y[i] * rhs.r1c1 + self.z[i] * rhs.r2c1),
+                    z: std::array::from_fn(|i| self.x[i] * rhs.r0c2 + self.y[i] * rhs.r1c2 + self.z[i] * rhs.r2c2),
+                }
+            }
+        }
+
+        impl MulAssign<Mat3> for $name {
+            fn mul_assign(&mut self, rhs: Mat3) {
+                *self = *self * rhs
+            }
+        }
+
+        // Transforms every lane as a point (w = 1) and keeps the xyz part,
+        // matching the xyz of Vector3 * Mat4.
+        impl Mul<Mat4> for $name {
+            type Output = Self;
+            fn mul(self, rhs: Mat4) -> Self {
+                Self {
+                    x: std::array::from_fn(|i| self.x[i] * rhs.r0c0 + self.y[i] * rhs.r1c0 + self.z[i] * rhs.r2c0 + rhs.r3c0),
+                    y: std::array::from_fn(|i| self.x[i] * rhs.r0c1 + self.y[i] * rhs.r1c1 + self.z[i] * rhs.r2c1 + rhs.r3c1),
+                    z: std::array::from_fn(|i| self.x[i] * rhs.r0c2 + self.y[i] * rhs.r1c2 + self.z[i] * rhs.r2c2 + rhs.r3c2),
+                }
+            }
+        }
+
+        impl MulAssign<Mat4> for $name {
+            fn mul_assign(&mut self, rhs: Mat4) {
+                *self = *self * rhs
+            }
+        }
+    };
+}
+
+vector3_wide!(Vector3x4, 4, 16);
+vector3_wide!(Vector3x8, 8, 32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    fn assert_bits_eq(a: &Vector3, b: &Vector3) {
+        assert_eq!([a.x, a.y, a.z].map(f32::to_bits), [b.x, b.y, b.z].map(f32::to_bits), "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn x8_mat4_matches_scalar_lanes() {
+        let mut rng = Rng(1);
+        for _ in 0..100 {
+            let m = rng.mat4();
+            let lanes: Vec<Vector3> = (0..Vector3x8::LANES).map(|_| rng.vec3()).collect();
+            let wide = Vector3x8::gather(&lanes) * m;
+            for (i, v) in lanes.iter().enumerate() {
+                assert_bits_eq(&wide.get_lane(i), &Vector3::from_vec4(&(*v * m)));
+            }
+        }
+    }
+
+    #[test]
+    fn x4_mat3_matches_scalar_lanes() {
+        let mut rng = Rng(2);
+        for _ in 0..100 {
+            let m = Mat3::from(std::array::from_fn::<f32, 9, _>(|_| rng.next()));
+            let lanes: Vec<Vector3> = (0..Vector3x4::LANES).map(|_| rng.vec3()).collect();
+            let wide = Vector3x4::gather(&lanes) * m;
+            for (i, v) in lanes.iter().enumerate() {
+                assert_bits_eq(&wide.get_lane(i), &(*v * m));
+            }
+        }
+    }
+
+    #[test]
+    fn transform_points_matches_transform_point() {
+        let mut rng = Rng(3);
+        let m = rng.mat4();
+        // Not a multiple of the lane count, so the tail goes through gather too.
+        let src: Vec<Vector3> = (0..21).map(|_| rng.vec3()).collect();
+        let mut dst = vec![Vector3::zero(); src.len()];
+        m.transform_points(&src, &mut dst);
+        for (s, d) in src.iter().zip(&dst) {
+            assert_bits_eq(d, &m.transform_point(s));
+        }
+    }
+}
@@ -1,8 +1,13 @@
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{fmt, str::FromStr};
 
-use crate::{mat4::Mat4, util::{is_equal, MATH_TOLERANCE}};
+use crate::{format::{self, ParseError}, mat4::Mat4, util::is_equal};
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use crate::simd;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "simd", repr(align(16)))]
 pub struct Vector4 {
     pub x: f32,
     pub y: f32,
@@ -10,6 +15,8 @@ pub struct Vector4 {
     pub w: f32
 }
 
+const _: () = assert!(std::mem::size_of::<Vector4>() == std::mem::size_of::<[f32; 4]>());
+
 impl Vector4 {
 
     pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
@@ -43,7 +50,10 @@ impl Vector4 {
     }
 
     pub fn dot(&self, rhs: &Vector4) -> f32 {
-        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::vec4_dot(self, rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w;
     }
 
     pub fn cross(&self, rhs: &Vector4) -> Self {
@@ -68,57 +78,75 @@ impl Vector4 {
         is_equal(self.z, rhs.z, epsilon) &&
         is_equal(self.w, rhs.w, epsilon)
     }
-}
 
-impl PartialEq for Vector4 {
-    fn eq(&self, rhs: &Self) -> bool{
-        self.is_equal(rhs, MATH_TOLERANCE)
+    pub fn iter(&self) -> std::slice::Iter<'_, f32> {
+        self.as_array().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, f32> {
+        self.as_mut_array().iter_mut()
+    }
+
+    pub fn as_array(&self) -> &[f32; 4] {
+        // SAFETY: Vector4 is repr(C) with four f32 fields and no padding, so x, y,
+        // z, w are the elements of a [f32; 4] in order. The simd feature only
+        // raises the alignment to 16, which still satisfies the array's.
+        unsafe { &*(self as *const Self as *const [f32; 4]) }
+    }
+
+    pub fn as_mut_array(&mut self) -> &mut [f32; 4] {
+        // SAFETY: as for as_array, and &mut self makes the borrow unique.
+        unsafe { &mut *(self as *mut Self as *mut [f32; 4]) }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        self.as_array()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
     }
 }
 
 impl Add for Vector4 {
     type Output = Self;
     fn add(self, rhs: Vector4) -> Self {
-        Vector4 {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::vec4_add(&self, &rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return Vector4 {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
             z: self.z + rhs.z,
             w: self.w + rhs.w,
-        }
+        };
     }    
 }
 
 impl AddAssign for Vector4 {
     fn add_assign(&mut self, rhs: Self) {
-        *self = Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-            w: self.w + rhs.w,
-        }
+        *self = *self + rhs
     }
 }
 
 impl Sub for Vector4 {
     type Output = Self;
     fn sub(self, rhs: Vector4) -> Self {
-        Vector4 {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::vec4_sub(&self, &rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return Vector4 {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
             z: self.z - rhs.z,
             w: self.w - rhs.w,
-        }
+        };
     }    
 }
 
 impl SubAssign for Vector4 {
     fn sub_assign(&mut self, rhs: Self) {
-        *self = Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-            w: self.w - rhs.w,
-        }
+        *self = *self - rhs
     }
 }
 
@@ -137,35 +165,36 @@ impl Neg for Vector4 {
 impl Mul<f32> for Vector4 {
     type Output = Self;
     fn mul(self, rhs: f32) -> Self {
-        Self {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::vec4_scale(&self, rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return Self {
             x: self.x * rhs,
-            y: self.y * rhs, 
+            y: self.y * rhs,
             z: self.z * rhs,
             w: self.w * rhs,
-        }
+        };
     }
 }
 
 impl MulAssign<f32> for Vector4 {
     fn mul_assign(&mut self, rhs: f32) {
-        *self = Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs,
-            w: self.w * rhs
-        }
+        *self = *self * rhs
     }
 }
 
 impl Mul<Mat4> for Vector4 {
     type Output = Vector4;
     fn mul(self, rhs: Mat4) -> Vector4 {
-        Vector4 {
-            x: self.x * rhs.r0c0 + self.y * rhs.r1c0 + self.z * rhs.r2c0 + rhs.r3c0,
-            y: self.x * rhs.r0c1 + self.y * rhs.r1c1 + self.z * rhs.r2c1 + rhs.r3c1,
-            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z + rhs.r2c2 + rhs.r3c2,
-            w: self.x * rhs.r0c3 + self.y * rhs.r1c3 + self.z + rhs.r2c3 + rhs.r3c3,
-        }
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return simd::vec4_mul_mat4(&self, &rhs);
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return Vector4 {
+            x: self.x * rhs.r0c0 + self.y * rhs.r1c0 + self.z * rhs.r2c0 + self.w * rhs.r3c0,
+            y: self.x * rhs.r0c1 + self.y * rhs.r1c1 + self.z * rhs.r2c1 + self.w * rhs.r3c1,
+            z: self.x * rhs.r0c2 + self.y * rhs.r1c2 + self.z * rhs.r2c2 + self.w * rhs.r3c2,
+            w: self.x * rhs.r0c3 + self.y * rhs.r1c3 + self.z * rhs.r2c3 + self.w * rhs.r3c3,
+        };
     }
 }
 /*/
@@ -190,4 +219,69 @@ impl MulAssign<Quat> for Vector4 {
             w: 0.0,
         }
     }
-}*/
\ No newline at end of file
+}*/
+
+impl From<[f32; 4]> for Vector4 {
+    fn from(a: [f32; 4]) -> Self {
+        Vector4::new(a[0], a[1], a[2], a[3])
+    }
+}
+
+impl From<Vector4> for [f32; 4] {
+    fn from(v: Vector4) -> Self {
+        [v.x, v.y, v.z, v.w]
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for Vector4 {
+    fn from(t: (f32, f32, f32, f32)) -> Self {
+        Vector4::new(t.0, t.1, t.2, t.3)
+    }
+}
+
+impl From<Vector4> for (f32, f32, f32, f32) {
+    fn from(v: Vector4) -> Self {
+        (v.x, v.y, v.z, v.w)
+    }
+}
+
+impl Index<usize> for Vector4 {
+    type Output = f32;
+    fn index(&self, i: usize) -> &f32 {
+        &self.as_array()[i]
+    }
+}
+
+impl IndexMut<usize> for Vector4 {
+    fn index_mut(&mut self, i: usize) -> &mut f32 {
+        &mut self.as_mut_array()[i]
+    }
+}
+
+impl fmt::Display for Vector4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::fmt_components(f, self.as_slice())
+    }
+}
+
+impl FromStr for Vector4 {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        format::parse_components::<4>(s).map(Vector4::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_views_alias_the_fields() {
+        let mut v = Vector4::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(v.as_array(), &[1.0, 2.0, 3.0, 4.0]);
+        v.as_mut_array()[3] = 0.0;
+        v.as_mut_slice()[1] = -2.0;
+        assert_eq!((v.x, v.y, v.z, v.w), (1.0, -2.0, 3.0, 0.0));
+        assert_eq!(v.as_slice(), [1.0, -2.0, 3.0, 0.0]);
+    }
+}